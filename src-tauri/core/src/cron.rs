@@ -0,0 +1,216 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, TimeZone, Timelike, Utc};
+
+// Five-field cron expressions: minute hour day-of-month month day-of-week. Fields take
+// `*`, numbers, ranges (`19-22`), lists (`1,15`) and steps (`*/30`, `9-17/2`). Day of week
+// runs 0 (Sunday) to 6, with 7 also meaning Sunday. As in standard cron, when both day
+// fields are restricted a time matches if either one does.
+#[derive(Debug, Clone)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+// How far ahead to look for a match before deciding the expression never fires
+const SEARCH_DAYS: i64 = 366 * 5;
+
+fn parse_number(value: &str, min: u32, max: u32, field: &str) -> Result<u32, String> {
+    let n: u32 = value.parse().map_err(|_| format!("Invalid {} '{}'", field, value))?;
+    if n < min || n > max {
+        return Err(format!("{} {} is outside {}-{}", field, n, min, max));
+    }
+    Ok(n)
+}
+
+fn parse_field(value: &str, min: u32, max: u32, field: &str) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in value.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, parse_number(step, 1, max, field)?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_number(a, min, max, field)?, parse_number(b, min, max, field)?)
+        } else {
+            let start = parse_number(range, min, max, field)?;
+            // `5/15` means every 15 starting at 5
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("Invalid {} range '{}'", field, range));
+        }
+        for n in (start..=end).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("Cron expression '{}' must have 5 fields", expr.trim()));
+        };
+        let mut weekdays = parse_field(weekday, 0, 7, "day of week")?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(CronExpr {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day of month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    fn day_matches<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> bool {
+        let day = self.days & (1 << time.day()) != 0;
+        let weekday = self.weekdays & (1 << time.weekday().num_days_from_sunday()) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+
+    // The first matching minute strictly after `after`
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let limit = after.clone() + Duration::days(SEARCH_DAYS);
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        while time < limit {
+            if self.months & (1 << time.month()) == 0 || !self.day_matches(&time) {
+                time = time.with_hour(0)?.with_minute(0)? + Duration::days(1);
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+}
+
+// Fixed UTC offset for a schedule, defaulting to the machine's. Indonesian zones have no
+// DST, so WIB is 420, WITA 480 and WIT 540.
+pub fn offset(utc_offset_minutes: Option<i32>) -> Result<FixedOffset, String> {
+    match utc_offset_minutes {
+        Some(minutes) => FixedOffset::east_opt(minutes * 60).ok_or_else(|| format!("Invalid UTC offset {} minutes", minutes)),
+        None => Ok(*Local::now().offset()),
+    }
+}
+
+// Time until the expression next fires in the given zone
+pub fn delay_until_next(expr: &CronExpr, offset: FixedOffset) -> Option<std::time::Duration> {
+    let now = Utc::now().with_timezone(&offset);
+    let next = expr.next_after(&now)?;
+    (next - now).to_std().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2026-01-01 is a Thursday
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expr: &str, after: &str) -> Option<DateTime<Utc>> {
+        CronExpr::parse(expr).unwrap().next_after(&utc(after))
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expr in ["* * * *", "* * * * * *", "60 * * * *", "* 24 * * *", "* * 0 * *", "* * * 13 *", "* * * * 8", "*/0 * * * *", "30-10 * * * *", "a * * * *", "1,,2 * * * *"] {
+            assert!(CronExpr::parse(expr).is_err(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn next_is_strictly_after_and_drops_seconds() {
+        assert_eq!(next("*/30 * * * *", "2026-01-01T10:07:00Z"), Some(utc("2026-01-01T10:30:00Z")));
+        assert_eq!(next("*/30 * * * *", "2026-01-01T10:30:00Z"), Some(utc("2026-01-01T11:00:00Z")));
+        assert_eq!(next("*/30 * * * *", "2026-01-01T10:29:59Z"), Some(utc("2026-01-01T10:30:00Z")));
+    }
+
+    #[test]
+    fn steps_start_from_the_range_or_number() {
+        assert_eq!(next("0 9-17/2 * * *", "2026-01-01T10:00:00Z"), Some(utc("2026-01-01T11:00:00Z")));
+        assert_eq!(next("0 9-17/2 * * *", "2026-01-01T17:00:00Z"), Some(utc("2026-01-02T09:00:00Z")));
+        assert_eq!(next("5/15 * * * *", "2026-01-01T10:50:00Z"), Some(utc("2026-01-01T11:05:00Z")));
+    }
+
+    #[test]
+    fn ranges_and_lists() {
+        assert_eq!(next("0 19-22 * * *", "2026-01-01T22:30:00Z"), Some(utc("2026-01-02T19:00:00Z")));
+        assert_eq!(next("0 0 1,15 * *", "2026-01-02T00:00:00Z"), Some(utc("2026-01-15T00:00:00Z")));
+        assert_eq!(next("0 0 1,15 * *", "2026-01-15T00:00:00Z"), Some(utc("2026-02-01T00:00:00Z")));
+        assert_eq!(next("0 0 1 3-4 *", "2026-01-01T00:00:00Z"), Some(utc("2026-03-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn seven_is_sunday() {
+        let sunday = Some(utc("2026-01-04T08:00:00Z"));
+        assert_eq!(next("0 8 * * 7", "2026-01-01T00:00:00Z"), sunday);
+        assert_eq!(next("0 8 * * 0", "2026-01-01T00:00:00Z"), sunday);
+        assert_eq!(next("0 8 * * 5-7", "2026-01-03T09:00:00Z"), sunday);
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // Only day of month: the 13th
+        assert_eq!(next("0 0 13 * *", "2026-01-01T00:00:00Z"), Some(utc("2026-01-13T00:00:00Z")));
+        // Only day of week: Fridays
+        assert_eq!(next("0 0 * * 5", "2026-01-01T00:00:00Z"), Some(utc("2026-01-02T00:00:00Z")));
+        // Both: the 13th or any Friday, whichever comes first
+        assert_eq!(next("0 0 13 * 5", "2026-01-01T00:00:00Z"), Some(utc("2026-01-02T00:00:00Z")));
+        assert_eq!(next("0 0 13 * 5", "2026-01-09T00:00:00Z"), Some(utc("2026-01-13T00:00:00Z")));
+        // A weekday keeps an impossible date firing
+        assert_eq!(next("0 0 30 2 1", "2026-02-01T00:00:00Z"), Some(utc("2026-02-02T00:00:00Z")));
+    }
+
+    #[test]
+    fn expressions_that_never_fire() {
+        assert_eq!(next("0 0 30 2 *", "2026-01-01T00:00:00Z"), None);
+        assert_eq!(next("0 0 31 4,6,9,11 *", "2026-01-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn leap_day_is_found_within_the_search_window() {
+        assert_eq!(next("0 0 29 2 *", "2026-01-01T00:00:00Z"), Some(utc("2028-02-29T00:00:00Z")));
+    }
+
+    #[test]
+    fn fields_match_in_the_given_offset() {
+        // 10:00 WIB, so 09:00 WIB has passed for today
+        let wib = offset(Some(420)).unwrap();
+        let after = utc("2026-01-01T03:00:00Z").with_timezone(&wib);
+        let expr = CronExpr::parse("0 9 * * *").unwrap();
+        assert_eq!(expr.next_after(&after).unwrap(), utc("2026-01-02T02:00:00Z"));
+
+        // Friday 23:00 at UTC-5 is already Saturday in UTC
+        let est = offset(Some(-300)).unwrap();
+        let after = utc("2026-01-01T00:00:00Z").with_timezone(&est);
+        let expr = CronExpr::parse("0 23 * * 5").unwrap();
+        assert_eq!(expr.next_after(&after).unwrap(), utc("2026-01-03T04:00:00Z"));
+    }
+
+    #[test]
+    fn offset_bounds() {
+        assert_eq!(offset(Some(540)).unwrap().local_minus_utc(), 540 * 60);
+        assert!(offset(Some(24 * 60)).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+// Embeddable API for the livekenceng member backend and Shopee. The desktop app's Tauri
+// commands send their requests through these clients and run showcase schedules on the
+// loop in `scheduler`; nothing in this crate depends on Tauri, so a CLI or bot server can
+// be built on the same code.
+
+pub mod models;
+
+#[cfg(feature = "members")]
+pub mod members;
+
+#[cfg(feature = "shopee")]
+pub mod shopee;
+
+#[cfg(feature = "scheduler")]
+pub mod cron;
+
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
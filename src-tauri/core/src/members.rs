@@ -0,0 +1,183 @@
+use serde::Deserialize;
+use std::fmt;
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://livekenceng.com";
+pub const STAGING_BASE_URL: &str = "https://staging.livekenceng.com";
+
+#[derive(Debug)]
+pub enum Error {
+    Request(String),
+    // Non-success status, or a plan/upgrade refusal; `body` is the raw response for the
+    // caller to classify
+    Status { status: u16, body: String },
+    Parse { message: String, body: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Request(message) => write!(f, "{}", message),
+            Error::Status { status, body } => write!(f, "HTTP {}: {}", status, body),
+            Error::Parse { message, body } => write!(f, "Failed to parse response: {} - {}", message, body),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Message fragments the backend uses when a member's plan doesn't include a feature
+const UPGRADE_PATTERNS: &[&str] = &[
+    "upgrade",
+    "subscription",
+    "your plan",
+    "not included in",
+    "not available on",
+    "paket",
+    "langganan",
+];
+
+pub fn is_upgrade_message(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    UPGRADE_PATTERNS.iter().any(|p| lower.contains(p))
+}
+
+// Decodes a response returned by `Client::send`. Entitlement failures come back as
+// success=false with a 2xx status, so they are reported as `Error::Status` like a failed
+// status is.
+pub fn decode<T: for<'de> Deserialize<'de>>(status: u16, text: &str) -> Result<T, Error> {
+    let refused = || Error::Status {
+        status,
+        body: text.to_string(),
+    };
+    if !(200..300).contains(&status) {
+        return Err(refused());
+    }
+    if let Ok(raw) = serde_json::from_str::<serde_json::Value>(text) {
+        let message = raw["message"].as_str().unwrap_or_default();
+        if raw["success"] == serde_json::Value::Bool(false) && is_upgrade_message(message) {
+            return Err(refused());
+        }
+    }
+    serde_json::from_str(text).map_err(|e| Error::Parse {
+        message: e.to_string(),
+        body: text.to_string(),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client::with_base_url(DEFAULT_BASE_URL)
+    }
+}
+
+impl Client {
+    pub fn with_base_url(base_url: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    // Fails requests that take longer than `timeout` overall
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        // Building with only a timeout set can't fail in practice; keep the default client if it does
+        if let Ok(http) = reqwest::Client::builder().timeout(timeout).build() {
+            self.http = http;
+        }
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Sends a request and returns the raw status and body without interpreting them
+    pub async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<&serde_json::Value>,
+        query: Option<&str>,
+    ) -> Result<(u16, String), Error> {
+        let mut url = format!("{}{}", self.base_url, endpoint);
+        if let Some(query) = query {
+            url = format!("{}?{}", url, query);
+        }
+
+        let mut request = match method {
+            "GET" => self.http.get(&url),
+            "POST" => self.http.post(&url),
+            "PUT" => self.http.put(&url),
+            "DELETE" => self.http.delete(&url),
+            _ => return Err(Error::Request("Invalid HTTP method".to_string())),
+        };
+        if let Some(json_body) = body {
+            request = request.json(json_body);
+        }
+        if method != "GET" || body.is_some() {
+            request = request.header("Content-Type", "application/json");
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Request(format!("Request failed: {}", e)))?;
+        let status = response.status().as_u16();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| Error::Request(format!("Failed to read response: {}", e)))?;
+        Ok((status, text))
+    }
+
+    // Sends a request and decodes the response as `T`
+    pub async fn request<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: Option<&serde_json::Value>,
+        query: Option<&str>,
+    ) -> Result<T, Error> {
+        let (status, text) = self.send(method, endpoint, body, query).await?;
+        decode(status, &text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ApiResponse;
+
+    #[test]
+    fn failed_status_keeps_status_and_body() {
+        let result = decode::<ApiResponse<serde_json::Value>>(404, "not found");
+        assert!(matches!(result, Err(Error::Status { status: 404, body }) if body == "not found"));
+    }
+
+    #[test]
+    fn upgrade_refusal_is_reported_as_status() {
+        let body = r#"{"success":false,"message":"Upgrade your plan to use this"}"#;
+        let result = decode::<ApiResponse<serde_json::Value>>(200, body);
+        assert!(matches!(result, Err(Error::Status { status: 200, .. })));
+    }
+
+    #[test]
+    fn other_refusals_are_decoded() {
+        let body = r#"{"success":false,"message":"Set not found"}"#;
+        let response = decode::<ApiResponse<serde_json::Value>>(200, body).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.message.as_deref(), Some("Set not found"));
+    }
+
+    #[test]
+    fn unparseable_body_is_a_parse_error() {
+        assert!(matches!(decode::<ApiResponse<serde_json::Value>>(200, "<html>"), Err(Error::Parse { .. })));
+    }
+}
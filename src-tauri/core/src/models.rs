@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+// Envelope used by every member API response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    #[serde(flatten)]
+    pub data: Option<T>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct User {
+    pub id: i32,
+    pub email: String,
+    pub telegram_username: Option<String>,
+    pub expiry_date: Option<String>,
+    pub machine_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedeemLicenseResponse {
+    pub expiry_date: Option<String>,
+    pub days_added: Option<i32>,
+    pub is_new_member: bool,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopeeAccount {
+    pub id: i32,
+    pub name: String,
+    pub is_active: bool,
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub cookie: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShopeeAccountsResponse {
+    pub data: Vec<ShopeeAccount>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Niche {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub product_sets: Vec<ProductSet>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NichesResponse {
+    pub niches: Vec<Niche>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSet {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub niche_id: Option<i32>,
+    #[serde(default)]
+    pub items: Vec<ProductSetItem>,
+    // Admin-curated sets can be used for live sessions but not edited
+    #[serde(default, alias = "read_only")]
+    pub is_read_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSetsResponse {
+    pub product_sets: Vec<ProductSet>,
+}
+
+// Vendor-published starter catalogue; members copy it into their own sets
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSetTemplate {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub items: Vec<ProductSetItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSetTemplatesResponse {
+    pub templates: Vec<ProductSetTemplate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductSetItem {
+    pub id: i32,
+    pub url: String,
+    pub shop_id: Option<i64>,
+    pub item_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionIdsResponse {
+    pub session_ids: Vec<String>,
+}
+
+// A viewer comment in a live session, as relayed by the shopee-live API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveComment {
+    pub id: String,
+    pub user_id: i64,
+    #[serde(default)]
+    pub username: String,
+    pub content: String,
+    #[serde(default)]
+    pub timestamp: u64,
+    // Comments posted by the streaming account itself
+    #[serde(default)]
+    pub is_host: bool,
+}
+
+// Comments after `cursor`; pass the returned cursor back to get only newer ones
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveCommentsResponse {
+    pub comments: Vec<LiveComment>,
+    pub cursor: Option<String>,
+}
+
+// A product currently in a live session's showcase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveShowcaseItem {
+    pub shop_id: i64,
+    pub item_id: i64,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveShowcaseResponse {
+    pub items: Vec<LiveShowcaseItem>,
+}
+
+// RTMP ingest details for streaming a session from OBS. OBS takes `server_url` and
+// `stream_key` separately; `push_url` is the two joined.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivePushUrl {
+    pub push_url: String,
+    pub server_url: String,
+    pub stream_key: String,
+}
+
+impl LivePushUrl {
+    // The key is everything after the last path separator, query string included
+    pub fn from_push_url(push_url: &str) -> Option<Self> {
+        let (server_url, stream_key) = push_url.trim().rsplit_once('/')?;
+        if !server_url.starts_with("rtmp") || stream_key.is_empty() {
+            return None;
+        }
+        Some(LivePushUrl {
+            push_url: push_url.trim().to_string(),
+            server_url: server_url.to_string(),
+            stream_key: stream_key.to_string(),
+        })
+    }
+}
+
+// QR Code structures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopeeQRData {
+    pub qrcode_id: String,
+    pub qrcode_base64: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QRStatusData {
+    pub qrcode_token: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShopeeAccountInfo {
+    pub userid: i64,
+    pub username: String,
+    #[serde(default)]
+    pub nickname: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+// Item as returned by Shopee's item API. Prices are in micro-units (x100000).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopeeItem {
+    pub itemid: i64,
+    pub shopid: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub price: i64,
+    #[serde(default)]
+    pub price_before_discount: Option<i64>,
+    #[serde(default)]
+    pub stock: i64,
+    #[serde(default)]
+    pub historical_sold: i64,
+    #[serde(default)]
+    pub image: Option<String>,
+    // "normal", "deleted", "banned", "unlisted"...
+    #[serde(default)]
+    pub item_status: Option<String>,
+    #[serde(default)]
+    pub item_rating: Option<ShopeeItemRating>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopeeItemRating {
+    #[serde(default)]
+    pub rating_star: f64,
+}
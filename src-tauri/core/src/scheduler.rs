@@ -0,0 +1,105 @@
+use chrono::FixedOffset;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+use crate::cron::{self, CronExpr};
+
+pub const MIN_INTERVAL_MINUTES: u64 = 5;
+
+// "Replace the showcase with set X every N minutes for account Y". Schedules follow the
+// account rather than a session, pushing to whichever live is active when they fire.
+// With `cron` set (e.g. "*/30 19-22 * * *" for every half hour from 19:00 to 23:00) the
+// schedule fires on matching minutes in `utc_offset_minutes` (machine time when unset)
+// and `interval_minutes` is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleSpec {
+    pub shopee_account_id: i32,
+    pub product_set_id: i32,
+    #[serde(default)]
+    pub interval_minutes: u64,
+    #[serde(default)]
+    pub cron: Option<String>,
+    #[serde(default)]
+    pub utc_offset_minutes: Option<i32>,
+}
+
+// When a schedule fires next
+pub enum Timing {
+    Interval(Duration),
+    Cron(CronExpr, FixedOffset),
+}
+
+impl Timing {
+    // Also normalizes the spec: a blank cron is dropped and short intervals are raised
+    // to the minimum
+    pub fn from_spec(spec: &mut ScheduleSpec) -> Result<Self, String> {
+        match spec.cron.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            Some(expr) => {
+                let cron = CronExpr::parse(expr)?;
+                let offset = cron::offset(spec.utc_offset_minutes)?;
+                if cron.next_after(&chrono::Utc::now().with_timezone(&offset)).is_none() {
+                    return Err(format!("Cron expression '{}' never fires", expr));
+                }
+                Ok(Timing::Cron(cron, offset))
+            }
+            None => {
+                spec.cron = None;
+                spec.interval_minutes = spec.interval_minutes.max(MIN_INTERVAL_MINUTES);
+                Ok(Timing::Interval(Duration::from_secs(spec.interval_minutes * 60)))
+            }
+        }
+    }
+}
+
+// What a running schedule needs from the program hosting it: pushing the set, waiting
+// out pauses, and keeping its own records and listeners up to date
+pub trait Host: Send + Sync {
+    // Called with the wait before every run
+    fn next_run(&self, wait: Duration);
+
+    // The wait between interval runs, which a host may stretch while the schedule is paused
+    fn countdown(&self, wait: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(wait)
+    }
+
+    // Holds a due run back until the host is ready for it
+    fn before_run(&self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    // Pushes the set to the account's active live, returning the session it went to
+    fn push(&self, spec: &ScheduleSpec) -> impl Future<Output = (Option<String>, Result<(), String>)> + Send;
+
+    fn ran(&self, spec: &ScheduleSpec, session_id: Option<String>, error: Option<String>);
+
+    // Called once a cron schedule has no matching minutes left
+    fn finished(&self);
+}
+
+// Interval schedules run right away and then every interval; cron schedules wait for
+// their next matching minute. Either runs until the task is dropped.
+pub async fn run<H: Host>(host: &H, timing: &Timing, spec: &ScheduleSpec) {
+    let mut first = true;
+    loop {
+        let wait = match timing {
+            Timing::Interval(_) if first => Duration::ZERO,
+            Timing::Interval(interval) => *interval,
+            Timing::Cron(cron, offset) => match cron::delay_until_next(cron, *offset) {
+                Some(wait) => wait,
+                None => break,
+            },
+        };
+        first = false;
+        host.next_run(wait);
+        match timing {
+            Timing::Interval(_) => host.countdown(wait).await,
+            Timing::Cron(..) => tokio::time::sleep(wait).await,
+        }
+
+        host.before_run().await;
+        let (session_id, result) = host.push(spec).await;
+        host.ran(spec, session_id, result.err());
+    }
+    host.finished();
+}
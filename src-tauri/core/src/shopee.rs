@@ -0,0 +1,572 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::time::Duration;
+
+use crate::models::{QRStatusData, ShopeeAccountInfo, ShopeeItem, ShopeeQRData};
+
+pub const DESKTOP_USER_AGENT: &str =
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36";
+
+// Login requests have to look like they come from the web login page on a Mac
+pub const LOGIN_USER_AGENT: &str =
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36";
+const DEVICE_SZ_FINGERPRINT: &str = "Eci2goR2Eb+MxmnU3gKNBQ==|U4oBUb+lXscV+6i8liMV/0lL2YjLYCw6ZgvAg3AVpmc=|WYw++VlzfflxOp1j|08|3";
+const SECURITY_DEVICE_FINGERPRINT: &str = "vRr1CLNxsx/YWsLqNCAeGQ==|3UI1dXTNSZRQkHYpKyn3MGV94+BUZv/37sidjlGODXY=|77wWZwahX4xYgzK9BHP57A==";
+
+// Failed Shopee call, keeping the HTTP status and Shopee error code for classification
+#[derive(Debug)]
+pub struct ApiFailure {
+    pub http_status: Option<u16>,
+    pub error_code: Option<i32>,
+    pub message: String,
+    // Shopee couldn't be reached or the response was cut off, so nothing was decided
+    pub transport: bool,
+}
+
+impl ApiFailure {
+    pub fn message(message: String) -> Self {
+        ApiFailure {
+            http_status: None,
+            error_code: None,
+            message,
+            transport: false,
+        }
+    }
+
+    pub fn transport(message: String) -> Self {
+        ApiFailure {
+            transport: true,
+            ..ApiFailure::message(message)
+        }
+    }
+}
+
+impl fmt::Display for ApiFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ApiFailure {}
+
+impl From<ApiFailure> for String {
+    fn from(failure: ApiFailure) -> Self {
+        failure.message
+    }
+}
+
+// Shopee responses share the error/error_msg/data shape
+#[derive(Debug, Serialize, Deserialize)]
+struct ShopeeResponse<T> {
+    error: i32,
+    error_msg: Option<String>,
+    data: Option<T>,
+}
+
+// Shopee's answer to a login or OTP request, with the session cookies it set. Captchas and
+// extra verification come back as a non-zero `error` with details in `data`.
+#[derive(Debug, Clone)]
+pub struct LoginResponse {
+    pub error: i32,
+    pub error_msg: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub set_cookie_headers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopItem {
+    pub itemid: i64,
+    pub shopid: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopItemsPage {
+    error: Option<i32>,
+    items: Option<Vec<ShopItem>>,
+    nomore: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShopDetail {
+    pub shopid: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub follower_count: i64,
+    pub account: Option<ShopDetailAccount>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShopDetailAccount {
+    pub portrait: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    // "relevancy", "sales", "price" or "ctime"
+    #[serde(default)]
+    pub sort_by: Option<String>,
+    // Micro-units, like prices
+    #[serde(default)]
+    pub min_price: Option<i64>,
+    #[serde(default)]
+    pub max_price: Option<i64>,
+    #[serde(default)]
+    pub page: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchItem {
+    pub itemid: i64,
+    pub shopid: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub price: i64,
+    #[serde(default)]
+    pub historical_sold: i64,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub shop_location: Option<String>,
+    #[serde(default)]
+    pub shop_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchPage {
+    error: Option<i32>,
+    items: Option<Vec<SearchHit>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    item_basic: Option<SearchItem>,
+}
+
+// live.shopee.co.id uses its own err_code/err_msg envelope
+#[derive(Debug, Deserialize)]
+struct LiveResponse<T> {
+    err_code: i32,
+    err_msg: Option<String>,
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LiveSessionData {
+    session: LiveStats,
+}
+
+// Counters for one live session as reported by Shopee
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LiveStats {
+    pub session_id: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+    // 1 while streaming; anything else means the session has ended or not started
+    #[serde(default)]
+    pub status: i32,
+    #[serde(default, alias = "ccu")]
+    pub viewers: i64,
+    #[serde(default, alias = "total_views")]
+    pub views: i64,
+    #[serde(default, alias = "like_cnt")]
+    pub likes: i64,
+    #[serde(default, alias = "share_cnt")]
+    pub shares: i64,
+    #[serde(default, alias = "sold_cnt", alias = "item_sold")]
+    pub items_sold: i64,
+}
+
+impl LiveStats {
+    pub fn is_live(&self) -> bool {
+        self.status == 1
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OngoingSessionData {
+    #[serde(default)]
+    session_id: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+}
+
+impl Client {
+    // Requests go through `proxy` when given (http, https or socks5 URL)
+    pub fn new(user_agent: &str, proxy: Option<&str>) -> Result<Self, String> {
+        Client::with_timeout(user_agent, proxy, None)
+    }
+
+    pub fn with_timeout(user_agent: &str, proxy: Option<&str>, timeout: Option<Duration>) -> Result<Self, String> {
+        let mut builder = reqwest::Client::builder().user_agent(user_agent);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(|e| format!("Invalid proxy: {}", e))?);
+        }
+        let http = builder.build().map_err(|e| format!("Failed to create client: {}", e))?;
+        Ok(Client { http })
+    }
+
+    // Underlying HTTP client, for Shopee endpoints not wrapped here
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    pub async fn generate_qr(&self) -> Result<ShopeeQRData, ApiFailure> {
+        let request = self.http.get("https://shopee.co.id/api/v2/authentication/gen_qrcode");
+        let (_, data) = self.call(with_web_headers(request)).await?;
+        data.ok_or_else(|| ApiFailure::message("Invalid response from Shopee API".to_string()))
+    }
+
+    pub async fn qr_status(&self, qrcode_id: &str) -> Result<QRStatusData, ApiFailure> {
+        let url = format!(
+            "https://shopee.co.id/api/v2/authentication/qrcode_status?qrcode_id={}",
+            urlencoding::encode(qrcode_id)
+        );
+        let (_, data) = self.call(with_web_headers(self.http.get(&url))).await?;
+        data.ok_or_else(|| ApiFailure::message("No data in response".to_string()))
+    }
+
+    pub async fn qr_login(&self, qrcode_token: &str) -> Result<LoginResponse, ApiFailure> {
+        let payload = serde_json::json!({
+            "qrcode_token": qrcode_token,
+            "device_sz_fingerprint": DEVICE_SZ_FINGERPRINT,
+            "client_identifier": {
+                "security_device_fingerprint": SECURITY_DEVICE_FINGERPRINT
+            }
+        });
+        let request = self
+            .http
+            .post("https://shopee.co.id/api/v2/authentication/qrcode_login")
+            .header("X-Sz-Sdk-Version", "3.3.0-2&1.6.6")
+            .header("Af-Ac-Enc-Sz-Token", "LKhci5u+IZWG5pLadxISkw==|KnTeDESKZrvJIH7v/k87MkjZgllq1OIb4WNTbBMjqiX47UKmLiYT/5gQveB5AcnnWrX7QOH0K22Cyg==|WYw++VlzfflxOp1j|08|3")
+            .header("Sec-Ch-Ua-Platform", "\"macOS\"")
+            .json(&payload);
+        login_call(with_login_headers(request, "https://shopee.co.id/buyer/login/qr?next=https%3A%2F%2Fshopee.co.id%2F")).await
+    }
+
+    // `login` is an email address, phone number or username
+    pub async fn password_login(&self, login: &str, password: &str) -> Result<LoginResponse, ApiFailure> {
+        let is_phone = !login.is_empty() && login.trim_start_matches('+').chars().all(|c| c.is_ascii_digit());
+        let field = if login.contains('@') {
+            "email"
+        } else if is_phone {
+            "phone"
+        } else {
+            "username"
+        };
+        let payload = serde_json::json!({
+            field: login,
+            "password": hash_password(password),
+            "support_ivs": true,
+            "client_identifier": {
+                "security_device_fingerprint": SECURITY_DEVICE_FINGERPRINT
+            }
+        });
+        let request = self.http.post("https://shopee.co.id/api/v2/authentication/login").json(&payload);
+        login_call(with_login_headers(request, "https://shopee.co.id/buyer/login?next=https%3A%2F%2Fshopee.co.id%2F")).await
+    }
+
+    // Texts a login code to `phone`; the returned data carries the `otp_seed` to log in with
+    pub async fn send_otp(&self, phone: &str) -> Result<LoginResponse, ApiFailure> {
+        let payload = serde_json::json!({
+            "phone": phone,
+            "operation": "login",
+            "channel": "sms",
+            "support_ivs": true,
+            "client_identifier": {
+                "security_device_fingerprint": SECURITY_DEVICE_FINGERPRINT
+            }
+        });
+        let request = self.http.post("https://shopee.co.id/api/v4/otp/send_vcode").json(&payload);
+        login_call(with_login_headers(request, "https://shopee.co.id/buyer/login/otp")).await
+    }
+
+    pub async fn otp_login(&self, phone: &str, otp: &str, otp_seed: Option<&str>) -> Result<LoginResponse, ApiFailure> {
+        let mut payload = serde_json::json!({
+            "phone": phone,
+            "otp": otp,
+            "support_ivs": true,
+            "client_identifier": {
+                "security_device_fingerprint": SECURITY_DEVICE_FINGERPRINT
+            }
+        });
+        if let Some(seed) = otp_seed {
+            payload["otp_seed"] = serde_json::Value::String(seed.to_string());
+        }
+        let request = self.http.post("https://shopee.co.id/api/v2/authentication/vcode_login").json(&payload);
+        login_call(with_login_headers(request, "https://shopee.co.id/buyer/login/otp")).await
+    }
+
+    // Account behind the cookie, plus any Set-Cookie headers Shopee sent back
+    pub async fn account_info(&self, cookies: &str) -> Result<(ShopeeAccountInfo, Vec<String>), ApiFailure> {
+        let request = self
+            .http
+            .get("https://shopee.co.id/api/v4/account/basic/get_account_info")
+            .header("Cookie", cookies)
+            .header("Accept", "application/json")
+            .header("Origin", "https://shopee.co.id")
+            .header("Referer", "https://shopee.co.id/");
+        let (set_cookie_headers, data) = self.call(request).await?;
+        let info = data.ok_or_else(|| ApiFailure::message("No account info in response".to_string()))?;
+        Ok((info, set_cookie_headers))
+    }
+
+    // Item details. Shopee answers with an error code or no data for removed items.
+    pub async fn item(&self, shop_id: i64, item_id: i64, cookies: Option<&str>) -> Result<Option<ShopeeItem>, ApiFailure> {
+        let url = format!("https://shopee.co.id/api/v4/item/get?itemid={}&shopid={}", item_id, shop_id);
+        let mut request = with_web_headers(self.http.get(&url));
+        if let Some(cookies) = cookies {
+            request = request.header("Cookie", cookies);
+        }
+        let (_, data) = self.call(request).await?;
+        Ok(data)
+    }
+
+    // One page of a shop's listing, with whether more pages follow
+    pub async fn shop_items(&self, shop_id: i64, offset: usize, limit: usize, cookies: Option<&str>) -> Result<(Vec<ShopItem>, bool), ApiFailure> {
+        let url = format!(
+            "https://shopee.co.id/api/v4/shop/search_items?filter_sold_out=1&limit={}&offset={}&order=desc&shopid={}&sort_by=pop&use_case=1",
+            limit, offset, shop_id
+        );
+        let mut request = with_web_headers(self.http.get(&url));
+        if let Some(cookies) = cookies {
+            request = request.header("Cookie", cookies);
+        }
+        let page: ShopItemsPage = fetch_json(request).await?;
+        check_error_code(page.error)?;
+        let items = page.items.unwrap_or_default();
+        let has_more = !page.nomore.unwrap_or(items.len() < limit) && !items.is_empty();
+        Ok((items, has_more))
+    }
+
+    pub async fn search_items(&self, keyword: &str, filters: &SearchFilters, cookies: Option<&str>) -> Result<Vec<SearchItem>, ApiFailure> {
+        let limit = filters.limit.unwrap_or(30).clamp(1, 60);
+        let mut url = format!(
+            "https://shopee.co.id/api/v4/search/search_items?by={}&keyword={}&limit={}&newest={}&order=desc&page_type=search&scenario=PAGE_GLOBAL_SEARCH&version=2",
+            urlencoding::encode(filters.sort_by.as_deref().unwrap_or("relevancy")),
+            urlencoding::encode(keyword),
+            limit,
+            filters.page.unwrap_or(0) * limit
+        );
+        if let Some(min) = filters.min_price {
+            url.push_str(&format!("&price_min={}", min / 100_000));
+        }
+        if let Some(max) = filters.max_price {
+            url.push_str(&format!("&price_max={}", max / 100_000));
+        }
+
+        let mut request = with_web_headers(self.http.get(&url));
+        if let Some(cookies) = cookies {
+            request = request.header("Cookie", cookies);
+        }
+        let page: SearchPage = fetch_json(request).await?;
+        check_error_code(page.error)?;
+        Ok(page.items.unwrap_or_default().into_iter().filter_map(|hit| hit.item_basic).collect())
+    }
+
+    // Public shop details for a shop's vanity URL (shopee.co.id/<username>)
+    pub async fn shop_detail(&self, username: &str, cookies: Option<&str>) -> Result<ShopDetail, ApiFailure> {
+        let url = format!("https://shopee.co.id/api/v4/shop/get_shop_detail?username={}", urlencoding::encode(username));
+        let mut request = with_web_headers(self.http.get(&url));
+        if let Some(cookies) = cookies {
+            request = request.header("Cookie", cookies);
+        }
+        let (_, data) = self.call::<ShopDetail>(request).await?;
+        data.ok_or_else(|| ApiFailure::message(format!("Shop '{}' not found", username)))
+    }
+
+    // Shop ID for a shop's vanity URL
+    pub async fn shop_id_by_username(&self, username: &str) -> Result<i64, ApiFailure> {
+        self.shop_detail(username, None).await.map(|detail| detail.shopid)
+    }
+
+    pub async fn live_stats(&self, session_id: &str, cookies: &str) -> Result<LiveStats, ApiFailure> {
+        let url = format!("https://live.shopee.co.id/api/v1/session/{}", urlencoding::encode(session_id));
+        let data: Option<LiveSessionData> = self.live_get(&url, cookies).await?;
+        data.map(|data| data.session)
+            .ok_or_else(|| ApiFailure::message(format!("Live session {} not found", session_id)))
+    }
+
+    // The live the cookie's account is streaming right now, if any
+    pub async fn ongoing_session(&self, cookies: &str) -> Result<Option<String>, ApiFailure> {
+        let data: Option<OngoingSessionData> = self.live_get("https://live.shopee.co.id/api/v1/streamer/session/ongoing", cookies).await?;
+        Ok(data.and_then(|data| data.session_id).filter(|id| *id > 0).map(|id| id.to_string()))
+    }
+
+    async fn live_get<T: for<'de> Deserialize<'de>>(&self, url: &str, cookies: &str) -> Result<Option<T>, ApiFailure> {
+        let request = self
+            .http
+            .get(url)
+            .header("Accept", "application/json, text/plain")
+            .header("Referer", "https://live.shopee.co.id/")
+            .header("Cookie", cookies);
+        let response: LiveResponse<T> = fetch_json(request).await?;
+        if response.err_code != 0 {
+            return Err(ApiFailure {
+                http_status: None,
+                error_code: Some(response.err_code),
+                message: format!(
+                    "Shopee live API error: {} - {}",
+                    response.err_code,
+                    response.err_msg.unwrap_or("Unknown error".to_string())
+                ),
+                transport: false,
+            });
+        }
+        Ok(response.data)
+    }
+
+    async fn call<T: for<'de> Deserialize<'de>>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<(Vec<String>, Option<T>), ApiFailure> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ApiFailure::transport(format!("Request failed: {}", e)))?;
+        let status = response.status();
+        let set_cookie_headers: Vec<String> = response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|v| v.to_str().ok().map(|s| s.to_string()))
+            .collect();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| ApiFailure::transport(format!("Failed to read response: {}", e)))?;
+
+        if !status.is_success() {
+            return Err(ApiFailure {
+                http_status: Some(status.as_u16()),
+                error_code: None,
+                message: format!("HTTP {}: {}", status, text),
+                transport: false,
+            });
+        }
+
+        let parsed: ShopeeResponse<T> = serde_json::from_str(&text)
+            .map_err(|e| ApiFailure::message(format!("Failed to parse response: {} - Response: {}", e, text)))?;
+        if parsed.error != 0 {
+            return Err(ApiFailure {
+                http_status: Some(status.as_u16()),
+                error_code: Some(parsed.error),
+                message: format!(
+                    "Shopee API error: {} - {}",
+                    parsed.error,
+                    parsed.error_msg.unwrap_or("Unknown error".to_string())
+                ),
+                transport: false,
+            });
+        }
+        Ok((set_cookie_headers, parsed.data))
+    }
+}
+
+// Login endpoints use the usual envelope, but a non-zero error is an answer the caller
+// handles rather than a failure
+async fn login_call(request: reqwest::RequestBuilder) -> Result<LoginResponse, ApiFailure> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ApiFailure::transport(format!("Request failed: {}", e)))?;
+    let status = response.status();
+    let set_cookie_headers: Vec<String> = response
+        .headers()
+        .get_all("set-cookie")
+        .iter()
+        .filter_map(|v| v.to_str().ok().map(|s| s.to_string()))
+        .collect();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| ApiFailure::transport(format!("Failed to read response: {}", e)))?;
+    if !status.is_success() {
+        return Err(ApiFailure {
+            http_status: Some(status.as_u16()),
+            error_code: None,
+            message: format!("HTTP {}: {}", status, text),
+            transport: false,
+        });
+    }
+    let parsed: ShopeeResponse<serde_json::Value> = serde_json::from_str(&text)
+        .map_err(|e| ApiFailure::message(format!("Failed to parse response: {} - Response: {}", e, text)))?;
+    Ok(LoginResponse {
+        error: parsed.error,
+        error_msg: parsed.error_msg,
+        data: parsed.data,
+        set_cookie_headers,
+    })
+}
+
+// Shopee hashes passwords client-side as sha256(md5(password)) before sending them
+fn hash_password(password: &str) -> String {
+    let md5_hex = format!("{:x}", md5::compute(password.as_bytes()));
+    let mut hasher = Sha256::new();
+    hasher.update(md5_hex.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+// For endpoints that don't use the error/error_msg/data envelope
+async fn fetch_json<T: for<'de> Deserialize<'de>>(request: reqwest::RequestBuilder) -> Result<T, ApiFailure> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ApiFailure::transport(format!("Request failed: {}", e)))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| ApiFailure::transport(format!("Failed to read response: {}", e)))?;
+    if !status.is_success() {
+        return Err(ApiFailure {
+            http_status: Some(status.as_u16()),
+            error_code: None,
+            message: format!("HTTP {}: {}", status, text),
+            transport: false,
+        });
+    }
+    serde_json::from_str(&text)
+        .map_err(|e| ApiFailure::message(format!("Failed to parse response: {} - Response: {}", e, text)))
+}
+
+fn check_error_code(error: Option<i32>) -> Result<(), ApiFailure> {
+    match error {
+        Some(code) if code != 0 => Err(ApiFailure {
+            http_status: None,
+            error_code: Some(code),
+            message: format!("Shopee API error: {}", code),
+            transport: false,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn with_web_headers(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    request
+        .header("Accept", "application/json, text/plain")
+        .header("Accept-Language", "en-US,en;q=0.9")
+        .header("Origin", "https://shopee.co.id")
+        .header("Referer", "https://shopee.co.id/")
+}
+
+fn with_login_headers(request: reqwest::RequestBuilder, referer: &str) -> reqwest::RequestBuilder {
+    request
+        .header("Accept", "application/json")
+        .header("Content-Type", "application/json")
+        .header("X-Api-Source", "pc")
+        .header("X-Shopee-Language", "id")
+        .header("X-Requested-With", "XMLHttpRequest")
+        .header("Origin", "https://shopee.co.id")
+        .header("Referer", referer)
+}
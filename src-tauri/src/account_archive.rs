@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::account_notes::AccountNotes;
+use crate::encrypted_store;
+use crate::ShopeeAccount;
+
+// Encrypted because archived entries keep the account's cookie
+pub const ARCHIVE_FILE: &str = "archived_accounts.enc";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAccount {
+    pub archive_id: String,
+    pub original_id: i32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<String>,
+    pub is_active: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<AccountNotes>,
+    pub archived_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivedAccountSummary {
+    pub archive_id: String,
+    pub original_id: i32,
+    pub name: String,
+    pub has_cookie: bool,
+    pub tags: Vec<String>,
+    pub archived_at: u64,
+}
+
+impl From<&ArchivedAccount> for ArchivedAccountSummary {
+    fn from(entry: &ArchivedAccount) -> Self {
+        ArchivedAccountSummary {
+            archive_id: entry.archive_id.clone(),
+            original_id: entry.original_id,
+            name: entry.name.clone(),
+            has_cookie: entry.cookie.as_deref().is_some_and(|c| !c.is_empty()),
+            tags: entry.tags.clone(),
+            archived_at: entry.archived_at,
+        }
+    }
+}
+
+// Snapshot the account with its local tags and notes before it is deleted server-side
+pub fn archive(app: &AppHandle, account: &ShopeeAccount) -> Result<(), String> {
+    let archived_at = crate::unix_now();
+    let tags = crate::account_tags::all(app)?
+        .remove(&account.id)
+        .map(|tags| tags.into_iter().collect())
+        .unwrap_or_default();
+    let entry = ArchivedAccount {
+        archive_id: format!("{}-{}", account.id, archived_at),
+        original_id: account.id,
+        name: account.name.clone(),
+        cookie: account.cookie.clone(),
+        is_active: account.is_active,
+        tags,
+        notes: crate::account_notes::get(app, account.id)?,
+        archived_at,
+    };
+    encrypted_store::update(app, ARCHIVE_FILE, |entries: &mut Vec<ArchivedAccount>| {
+        entries.push(entry);
+    })
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<ArchivedAccountSummary>, String> {
+    let entries: Vec<ArchivedAccount> = encrypted_store::load(app, ARCHIVE_FILE)?;
+    let mut summaries: Vec<ArchivedAccountSummary> = entries.iter().map(ArchivedAccountSummary::from).collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.archived_at));
+    Ok(summaries)
+}
+
+pub fn get(app: &AppHandle, archive_id: &str) -> Result<Option<ArchivedAccount>, String> {
+    let entries: Vec<ArchivedAccount> = encrypted_store::load(app, ARCHIVE_FILE)?;
+    Ok(entries.into_iter().find(|e| e.archive_id == archive_id))
+}
+
+// Archived entries as they go into a workspace backup: the cookie only when the backup
+// includes cookies, and notes only if the account opted in to backups
+pub fn for_backup(app: &AppHandle, include_cookies: bool) -> Result<Vec<ArchivedAccount>, String> {
+    let entries: Vec<ArchivedAccount> = encrypted_store::load(app, ARCHIVE_FILE)?;
+    Ok(strip_for_backup(entries, include_cookies))
+}
+
+fn strip_for_backup(mut entries: Vec<ArchivedAccount>, include_cookies: bool) -> Vec<ArchivedAccount> {
+    for entry in &mut entries {
+        if !include_cookies {
+            entry.cookie = None;
+        }
+        entry.notes = entry.notes.take().filter(|notes| notes.include_in_backup);
+    }
+    entries
+}
+
+pub fn remove(app: &AppHandle, archive_id: &str) -> Result<(), String> {
+    encrypted_store::update(app, ARCHIVE_FILE, |entries: &mut Vec<ArchivedAccount>| {
+        entries.retain(|e| e.archive_id != archive_id);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(include_notes_in_backup: bool) -> ArchivedAccount {
+        ArchivedAccount {
+            archive_id: "7-100".to_string(),
+            original_id: 7,
+            name: "toko".to_string(),
+            cookie: Some("SPC_EC=secret".to_string()),
+            is_active: true,
+            tags: vec!["fashion".to_string()],
+            notes: Some(AccountNotes {
+                notes: Some("handover".to_string()),
+                include_in_backup: include_notes_in_backup,
+                ..Default::default()
+            }),
+            archived_at: 100,
+        }
+    }
+
+    #[test]
+    fn backup_without_cookies_has_no_cookie_field() {
+        let entries = strip_for_backup(vec![entry(true)], false);
+        let value = serde_json::to_value(&entries).unwrap();
+        let object = value[0].as_object().unwrap();
+        assert!(!object.contains_key("cookie"));
+        assert!(!value.to_string().contains("SPC_EC"));
+        assert_eq!(object["tags"], serde_json::json!(["fashion"]));
+    }
+
+    #[test]
+    fn backup_with_cookies_keeps_them() {
+        let entries = strip_for_backup(vec![entry(true)], true);
+        assert_eq!(entries[0].cookie.as_deref(), Some("SPC_EC=secret"));
+    }
+
+    #[test]
+    fn notes_are_only_kept_when_opted_in() {
+        let entries = strip_for_backup(vec![entry(false), entry(true)], true);
+        assert!(entries[0].notes.is_none());
+        assert_eq!(entries[1].notes.as_ref().and_then(|n| n.notes.as_deref()), Some("handover"));
+    }
+
+    #[test]
+    fn entry_without_cookie_field_still_loads() {
+        let value = serde_json::to_value(strip_for_backup(vec![entry(true)], false)).unwrap();
+        let entries: Vec<ArchivedAccount> = serde_json::from_value(value).unwrap();
+        assert!(entries[0].cookie.is_none());
+    }
+}
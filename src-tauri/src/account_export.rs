@@ -0,0 +1,59 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::cookie_health::{self, CookieStatus};
+use crate::ShopeeAccount;
+
+// Account listing for audits; cookies are deliberately left out
+#[derive(Debug, Serialize)]
+struct ExportedAccount {
+    id: i32,
+    name: String,
+    is_active: bool,
+    created_at: Option<String>,
+    shopee_username: Option<String>,
+    last_status: Option<CookieStatus>,
+    last_checked_at: Option<u64>,
+}
+
+// Writes JSON or CSV depending on the file extension and returns the number of rows
+pub fn export(app: &AppHandle, accounts: Vec<ShopeeAccount>, path: &Path) -> Result<usize, String> {
+    let health = cookie_health::load_all(app);
+    let rows: Vec<ExportedAccount> = accounts
+        .into_iter()
+        .map(|account| {
+            let last = health.get(&account.id);
+            ExportedAccount {
+                id: account.id,
+                name: account.name,
+                is_active: account.is_active,
+                created_at: account.created_at,
+                shopee_username: last.and_then(|h| h.username.clone()),
+                last_status: last.map(|h| h.status),
+                last_checked_at: last.map(|h| h.checked_at),
+            }
+        })
+        .collect();
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let bytes = match extension.as_str() {
+        "json" => serde_json::to_vec_pretty(&rows).map_err(|e| format!("Failed to serialize accounts: {}", e))?,
+        "csv" => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for row in &rows {
+                writer.serialize(row).map_err(|e| format!("Failed to write CSV: {}", e))?;
+            }
+            writer.into_inner().map_err(|e| format!("Failed to write CSV: {}", e))?
+        }
+        _ => return Err("Unsupported file type, expected .json or .csv".to_string()),
+    };
+
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(rows.len())
+}
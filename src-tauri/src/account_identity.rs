@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::storage;
+
+// Shopee user IDs of stored accounts, so duplicate checks don't hit Shopee for every account
+const USERIDS_FILE: &str = "account_userids.json";
+
+pub fn record(app: &AppHandle, account_id: i32, userid: i64) {
+    if let Err(e) = storage::update_json(app, USERIDS_FILE, |map: &mut HashMap<i32, i64>| {
+        map.insert(account_id, userid);
+    }) {
+        eprintln!("Failed to record account user ID: {}", e);
+    }
+}
+
+// Returns the stored account that is the same Shopee user, looking up any account whose
+// user ID isn't known yet with its cookie
+pub async fn find_duplicate(app: &AppHandle, email: &str, password: &str, userid: i64) -> Result<Option<i32>, String> {
+    let known: HashMap<i32, i64> = storage::load_json(app, USERIDS_FILE)?;
+    let accounts = crate::fetch_shopee_accounts(email, password)
+        .await
+        .map_err(|e| e.to_string())?
+        .data;
+
+    for account in accounts {
+        let account_userid = match known.get(&account.id) {
+            Some(&id) => Some(id),
+            None => match account.cookie.as_deref().filter(|c| !c.is_empty()) {
+                Some(cookie) => {
+                    let proxy = crate::account_proxies::proxy_for(app, account.id);
+                    match crate::fetch_account_info(cookie, proxy.as_deref()).await {
+                        Ok(info) => {
+                            record(app, account.id, info.userid);
+                            Some(info.userid)
+                        }
+                        Err(_) => None,
+                    }
+                }
+                None => None,
+            },
+        };
+        if account_userid == Some(userid) {
+            return Ok(Some(account.id));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+
+use crate::error::{AggregateError, CommandError};
+
+const MAX_CONCURRENT_UPLOADS: usize = 4;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportedAccount {
+    pub name: String,
+    pub cookie: String,
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportSummary {
+    pub total: usize,
+    pub imported: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ImportProgress {
+    done: usize,
+    total: usize,
+    name: String,
+    success: bool,
+    error: Option<String>,
+}
+
+// Accepts either a JSON array of {name, cookie, is_active?} or a CSV with name and cookie columns
+pub fn parse_file(path: &Path) -> Result<Vec<ImportedAccount>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let accounts: Vec<ImportedAccount> = match extension.as_str() {
+        "json" => serde_json::from_str(&contents).map_err(|e| format!("Invalid JSON: {}", e))?,
+        "csv" => {
+            let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(contents.as_bytes());
+            let mut accounts = Vec::new();
+            for (i, record) in reader.deserialize().enumerate() {
+                let account: ImportedAccount = record.map_err(|e| format!("Invalid CSV row {}: {}", i + 1, e))?;
+                accounts.push(account);
+            }
+            accounts
+        }
+        _ => return Err("Unsupported file type, expected .json or .csv".to_string()),
+    };
+
+    Ok(accounts
+        .into_iter()
+        .filter(|a| !a.name.trim().is_empty() || !a.cookie.trim().is_empty())
+        .collect())
+}
+
+async fn import_one(app: &AppHandle, email: &str, password: &str, account: &ImportedAccount) -> Result<(), CommandError> {
+    if account.cookie.trim().is_empty() {
+        return Err("Cookie is empty".into());
+    }
+    let cookie = crate::cookies::CookieJar::parse_header(&account.cookie).to_header();
+
+    // Validates the cookie and rejects accounts that are already stored
+    crate::add_shopee_account(
+        app.clone(),
+        email.to_string(),
+        password.to_string(),
+        account.name.trim().to_string(),
+        cookie,
+        account.is_active,
+    )
+    .await
+    .map(|_| ())
+}
+
+// Validates each cookie against Shopee and uploads the good ones, a few at a time.
+// Any failed rows are reported together as an aggregate error.
+pub async fn import(app: &AppHandle, email: String, password: String, accounts: Vec<ImportedAccount>) -> Result<ImportSummary, CommandError> {
+    let total = accounts.len();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_UPLOADS));
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for (index, account) in accounts.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let email = email.clone();
+        let password = password.clone();
+        let app = app.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = import_one(&app, &email, &password, &account).await;
+            (index, account.name, result)
+        });
+    }
+
+    let mut done = 0;
+    let mut outcome = AggregateError::new(total);
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((index, name, result)) = joined else {
+            continue;
+        };
+        done += 1;
+
+        let progress = ImportProgress {
+            done,
+            total,
+            name: name.clone(),
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        if let Err(e) = app.emit("account-import-progress", progress) {
+            eprintln!("Failed to emit account-import-progress: {}", e);
+        }
+
+        match result {
+            Ok(()) => outcome.succeeded += 1,
+            Err(error) => outcome.push(index, name, error),
+        }
+    }
+
+    let imported = outcome.into_result()?;
+    Ok(ImportSummary { total, imported })
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::encrypted_store;
+
+pub const NOTES_FILE: &str = "account_notes.enc";
+
+// Handover context for a Shopee account, kept encrypted on this machine only
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountNotes {
+    pub phone_hint: Option<String>,
+    pub owner_contact: Option<String>,
+    pub escalation_info: Option<String>,
+    pub notes: Option<String>,
+    // Opt-in: only notes with this flag are copied into workspace backups
+    #[serde(default)]
+    pub include_in_backup: bool,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+type NotesMap = HashMap<i32, AccountNotes>;
+
+pub fn get(app: &AppHandle, account_id: i32) -> Result<Option<AccountNotes>, String> {
+    let notes: NotesMap = encrypted_store::load(app, NOTES_FILE)?;
+    Ok(notes.get(&account_id).cloned())
+}
+
+// Only the notes whose account opted in to backups
+pub fn for_backup(app: &AppHandle) -> Result<NotesMap, String> {
+    let mut notes: NotesMap = encrypted_store::load(app, NOTES_FILE)?;
+    notes.retain(|_, notes| notes.include_in_backup);
+    Ok(notes)
+}
+
+pub fn set(app: &AppHandle, account_id: i32, mut notes: AccountNotes) -> Result<AccountNotes, String> {
+    notes.updated_at = crate::unix_now();
+    encrypted_store::update(app, NOTES_FILE, |map: &mut NotesMap| {
+        map.insert(account_id, notes.clone());
+    })?;
+    Ok(notes)
+}
+
+pub fn remove(app: &AppHandle, account_id: i32) -> Result<(), String> {
+    encrypted_store::update(app, NOTES_FILE, |map: &mut NotesMap| {
+        map.remove(&account_id);
+    })
+}
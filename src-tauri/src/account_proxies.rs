@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::storage;
+
+const PROXIES_FILE: &str = "account_proxies.json";
+
+pub fn all(app: &AppHandle) -> Result<HashMap<i32, String>, String> {
+    storage::load_json(app, PROXIES_FILE)
+}
+
+pub fn validate(url: &str) -> Result<(), String> {
+    let supported = ["http://", "https://", "socks5://", "socks5h://"];
+    if !supported.iter().any(|s| url.starts_with(s)) {
+        return Err("Proxy URL must start with http://, https://, socks5:// or socks5h://".to_string());
+    }
+    reqwest::Proxy::all(url).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    Ok(())
+}
+
+// None or an empty URL unbinds the account so it uses the default proxy, if any, again
+pub fn set(app: &AppHandle, account_id: i32, proxy_url: Option<String>) -> Result<(), String> {
+    let proxy_url = proxy_url.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(url) = &proxy_url {
+        validate(url)?;
+    }
+
+    storage::update_json(app, PROXIES_FILE, |map: &mut HashMap<i32, String>| match proxy_url {
+        Some(url) => {
+            map.insert(account_id, url);
+        }
+        None => {
+            map.remove(&account_id);
+        }
+    })
+}
+
+pub fn remove(app: &AppHandle, account_id: i32) -> Result<(), String> {
+    set(app, account_id, None)
+}
+
+// The account's own proxy, else the default proxy from settings
+pub fn proxy_for(app: &AppHandle, account_id: i32) -> Option<String> {
+    all(app)
+        .ok()
+        .and_then(|map| map.get(&account_id).cloned())
+        .or_else(|| crate::settings::current().default_proxy)
+}
@@ -0,0 +1,55 @@
+use std::collections::{BTreeSet, HashMap};
+use tauri::AppHandle;
+
+use crate::storage;
+
+const TAGS_FILE: &str = "account_tags.json";
+
+type TagMap = HashMap<i32, BTreeSet<String>>;
+
+fn normalize(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+pub fn all(app: &AppHandle) -> Result<TagMap, String> {
+    storage::load_json(app, TAGS_FILE)
+}
+
+pub fn set(app: &AppHandle, account_id: i32, tags: Vec<String>) -> Result<Vec<String>, String> {
+    let tags: BTreeSet<String> = tags.iter().map(|t| normalize(t)).filter(|t| !t.is_empty()).collect();
+    let result: Vec<String> = tags.iter().cloned().collect();
+    storage::update_json(app, TAGS_FILE, |map: &mut TagMap| {
+        if tags.is_empty() {
+            map.remove(&account_id);
+        } else {
+            map.insert(account_id, tags);
+        }
+    })?;
+    Ok(result)
+}
+
+pub fn remove(app: &AppHandle, account_id: i32) -> Result<(), String> {
+    storage::update_json(app, TAGS_FILE, |map: &mut TagMap| {
+        map.remove(&account_id);
+    })
+}
+
+pub fn accounts_with_tag(app: &AppHandle, tag: &str) -> Result<Vec<i32>, String> {
+    let tag = normalize(tag);
+    let mut ids: Vec<i32> = all(app)?
+        .into_iter()
+        .filter(|(_, tags)| tags.contains(&tag))
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+// Narrow a list of accounts to a tag, for commands that take an optional tag as target
+pub fn filter_by_tag<T>(app: &AppHandle, items: Vec<T>, tag: Option<&str>, id: impl Fn(&T) -> i32) -> Result<Vec<T>, String> {
+    let Some(tag) = tag else {
+        return Ok(items);
+    };
+    let ids = accounts_with_tag(app, tag)?;
+    Ok(items.into_iter().filter(|item| ids.contains(&id(item))).collect())
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::encrypted_store;
+use crate::error::CommandError;
+use crate::ShopeeAccount;
+
+const CACHE_FILE: &str = "shopee_accounts_cache.enc";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedAccounts {
+    accounts: Vec<ShopeeAccount>,
+    fetched_at: u64,
+    // Hash of the credentials the backend accepted when the list was fetched. Entries from
+    // before it was stored have none and are never served.
+    #[serde(default)]
+    verifier: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CacheUpdatedEvent {
+    cache: &'static str,
+    accounts: Vec<ShopeeAccount>,
+}
+
+// Keyed by member email so several members on one machine don't see each other's accounts
+type CacheMap = HashMap<String, CachedAccounts>;
+
+// Members whose credentials the backend has accepted since the app started, by verifier
+#[derive(Default)]
+pub struct AccountsCacheState {
+    verified: Mutex<HashMap<String, String>>,
+}
+
+impl AccountsCacheState {
+    pub fn is_verified(&self, member_email: &str, verifier: &str) -> bool {
+        self.verified.lock().unwrap().get(member_email).is_some_and(|v| v == verifier)
+    }
+
+    pub fn mark_verified(&self, member_email: &str, verifier: &str) {
+        self.verified.lock().unwrap().insert(member_email.to_string(), verifier.to_string());
+    }
+
+    fn forget(&self, member_email: &str) {
+        self.verified.lock().unwrap().remove(member_email);
+    }
+}
+
+pub fn verifier(member_email: &str, password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(member_email.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(password.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+// The backend couldn't be reached or is down, as opposed to rejecting the request
+pub fn is_unreachable(error: &CommandError) -> bool {
+    match error {
+        CommandError::Message(message) => message.starts_with("Request failed"),
+        CommandError::Http { status, .. } => matches!(status, 502..=504),
+        _ => false,
+    }
+}
+
+// Only returns the list to the credentials it was fetched with
+pub fn get(app: &AppHandle, member_email: &str, verifier: &str) -> Option<Vec<ShopeeAccount>> {
+    let cache: CacheMap = encrypted_store::load(app, CACHE_FILE).ok()?;
+    cache
+        .get(member_email)
+        .filter(|c| !c.verifier.is_empty() && c.verifier == verifier)
+        .map(|c| c.accounts.clone())
+}
+
+pub fn store(app: &AppHandle, member_email: &str, verifier: &str, accounts: &[ShopeeAccount]) {
+    let entry = CachedAccounts {
+        accounts: accounts.to_vec(),
+        fetched_at: crate::unix_now(),
+        verifier: verifier.to_string(),
+    };
+    if let Err(e) = encrypted_store::update(app, CACHE_FILE, |map: &mut CacheMap| {
+        map.insert(member_email.to_string(), entry);
+    }) {
+        eprintln!("Failed to write accounts cache: {}", e);
+    }
+}
+
+// Drops the member's cached list after any change to their accounts, so the next read
+// goes to the backend
+pub fn invalidate(app: &AppHandle, member_email: &str) {
+    if let Err(e) = encrypted_store::update(app, CACHE_FILE, |map: &mut CacheMap| {
+        map.remove(member_email);
+    }) {
+        eprintln!("Failed to clear accounts cache: {}", e);
+    }
+}
+
+// Fetch the authoritative list in the background and tell the UI if the cached copy was stale.
+// If the backend now rejects the credentials the cached copy is dropped.
+pub fn reconcile(app: &AppHandle, email: String, password: String, cached: Vec<ShopeeAccount>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let fresh = match crate::fetch_shopee_accounts(&email, &password).await {
+            Ok(response) => response.data,
+            Err(e) => {
+                println!("[ACCOUNTS CACHE] Reconcile failed: {}", e);
+                if !is_unreachable(&e) {
+                    app.state::<AccountsCacheState>().forget(&email);
+                    invalidate(&app, &email);
+                }
+                return;
+            }
+        };
+
+        store(&app, &email, &verifier(&email, &password), &fresh);
+        if serde_json::to_value(&fresh).ok() == serde_json::to_value(&cached).ok() {
+            return;
+        }
+
+        let event = CacheUpdatedEvent {
+            cache: "shopee_accounts",
+            accounts: fresh,
+        };
+        if let Err(e) = app.emit("cache-updated", event) {
+            eprintln!("Failed to emit cache-updated: {}", e);
+        }
+    });
+}
@@ -0,0 +1,148 @@
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::db;
+
+const MAX_NOTE_CHARS: usize = 2000;
+const MAX_FIELDS: usize = 20;
+const MAX_FIELD_NAME_CHARS: usize = 40;
+const MAX_FIELD_VALUE_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationTarget {
+    Account,
+    ProductSet,
+}
+
+impl AnnotationTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            AnnotationTarget::Account => "account",
+            AnnotationTarget::ProductSet => "product_set",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Gray,
+}
+
+// Operator annotations on an account or product set. Kept in the local database only; for
+// sensitive handover details use the encrypted account notes instead.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub color: Option<LabelColor>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnotationEntry {
+    pub target_id: i64,
+    pub annotation: Annotation,
+}
+
+impl Annotation {
+    fn normalize(&mut self) -> Result<(), String> {
+        self.note = self.note.take().map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        if self.note.as_ref().is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS) {
+            return Err(format!("Note must be at most {} characters", MAX_NOTE_CHARS));
+        }
+
+        let mut fields = BTreeMap::new();
+        for (name, value) in std::mem::take(&mut self.fields) {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err("Custom field names can't be empty".to_string());
+            }
+            if name.chars().count() > MAX_FIELD_NAME_CHARS {
+                return Err(format!("Custom field name '{}' is longer than {} characters", name, MAX_FIELD_NAME_CHARS));
+            }
+            if value.chars().count() > MAX_FIELD_VALUE_CHARS {
+                return Err(format!("Value of '{}' is longer than {} characters", name, MAX_FIELD_VALUE_CHARS));
+            }
+            fields.insert(name, value.trim().to_string());
+        }
+        if fields.len() > MAX_FIELDS {
+            return Err(format!("At most {} custom fields are allowed", MAX_FIELDS));
+        }
+        self.fields = fields;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.note.is_none() && self.color.is_none() && self.fields.is_empty()
+    }
+}
+
+pub fn get(app: &AppHandle, target: AnnotationTarget, target_id: i64) -> Result<Option<Annotation>, String> {
+    db::with(app, |conn| {
+        let data: Option<String> = conn
+            .query_row(
+                "SELECT data FROM annotations WHERE target = ?1 AND target_id = ?2",
+                (target.as_str(), target_id),
+                |row| row.get(0),
+            )
+            .optional()?;
+        data.map(|data| db::from_json(&data)).transpose()
+    })
+}
+
+// All annotations of one kind, e.g. to color the account list in one call
+pub fn list(app: &AppHandle, target: AnnotationTarget) -> Result<Vec<AnnotationEntry>, String> {
+    db::with(app, |conn| {
+        let mut statement = conn.prepare("SELECT target_id, data FROM annotations WHERE target = ?1 ORDER BY target_id")?;
+        let mut entries = Vec::new();
+        for row in statement.query_map([target.as_str()], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))? {
+            let (target_id, data) = row?;
+            entries.push(AnnotationEntry {
+                target_id,
+                annotation: db::from_json(&data)?,
+            });
+        }
+        Ok(entries)
+    })
+}
+
+// Saving an annotation with nothing in it removes it
+pub fn set(app: &AppHandle, target: AnnotationTarget, target_id: i64, mut annotation: Annotation) -> Result<Annotation, String> {
+    annotation.normalize()?;
+    if annotation.is_empty() {
+        remove(app, target, target_id)?;
+        return Ok(annotation);
+    }
+    annotation.updated_at = crate::unix_now();
+    db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO annotations (target, target_id, data, updated_at) VALUES (?1, ?2, ?3, ?4)",
+            (target.as_str(), target_id, db::to_json(&annotation)?, annotation.updated_at as i64),
+        )
+    })?;
+    Ok(annotation)
+}
+
+pub fn remove(app: &AppHandle, target: AnnotationTarget, target_id: i64) -> Result<(), String> {
+    db::with(app, |conn| {
+        conn.execute(
+            "DELETE FROM annotations WHERE target = ?1 AND target_id = ?2",
+            (target.as_str(), target_id),
+        )
+        .map(|_| ())
+    })
+}
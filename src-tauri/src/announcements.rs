@@ -0,0 +1,135 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeSet;
+use tauri::AppHandle;
+
+use crate::storage;
+use crate::{ApiResponse, CommandError};
+
+const ANNOUNCEMENTS_FILE: &str = "announcements.json";
+const ANNOUNCEMENTS_ENDPOINT: &str = "/api/announcements";
+// The cached feed is served without asking the backend for this long
+const CACHE_SECS: u64 = 15 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementKind {
+    #[default]
+    News,
+    Maintenance,
+    // E.g. Shopee changed their API and an update is needed
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Announcement {
+    #[serde(deserialize_with = "id_string")]
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub kind: AnnouncementKind,
+    pub url: Option<String>,
+    pub published_at: Option<String>,
+}
+
+// The backend numbers announcements, but string IDs are accepted too
+fn id_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(id) => Ok(id),
+        serde_json::Value::Number(id) => Ok(id.to_string()),
+        other => Err(serde::de::Error::custom(format!("invalid announcement id: {}", other))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnouncementList {
+    announcements: Vec<Announcement>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cache {
+    fetched_at: Option<u64>,
+    announcements: Vec<Announcement>,
+    read_ids: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnouncementView {
+    #[serde(flatten)]
+    pub announcement: Announcement,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnnouncementFeed {
+    pub announcements: Vec<AnnouncementView>,
+    // For the badge
+    pub unread: usize,
+    pub fetched_at: Option<u64>,
+    // The backend couldn't be reached and this is the cached copy
+    pub stale: bool,
+}
+
+fn feed(cache: &Cache, stale: bool) -> AnnouncementFeed {
+    let announcements: Vec<AnnouncementView> = cache
+        .announcements
+        .iter()
+        .map(|a| AnnouncementView {
+            announcement: a.clone(),
+            read: cache.read_ids.contains(&a.id),
+        })
+        .collect();
+    AnnouncementFeed {
+        unread: announcements.iter().filter(|a| !a.read).count(),
+        announcements,
+        fetched_at: cache.fetched_at,
+        stale,
+    }
+}
+
+async fn fetch() -> Result<Vec<Announcement>, CommandError> {
+    let response: ApiResponse<AnnouncementList> =
+        crate::make_api_request("GET", ANNOUNCEMENTS_ENDPOINT, None, Some("app_identifier=botgacor")).await?;
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to load announcements".to_string()).into());
+    }
+    Ok(response.data.map(|d| d.announcements).unwrap_or_default())
+}
+
+// Returns the cached feed while it's fresh, otherwise fetches it again. When the backend
+// can't be reached the cached copy is returned marked stale, unless there is none.
+pub async fn get(app: &AppHandle, refresh: bool) -> Result<AnnouncementFeed, CommandError> {
+    let cache: Cache = storage::load_json(app, ANNOUNCEMENTS_FILE)?;
+    let fresh = cache.fetched_at.is_some_and(|at| crate::unix_now().saturating_sub(at) < CACHE_SECS);
+    if fresh && !refresh {
+        return Ok(feed(&cache, false));
+    }
+
+    let announcements = match fetch().await {
+        Ok(announcements) => announcements,
+        Err(e) if cache.fetched_at.is_some() => {
+            eprintln!("[ANNOUNCEMENTS] Using cached feed: {}", e);
+            return Ok(feed(&cache, true));
+        }
+        Err(e) => return Err(e),
+    };
+    let updated = storage::update_json(app, ANNOUNCEMENTS_FILE, |cache: &mut Cache| {
+        // Read marks of announcements that were taken down aren't needed any more
+        cache.read_ids.retain(|id| announcements.iter().any(|a| &a.id == id));
+        cache.announcements = announcements;
+        cache.fetched_at = Some(crate::unix_now());
+        feed(cache, false)
+    })?;
+    Ok(updated)
+}
+
+// Marks the given announcements read, or all of them when `ids` is None; returns the unread count
+pub fn mark_read(app: &AppHandle, ids: Option<Vec<String>>) -> Result<usize, String> {
+    storage::update_json(app, ANNOUNCEMENTS_FILE, |cache: &mut Cache| {
+        let known: Vec<String> = cache.announcements.iter().map(|a| a.id.clone()).collect();
+        let ids = ids.unwrap_or_else(|| known.clone());
+        cache.read_ids.extend(ids.into_iter().filter(|id| known.contains(id)));
+        feed(cache, false).unread
+    })
+}
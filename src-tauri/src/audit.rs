@@ -0,0 +1,106 @@
+use rusqlite::types::Value;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::db;
+
+const DEFAULT_LIMIT: u32 = 200;
+const MAX_LIMIT: u32 = 1000;
+
+// One action taken through the app. Rows are only ever inserted; the table's triggers
+// reject updates and deletes.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub at: u64,
+    // Active operator profile, if anyone had switched to one
+    pub operator: Option<String>,
+    pub action: String,
+    // What was acted on, e.g. "niche:12" or "account:34"
+    pub target: Option<String>,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditFilter {
+    pub operator: Option<String>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    // For paging: only entries older than this ID
+    pub before_id: Option<i64>,
+    pub limit: Option<u32>,
+}
+
+// Records an action against the active operator. Failing to write the log never fails the
+// action itself, which has already happened by the time this is called.
+pub fn record(app: &AppHandle, action: &str, target: Option<String>, details: serde_json::Value) {
+    let operator = crate::operators::current(app);
+    let result = db::with(app, |conn| {
+        conn.execute(
+            "INSERT INTO audit_log (at, operator, action, target, details) VALUES (?1, ?2, ?3, ?4, ?5)",
+            (crate::unix_now() as i64, &operator, action, &target, details.to_string()),
+        )
+    });
+    if let Err(e) = result {
+        eprintln!("[AUDIT] Failed to record {}: {}", action, e);
+    }
+}
+
+// Newest first
+pub fn query(app: &AppHandle, filter: &AuditFilter) -> Result<Vec<AuditEntry>, String> {
+    let mut conditions = Vec::new();
+    let mut params: Vec<Value> = Vec::new();
+    let mut condition = |sql: &str, value: Value| {
+        params.push(value);
+        conditions.push(format!("{} ?{}", sql, params.len()));
+    };
+    if let Some(operator) = &filter.operator {
+        condition("operator =", Value::Text(operator.clone()));
+    }
+    if let Some(action) = &filter.action {
+        condition("action =", Value::Text(action.clone()));
+    }
+    if let Some(target) = &filter.target {
+        condition("target =", Value::Text(target.clone()));
+    }
+    if let Some(since) = filter.since {
+        condition("at >=", Value::Integer(since as i64));
+    }
+    if let Some(until) = filter.until {
+        condition("at <=", Value::Integer(until as i64));
+    }
+    if let Some(before_id) = filter.before_id {
+        condition("id <", Value::Integer(before_id));
+    }
+    let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let sql = format!(
+        "SELECT id, at, operator, action, target, details FROM audit_log {} ORDER BY id DESC LIMIT {}",
+        where_clause, limit
+    );
+    db::with(app, |conn| {
+        let mut statement = conn.prepare(&sql)?;
+        let mut rows = statement.query(rusqlite::params_from_iter(params))?;
+        let mut entries = Vec::new();
+        while let Some(row) = rows.next()? {
+            let details: String = row.get(5)?;
+            entries.push(AuditEntry {
+                id: row.get(0)?,
+                at: row.get::<_, i64>(1)? as u64,
+                operator: row.get(2)?,
+                action: row.get(3)?,
+                target: row.get(4)?,
+                details: db::from_json(&details)?,
+            });
+        }
+        Ok(entries)
+    })
+}
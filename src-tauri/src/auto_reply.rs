@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::storage;
+use crate::LiveComment;
+
+const RULES_FILE: &str = "auto_reply.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+// Replies from the host account are spaced at least this far apart
+const MIN_REPLY_GAP: Duration = Duration::from_secs(3);
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown() -> u64 {
+    60
+}
+
+// `template` may use {user} for the commenter's name and {n} for the first number in the
+// comment (e.g. "harga no 5?" -> "Cek keranjang no. 5 kak!"). A rule using {n} only fires
+// when the comment contains a number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoReplyRule {
+    pub keyword: String,
+    pub template: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoReplyConfig {
+    #[serde(default)]
+    pub rules: Vec<AutoReplyRule>,
+    // A viewer gets at most one auto-reply per this many seconds
+    #[serde(default = "default_cooldown")]
+    pub user_cooldown_secs: u64,
+}
+
+impl Default for AutoReplyConfig {
+    fn default() -> Self {
+        AutoReplyConfig {
+            rules: Vec::new(),
+            user_cooldown_secs: default_cooldown(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AutoReplySentEvent {
+    session_id: String,
+    comment_id: String,
+    username: String,
+    keyword: String,
+    reply: String,
+}
+
+pub fn load(app: &AppHandle) -> Result<AutoReplyConfig, String> {
+    storage::load_json(app, RULES_FILE)
+}
+
+pub fn save(app: &AppHandle, config: &AutoReplyConfig) -> Result<(), String> {
+    for (i, rule) in config.rules.iter().enumerate() {
+        if rule.keyword.trim().is_empty() || rule.template.trim().is_empty() {
+            return Err(format!("Rule {}: keyword and reply are required", i + 1));
+        }
+    }
+    storage::save_json(app, RULES_FILE, config)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+fn first_number(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_ascii_digit()).find(|s| !s.is_empty()).map(|s| s.to_string())
+}
+
+// Single-word keywords match whole words; multi-word keywords match as a phrase
+fn matches(rule: &AutoReplyRule, comment: &str) -> bool {
+    let keyword = rule.keyword.trim().to_lowercase();
+    if keyword.contains(char::is_whitespace) {
+        comment.to_lowercase().contains(&keyword)
+    } else {
+        words(comment).contains(&keyword)
+    }
+}
+
+fn render(rule: &AutoReplyRule, comment: &LiveComment) -> Option<String> {
+    let mut reply = rule.template.replace("{user}", &comment.username);
+    if reply.contains("{n}") {
+        reply = reply.replace("{n}", &first_number(&comment.content)?);
+    }
+    Some(reply)
+}
+
+pub fn reply_for(config: &AutoReplyConfig, comment: &LiveComment) -> Option<(String, String)> {
+    config
+        .rules
+        .iter()
+        .filter(|rule| rule.enabled && matches(rule, &comment.content))
+        .find_map(|rule| render(rule, comment).map(|reply| (rule.keyword.clone(), reply)))
+}
+
+#[derive(Default)]
+pub struct AutoReplyState {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl AutoReplyState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.tasks.lock().unwrap().remove(session_id) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn sessions(&self) -> Vec<String> {
+        self.tasks.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.tasks.lock().unwrap().remove(session_id);
+    }
+}
+
+// Watches the session's comments and answers the ones matching a rule. Comments already
+// in the session when it starts are not answered. Rules are re-read on every poll so edits
+// apply without a restart.
+pub fn start(
+    app: &AppHandle,
+    state: &AutoReplyState,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<(), String> {
+    if load(app)?.rules.iter().all(|rule| !rule.enabled) {
+        return Err("Add at least one enabled auto-reply rule first".to_string());
+    }
+    state.stop(&session_id);
+
+    let app = app.clone();
+    let key = session_id.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut cursor: Option<String> = None;
+        let mut primed = false;
+        let mut failures = 0;
+        let mut replied_to: HashMap<i64, Instant> = HashMap::new();
+        let mut last_reply: Option<Instant> = None;
+
+        loop {
+            let page = match crate::fetch_live_comments(&email, &password, shopee_account_id, &session_id, cursor.as_deref()).await {
+                Ok(page) => {
+                    failures = 0;
+                    page
+                }
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("[AUTO REPLY] Session {} comment poll failed ({}): {}", session_id, failures, e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        break;
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+            if page.cursor.is_some() {
+                cursor = page.cursor;
+            }
+            if !primed {
+                primed = true;
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+
+            let config = load(&app).unwrap_or_default();
+            let cooldown = Duration::from_secs(config.user_cooldown_secs);
+            for comment in page.comments.iter().filter(|c| !c.is_host) {
+                if replied_to.get(&comment.user_id).is_some_and(|at| at.elapsed() < cooldown) {
+                    continue;
+                }
+                let Some((keyword, reply)) = reply_for(&config, comment) else {
+                    continue;
+                };
+                if let Some(wait) = last_reply.and_then(|at| MIN_REPLY_GAP.checked_sub(at.elapsed())) {
+                    tokio::time::sleep(wait).await;
+                }
+                crate::blackout::wait_until_clear(&app).await;
+
+                match crate::post_live_comment(&app, &email, &password, shopee_account_id, &session_id, &reply).await {
+                    Ok(()) => {
+                        replied_to.insert(comment.user_id, Instant::now());
+                        last_reply = Some(Instant::now());
+                        let event = AutoReplySentEvent {
+                            session_id: session_id.clone(),
+                            comment_id: comment.id.clone(),
+                            username: comment.username.clone(),
+                            keyword,
+                            reply,
+                        };
+                        if let Err(e) = app.emit("auto-reply-sent", event) {
+                            eprintln!("Failed to emit auto-reply-sent: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("[AUTO REPLY] Failed to reply to comment {}: {}", comment.id, e),
+                }
+            }
+            replied_to.retain(|_, at| at.elapsed() < cooldown);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        app.state::<AutoReplyState>().finish(&session_id);
+    });
+
+    state.tasks.lock().unwrap().insert(key, task);
+    Ok(())
+}
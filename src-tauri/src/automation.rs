@@ -0,0 +1,378 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::jobs;
+use crate::pin_rotation::PinRotationState;
+use crate::storage;
+
+const RULES_FILE: &str = "automation_rules.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown() -> u64 {
+    60
+}
+
+// Viewer and stock conditions fire when they become true, not on every poll while they
+// stay true; comment conditions fire per matching comment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    ViewersAbove { threshold: i64 },
+    ViewersBelow { threshold: i64 },
+    CommentContains { text: String },
+    // The item the session's pin rotation currently shows has sold out
+    PinnedOutOfStock,
+}
+
+// `template` may use {user} for the commenter's name when the rule is triggered by a comment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    PinItem { item_id: i64 },
+    Reply { template: String },
+    AdvanceRotation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRule {
+    pub id: String,
+    pub name: String,
+    pub condition: Condition,
+    pub action: Action,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // A rule fires at most once per this many seconds in a session
+    #[serde(default = "default_cooldown")]
+    pub cooldown_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationSessionInfo {
+    pub session_id: String,
+    pub shopee_account_id: i32,
+    pub job_id: String,
+    pub disabled_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AutomationFiredEvent {
+    session_id: String,
+    rule_id: String,
+    rule_name: String,
+    success: bool,
+    error: Option<String>,
+}
+
+pub fn load(app: &AppHandle) -> Result<Vec<AutomationRule>, String> {
+    storage::load_json(app, RULES_FILE)
+}
+
+pub fn save(app: &AppHandle, rules: &[AutomationRule]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for (i, rule) in rules.iter().enumerate() {
+        if rule.id.trim().is_empty() || !ids.insert(rule.id.as_str()) {
+            return Err(format!("Rule {}: needs a unique ID", i + 1));
+        }
+        if let Condition::CommentContains { text } = &rule.condition {
+            if text.trim().is_empty() {
+                return Err(format!("Rule {}: comment text is required", i + 1));
+            }
+        }
+        if let Action::Reply { template } = &rule.action {
+            if template.trim().is_empty() {
+                return Err(format!("Rule {}: reply template is required", i + 1));
+            }
+        }
+    }
+    storage::save_json(app, RULES_FILE, &rules)
+}
+
+struct Engine {
+    info: AutomationSessionInfo,
+    disabled: Arc<Mutex<HashSet<String>>>,
+    task: JoinHandle<()>,
+}
+
+// Sessions with the rule engine running, keyed by session ID
+#[derive(Default)]
+pub struct AutomationState {
+    engines: Mutex<HashMap<String, Engine>>,
+}
+
+impl AutomationState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.engines.lock().unwrap().remove(session_id) {
+            Some(engine) => {
+                engine.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<AutomationSessionInfo> {
+        self.engines
+            .lock()
+            .unwrap()
+            .values()
+            .map(|engine| AutomationSessionInfo {
+                disabled_rules: engine.disabled.lock().unwrap().iter().cloned().collect(),
+                ..engine.info.clone()
+            })
+            .collect()
+    }
+
+    // Turns a rule off or on for one session without touching the saved rule
+    pub fn set_rule_enabled(&self, session_id: &str, rule_id: &str, enabled: bool) -> Result<(), String> {
+        let engines = self.engines.lock().unwrap();
+        let engine = engines
+            .get(session_id)
+            .ok_or_else(|| format!("Automation isn't running for session {}", session_id))?;
+        let mut disabled = engine.disabled.lock().unwrap();
+        if enabled {
+            disabled.remove(rule_id);
+        } else {
+            disabled.insert(rule_id.to_string());
+        }
+        Ok(())
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.engines.lock().unwrap().remove(session_id);
+    }
+}
+
+// What the engine knows about the session on one poll
+#[derive(Default)]
+struct Observation {
+    viewers: Option<i64>,
+    comments: Vec<crate::LiveComment>,
+    pinned_out_of_stock: Option<i64>,
+}
+
+// Current truth of a state condition; None for comment conditions and when the data is missing
+fn state_holds(condition: &Condition, seen: &Observation) -> Option<bool> {
+    match condition {
+        Condition::ViewersAbove { threshold } => seen.viewers.map(|v| v > *threshold),
+        Condition::ViewersBelow { threshold } => seen.viewers.map(|v| v < *threshold),
+        Condition::PinnedOutOfStock => Some(seen.pinned_out_of_stock.is_some()),
+        Condition::CommentContains { .. } => None,
+    }
+}
+
+// Edge tracking key; stock is tracked per pinned item so each sold-out item fires once
+fn state_key(rule: &AutomationRule, seen: &Observation) -> String {
+    match (&rule.condition, seen.pinned_out_of_stock) {
+        (Condition::PinnedOutOfStock, Some(item_id)) => format!("{}:{}", rule.id, item_id),
+        _ => rule.id.clone(),
+    }
+}
+
+// Where the comment feed is up to; the first page is history and is skipped
+#[derive(Default)]
+struct CommentFeed {
+    cursor: Option<String>,
+    primed: bool,
+}
+
+struct Session<'a> {
+    app: &'a AppHandle,
+    email: &'a str,
+    password: &'a str,
+    shopee_account_id: i32,
+    session_id: &'a str,
+}
+
+impl Session<'_> {
+    async fn observe(&self, rules: &[AutomationRule], feed: &mut CommentFeed) -> Result<Observation, String> {
+        let needs = |f: fn(&Condition) -> bool| rules.iter().any(|rule| f(&rule.condition));
+        let mut seen = Observation::default();
+
+        if needs(|c| matches!(c, Condition::ViewersAbove { .. } | Condition::ViewersBelow { .. })) {
+            let stats = crate::fetch_live_stats(self.app, self.email, self.password, self.shopee_account_id, self.session_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            seen.viewers = Some(stats.viewers);
+        }
+        if needs(|c| matches!(c, Condition::CommentContains { .. })) {
+            let page = crate::fetch_live_comments(self.email, self.password, self.shopee_account_id, self.session_id, feed.cursor.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            if page.cursor.is_some() {
+                feed.cursor = page.cursor;
+            }
+            if feed.primed {
+                seen.comments = page.comments.into_iter().filter(|c| !c.is_host).collect();
+            }
+            feed.primed = true;
+        }
+        if needs(|c| matches!(c, Condition::PinnedOutOfStock)) {
+            if let Some(pinned) = self.app.state::<PinRotationState>().current(self.session_id) {
+                let item = crate::fetch_shopee_item(self.app, self.email, self.password, self.shopee_account_id, pinned.shop_id, pinned.item_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                if item.is_some_and(|item| item.stock <= 0) {
+                    seen.pinned_out_of_stock = Some(pinned.item_id);
+                }
+            }
+        }
+        Ok(seen)
+    }
+
+    async fn run(&self, action: &Action, comment: Option<&crate::LiveComment>) -> Result<(), String> {
+        let result = match action {
+            Action::PinItem { item_id } => {
+                crate::pin_product_request(self.app, self.email, self.password, self.shopee_account_id, self.session_id, *item_id).await
+            }
+            Action::Reply { template } => {
+                let user = comment.map(|c| c.username.as_str()).unwrap_or_default();
+                let reply = template.replace("{user}", user);
+                crate::post_live_comment(self.app, self.email, self.password, self.shopee_account_id, self.session_id, &reply).await
+            }
+            Action::AdvanceRotation => return self.app.state::<PinRotationState>().advance(self.session_id),
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn report(&self, rule: &AutomationRule, result: &Result<(), String>) {
+        if let Err(e) = result {
+            eprintln!("[AUTOMATION] Rule '{}' failed in session {}: {}", rule.name, self.session_id, e);
+        }
+        let event = AutomationFiredEvent {
+            session_id: self.session_id.to_string(),
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            success: result.is_ok(),
+            error: result.as_ref().err().cloned(),
+        };
+        if let Err(e) = self.app.emit("automation-fired", event) {
+            eprintln!("Failed to emit automation-fired: {}", e);
+        }
+    }
+}
+
+// Polls the session's stats and comments and fires matching rules. Rules are re-read on every
+// poll so edits apply without a restart.
+pub fn start(app: &AppHandle, state: &AutomationState, email: String, password: String, shopee_account_id: i32, session_id: String) -> Result<AutomationSessionInfo, String> {
+    if load(app)?.iter().all(|rule| !rule.enabled) {
+        return Err("Add at least one enabled automation rule first".to_string());
+    }
+    state.stop(&session_id);
+
+    let cancel_session = session_id.clone();
+    let job = jobs::start_background(
+        app,
+        "automation",
+        format!("Automation rules for session {}", session_id),
+        Some(shopee_account_id),
+        move |app| {
+            app.state::<AutomationState>().stop(&cancel_session);
+        },
+    );
+    let info = AutomationSessionInfo {
+        session_id: session_id.clone(),
+        shopee_account_id,
+        job_id: job.id().to_string(),
+        disabled_rules: Vec::new(),
+    };
+    let disabled = Arc::new(Mutex::new(HashSet::new()));
+
+    let app = app.clone();
+    let task_disabled = disabled.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let session = Session {
+            app: &app,
+            email: &email,
+            password: &password,
+            shopee_account_id,
+            session_id: &session_id,
+        };
+        let mut feed = CommentFeed::default();
+        let mut failures = 0;
+        let mut holding: HashMap<String, bool> = HashMap::new();
+        let mut last_fired: HashMap<String, Instant> = HashMap::new();
+
+        loop {
+            job.checkpoint().await;
+            let disabled = task_disabled.lock().unwrap().clone();
+            let rules: Vec<AutomationRule> = load(&app)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|rule| rule.enabled && !disabled.contains(&rule.id))
+                .collect();
+
+            let seen = match session.observe(&rules, &mut feed).await {
+                Ok(seen) => {
+                    failures = 0;
+                    seen
+                }
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("[AUTOMATION] Session {} poll failed ({}): {}", session_id, failures, e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        job.finish::<(), _>(&Err(e));
+                        break;
+                    }
+                    job.sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            for rule in &rules {
+                let cooldown = Duration::from_secs(rule.cooldown_secs);
+                let cooling = last_fired.get(&rule.id).is_some_and(|at| at.elapsed() < cooldown);
+                let trigger = match (&rule.condition, state_holds(&rule.condition, &seen)) {
+                    (Condition::CommentContains { text }, _) => {
+                        let text = text.to_lowercase();
+                        seen.comments.iter().find(|c| c.content.to_lowercase().contains(&text)).map(Some)
+                    }
+                    (_, Some(now)) => {
+                        let key = state_key(rule, &seen);
+                        let rising = now && !holding.get(&key).copied().unwrap_or(false);
+                        // A rising edge during the cooldown is left unrecorded, so the rule
+                        // fires once the cooldown is over if the condition still holds
+                        if !(rising && cooling) {
+                            holding.insert(key, now);
+                        }
+                        rising.then_some(None)
+                    }
+                    (_, None) => None,
+                };
+                let Some(comment) = trigger else {
+                    continue;
+                };
+                if cooling {
+                    continue;
+                }
+
+                crate::blackout::wait_until_clear(&app).await;
+                let result = session.run(&rule.action, comment).await;
+                last_fired.insert(rule.id.clone(), Instant::now());
+                jobs::log(&app, job.id(), format!("Rule '{}' fired{}", rule.name, result.as_ref().err().map(|e| format!(": {}", e)).unwrap_or_default()));
+                session.report(rule, &result);
+            }
+            job.sleep(POLL_INTERVAL).await;
+        }
+
+        app.state::<AutomationState>().finish(&session_id);
+    });
+
+    state.engines.lock().unwrap().insert(
+        info.session_id.clone(),
+        Engine {
+            info: info.clone(),
+            disabled,
+            task,
+        },
+    );
+    Ok(info)
+}
@@ -0,0 +1,130 @@
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, WindowEvent, Wry};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::jobs::{self, AutomationStatus, JobsState};
+
+// Passed by the OS autostart entry so the app comes up without a window
+pub const BACKGROUND_ARG: &str = "--background";
+const MAIN_WINDOW: &str = "main";
+const TRAY_ID: &str = "main";
+
+// Menu entries whose text follows the automation status
+struct TrayMenu {
+    status: MenuItem<Wry>,
+    toggle_pause: MenuItem<Wry>,
+}
+
+pub fn launched_in_background() -> bool {
+    std::env::args().any(|arg| arg == BACKGROUND_ARG)
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    if let Err(e) = window.show().and_then(|_| window.unminimize()).and_then(|_| window.set_focus()) {
+        eprintln!("Failed to show main window: {}", e);
+    }
+}
+
+fn status_text(status: &AutomationStatus) -> String {
+    let state = if status.paused { "Paused" } else { "Running" };
+    format!("{}: {} active, {} queued, {} error(s)", state, status.running, status.queued, status.failed)
+}
+
+// Called whenever a job changes, so the tray always shows the current totals
+pub fn refresh_tray(app: &AppHandle) {
+    let Some(menu) = app.try_state::<TrayMenu>() else {
+        return;
+    };
+    let status = app.state::<JobsState>().status();
+    let toggle_text = if status.paused { "Resume all automation" } else { "Pause all automation" };
+    let result = menu
+        .status
+        .set_text(status_text(&status))
+        .and_then(|_| menu.toggle_pause.set_text(toggle_text));
+    if let Err(e) = result {
+        eprintln!("Failed to update tray menu: {}", e);
+    }
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_tooltip(Some(format!("botgacor - {}", status_text(&status)))) {
+            eprintln!("Failed to update tray tooltip: {}", e);
+        }
+    }
+}
+
+fn toggle_all_paused(app: &AppHandle) {
+    if app.state::<JobsState>().status().paused {
+        jobs::resume_all(app);
+    } else {
+        jobs::pause_all(app);
+    }
+}
+
+// The main window starts hidden. It's shown right away on a normal launch; after an autostart
+// launch it stays hidden until the tray icon is clicked, and closing it hides it again so
+// restored schedules keep running.
+pub fn setup(app: &AppHandle) -> tauri::Result<()> {
+    let status = MenuItem::with_id(app, "status", "", false, None::<&str>)?;
+    let toggle_pause = MenuItem::with_id(app, "toggle_pause", "Pause all automation", true, None::<&str>)?;
+    let show = MenuItem::with_id(app, "show", "Open dashboard", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let menu = Menu::with_items(app, &[&status, &toggle_pause, &separator, &show, &quit])?;
+
+    let mut tray = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("botgacor")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "toggle_pause" => toggle_all_paused(app),
+            "show" => show_main_window(app),
+            "quit" => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+    app.manage(TrayMenu { status, toggle_pause });
+    refresh_tray(app);
+
+    if !launched_in_background() {
+        show_main_window(app);
+        return Ok(());
+    }
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let hidden = window.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                api.prevent_close();
+                if let Err(e) = hidden.hide() {
+                    eprintln!("Failed to hide main window: {}", e);
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+pub fn autostart_enabled(app: &AppHandle) -> Result<bool, String> {
+    app.autolaunch().is_enabled().map_err(|e| format!("Failed to read autostart setting: {}", e))
+}
+
+pub fn set_autostart(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+    result.map_err(|e| format!("Failed to change autostart setting: {}", e))
+}
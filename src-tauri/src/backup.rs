@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::{account_archive, account_notes, db, encrypted_store, storage};
+
+const BACKUP_FORMAT: &str = "botgacor-backup";
+const BACKUP_VERSION: u32 = 2;
+
+// Local settings and state without credentials, copied as-is
+const PLAIN_FILES: &[&str] = &[
+    "settings.json",
+    "account_tags.json",
+    "account_userids.json",
+    "account_health.json",
+    "auto_reply.json",
+    "automation_rules.json",
+    "blackout_windows.json",
+    "disabled_items.json",
+    "item_order.json",
+    "moderation.json",
+    "operators.json",
+    "pacing.json",
+    "watch_folder.json",
+];
+// History and caches in the database
+const TABLES: &[&str] = &["product_metadata", "job_history", "session_reports", "live_samples", "showcase_snapshots", "annotations"];
+// Only exported with a passphrase. Files ending in .enc are encrypted with this machine's
+// key, so they are decrypted for the backup and re-encrypted with the new machine's on import.
+const SECRET_FILES: &[&str] = &[account_notes::NOTES_FILE, account_archive::ARCHIVE_FILE, "account_proxies.json", "discord_webhook.enc", "smtp.enc", "webhooks.enc"];
+const COOKIE_FILES: &[&str] = &["cookie_history.enc", "shopee_accounts_cache.enc"];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Secrets {
+    files: BTreeMap<String, serde_json::Value>,
+    schedules: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupFile {
+    format: String,
+    version: u32,
+    created_at: u64,
+    files: BTreeMap<String, serde_json::Value>,
+    tables: BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>,
+    // Secrets as JSON encrypted with the passphrase, see encrypted_store::passphrase_key
+    secrets: Option<String>,
+    // Hex salt for the passphrase key; absent in version 1 backups
+    #[serde(default)]
+    kdf_salt: Option<String>,
+    includes_cookies: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupSummary {
+    pub files: usize,
+    pub rows: usize,
+    pub schedules: usize,
+    pub includes_secrets: bool,
+    pub includes_cookies: bool,
+}
+
+fn read_file(app: &AppHandle, name: &str) -> Result<serde_json::Value, String> {
+    if name.ends_with(".enc") {
+        encrypted_store::load(app, name)
+    } else {
+        storage::load_json(app, name)
+    }
+}
+
+fn write_file(app: &AppHandle, name: &str, value: &serde_json::Value) -> Result<(), String> {
+    let _guard = storage::lock();
+    if name.ends_with(".enc") {
+        encrypted_store::save(app, name, value)
+    } else {
+        storage::save_json(app, name, value)
+    }
+}
+
+// Missing files load as null and are left out
+fn collect(app: &AppHandle, names: &[&str], into: &mut BTreeMap<String, serde_json::Value>) -> Result<(), String> {
+    for name in names {
+        let value = read_file(app, name)?;
+        if !value.is_null() {
+            into.insert(name.to_string(), value);
+        }
+    }
+    Ok(())
+}
+
+// Swaps a collected file for its filtered contents, dropping it when nothing is left
+fn replace_file<T: Serialize>(files: &mut BTreeMap<String, serde_json::Value>, name: &str, value: Option<T>) -> Result<(), String> {
+    match value {
+        Some(value) => {
+            let value = serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+            files.insert(name.to_string(), value);
+        }
+        None => {
+            files.remove(name);
+        }
+    }
+    Ok(())
+}
+
+// Bundles local state into one file for moving to another PC. Opted-in notes, archived
+// accounts, proxies, schedules (which hold the member's credentials) and, if asked for,
+// cookies are only included when a passphrase is given, and are encrypted with it.
+pub fn export(app: &AppHandle, path: &Path, passphrase: Option<&str>, include_cookies: bool) -> Result<BackupSummary, String> {
+    let passphrase = passphrase.map(str::trim).filter(|p| !p.is_empty());
+    if include_cookies && passphrase.is_none() {
+        return Err("A passphrase is required to include cookies in a backup".to_string());
+    }
+
+    let mut files = BTreeMap::new();
+    collect(app, PLAIN_FILES, &mut files)?;
+    let mut tables = BTreeMap::new();
+    for table in TABLES {
+        tables.insert(table.to_string(), db::dump_table(app, table)?);
+    }
+
+    let mut summary = BackupSummary {
+        files: files.len(),
+        rows: tables.values().map(Vec::len).sum(),
+        schedules: 0,
+        includes_secrets: passphrase.is_some(),
+        includes_cookies: include_cookies,
+    };
+    let kdf_salt = encrypted_store::new_passphrase_salt();
+    let secrets = match passphrase {
+        Some(passphrase) => {
+            let mut secrets = Secrets {
+                schedules: crate::scheduler::export(app)?,
+                ..Default::default()
+            };
+            collect(app, SECRET_FILES, &mut secrets.files)?;
+            // Notes and archived accounts are filtered rather than copied whole
+            let notes = account_notes::for_backup(app)?;
+            replace_file(&mut secrets.files, account_notes::NOTES_FILE, (!notes.is_empty()).then_some(notes))?;
+            let archived = account_archive::for_backup(app, include_cookies)?;
+            replace_file(&mut secrets.files, account_archive::ARCHIVE_FILE, (!archived.is_empty()).then_some(archived))?;
+            if include_cookies {
+                collect(app, COOKIE_FILES, &mut secrets.files)?;
+            }
+            summary.files += secrets.files.len();
+            summary.schedules = secrets.schedules.len();
+            let plaintext = serde_json::to_vec(&secrets).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+            Some(encrypted_store::encrypt_with_key(&encrypted_store::passphrase_key(passphrase, &kdf_salt)?, &plaintext)?)
+        }
+        None => None,
+    };
+
+    let backup = BackupFile {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        created_at: crate::unix_now(),
+        files,
+        tables,
+        kdf_salt: secrets.as_ref().map(|_| hex::encode(kdf_salt)),
+        secrets,
+        includes_cookies: include_cookies,
+    };
+    let bytes = serde_json::to_vec_pretty(&backup).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(summary)
+}
+
+// Restores a backup over the local state. Everything is read and decrypted before anything
+// is written, so a wrong passphrase leaves the current state untouched.
+pub fn import(app: &AppHandle, path: &Path, passphrase: Option<&str>) -> Result<BackupSummary, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let backup: BackupFile = serde_json::from_slice(&bytes).map_err(|_| "Not a botgacor backup file".to_string())?;
+    if backup.format != BACKUP_FORMAT {
+        return Err("Not a botgacor backup file".to_string());
+    }
+    if backup.version > BACKUP_VERSION {
+        return Err("This backup was made by a newer version of the app, please update".to_string());
+    }
+
+    let secrets = match (&backup.secrets, passphrase.map(str::trim).filter(|p| !p.is_empty())) {
+        (Some(encrypted), Some(passphrase)) => {
+            let key = match &backup.kdf_salt {
+                Some(salt) => {
+                    let salt = hex::decode(salt).map_err(|_| "Invalid backup: bad key salt".to_string())?;
+                    encrypted_store::passphrase_key(passphrase, &salt)?
+                }
+                None if backup.version < 2 => encrypted_store::legacy_passphrase_key(passphrase),
+                None => return Err("Invalid backup: missing key salt".to_string()),
+            };
+            let plaintext = encrypted_store::decrypt_with_key(&key, encrypted)
+                .map_err(|_| "Wrong passphrase for this backup".to_string())?;
+            let secrets: Secrets = serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid backup: {}", e))?;
+            Some(secrets)
+        }
+        (Some(_), None) => return Err("This backup contains encrypted data, enter its passphrase".to_string()),
+        (None, _) => None,
+    };
+
+    // Only known file names, so a crafted backup can't write elsewhere in the data dir
+    let unexpected = backup.files.keys().find(|name| !PLAIN_FILES.contains(&name.as_str())).or_else(|| {
+        secrets.as_ref().and_then(|secrets| {
+            secrets
+                .files
+                .keys()
+                .find(|name| !SECRET_FILES.contains(&name.as_str()) && !COOKIE_FILES.contains(&name.as_str()))
+        })
+    });
+    if let Some(name) = unexpected {
+        return Err(format!("Unexpected file {} in backup", name));
+    }
+
+    let mut summary = BackupSummary {
+        files: 0,
+        rows: 0,
+        schedules: 0,
+        includes_secrets: secrets.is_some(),
+        includes_cookies: backup.includes_cookies && secrets.is_some(),
+    };
+    for (name, value) in &backup.files {
+        write_file(app, name, value)?;
+        summary.files += 1;
+    }
+    for (table, rows) in &backup.tables {
+        if TABLES.contains(&table.as_str()) {
+            summary.rows += db::load_table(app, table, rows)?;
+        }
+    }
+    if let Some(secrets) = secrets {
+        for (name, value) in &secrets.files {
+            write_file(app, name, value)?;
+            summary.files += 1;
+        }
+        summary.schedules = crate::scheduler::import(app, secrets.schedules)?;
+    }
+
+    // Settings are cached in memory
+    crate::settings::init(app);
+    Ok(summary)
+}
@@ -0,0 +1,96 @@
+use chrono::{Datelike, Local, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::storage;
+
+const BLACKOUT_FILE: &str = "blackout_windows.json";
+
+// A daily local-time range during which automation must not act. `end` earlier than
+// `start` means the window runs past midnight. Empty `days` means every day
+// (0 = Monday ... 6 = Sunday, for the day the window starts).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlackoutWindow {
+    pub label: Option<String>,
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| format!("Invalid time '{}', expected HH:MM", value))
+}
+
+pub fn validate(windows: &[BlackoutWindow]) -> Result<(), String> {
+    for (i, window) in windows.iter().enumerate() {
+        let start = parse_time(&window.start).map_err(|e| format!("Window {}: {}", i + 1, e))?;
+        let end = parse_time(&window.end).map_err(|e| format!("Window {}: {}", i + 1, e))?;
+        if start == end {
+            return Err(format!("Window {}: start and end are the same", i + 1));
+        }
+        if window.days.iter().any(|d| *d > 6) {
+            return Err(format!("Window {}: days must be between 0 (Monday) and 6 (Sunday)", i + 1));
+        }
+    }
+    Ok(())
+}
+
+pub fn load(app: &AppHandle) -> Result<Vec<BlackoutWindow>, String> {
+    storage::load_json(app, BLACKOUT_FILE)
+}
+
+pub fn save(app: &AppHandle, windows: &[BlackoutWindow]) -> Result<(), String> {
+    validate(windows)?;
+    storage::save_json(app, BLACKOUT_FILE, &windows)
+}
+
+fn seconds_of_day(time: NaiveTime) -> i64 {
+    time.num_seconds_from_midnight() as i64
+}
+
+// Seconds until this window ends if `now` falls inside it
+fn remaining_in(window: &BlackoutWindow, weekday: u8, now_secs: i64) -> Option<i64> {
+    let start = seconds_of_day(parse_time(&window.start).ok()?);
+    let end = seconds_of_day(parse_time(&window.end).ok()?);
+    let applies = |day: u8| window.days.is_empty() || window.days.contains(&day);
+    let yesterday = (weekday + 6) % 7;
+
+    if start < end {
+        (applies(weekday) && now_secs >= start && now_secs < end).then_some(end - now_secs)
+    } else if now_secs >= start && applies(weekday) {
+        // Started today, ends after midnight
+        Some(24 * 3600 - now_secs + end)
+    } else if now_secs < end && applies(yesterday) {
+        // Started yesterday, still running this morning
+        Some(end - now_secs)
+    } else {
+        None
+    }
+}
+
+// How long until automation may act again, or None when no window is active
+pub fn active_remaining(app: &AppHandle) -> Option<Duration> {
+    let windows = load(app).unwrap_or_else(|e| {
+        eprintln!("[BLACKOUT] Failed to load windows: {}", e);
+        vec![]
+    });
+    let now = Local::now();
+    let weekday = now.weekday().num_days_from_monday() as u8;
+    let now_secs = now.num_seconds_from_midnight() as i64;
+
+    windows
+        .iter()
+        .filter_map(|w| remaining_in(w, weekday, now_secs))
+        .max()
+        .map(|secs| Duration::from_secs(secs as u64))
+}
+
+// Holds an automated action until every overlapping blackout window has passed
+pub async fn wait_until_clear(app: &AppHandle) {
+    while let Some(remaining) = active_remaining(app) {
+        println!("[BLACKOUT] Delaying automation for {}s", remaining.as_secs());
+        tokio::time::sleep(remaining + Duration::from_secs(1)).await;
+    }
+}
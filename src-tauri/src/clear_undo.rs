@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::product_items::ProductSetItemInput;
+use crate::storage;
+use crate::unix_now;
+use crate::LiveShowcaseItem;
+
+// Snapshots taken right before a clear, so the last clear of a set or showcase can be undone
+const UNDO_FILE: &str = "clear_undo.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetSnapshot {
+    pub items: Vec<ProductSetItemInput>,
+    pub cleared_at: u64,
+}
+
+// What a live session's showcase was last filled from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShowcasePush {
+    pub shopee_account_id: i32,
+    pub product_set_id: i32,
+    pub pushed_at: u64,
+}
+
+// What a live session's showcase held right before it was cleared
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearedShowcase {
+    pub shopee_account_id: i32,
+    // The set the showcase was last filled from, when known
+    #[serde(default)]
+    pub product_set_id: Option<i32>,
+    #[serde(default)]
+    pub items: Vec<LiveShowcaseItem>,
+    #[serde(default)]
+    pub cleared_at: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UndoStore {
+    #[serde(default)]
+    sets: HashMap<i32, SetSnapshot>,
+    #[serde(default)]
+    last_pushes: HashMap<String, ShowcasePush>,
+    #[serde(default)]
+    cleared_sessions: HashMap<String, ClearedShowcase>,
+}
+
+pub fn snapshot_set(app: &AppHandle, product_set_id: i32, items: Vec<ProductSetItemInput>) -> Result<(), String> {
+    storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| {
+        store.sets.insert(product_set_id, SetSnapshot { items, cleared_at: unix_now() });
+    })
+}
+
+pub fn take_set(app: &AppHandle, product_set_id: i32) -> Result<Option<SetSnapshot>, String> {
+    storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| store.sets.remove(&product_set_id))
+}
+
+pub fn record_push(app: &AppHandle, session_id: &str, shopee_account_id: i32, product_set_id: i32) {
+    let push = ShowcasePush {
+        shopee_account_id,
+        product_set_id,
+        pushed_at: unix_now(),
+    };
+    if let Err(e) = storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| {
+        store.last_pushes.insert(session_id.to_string(), push);
+    }) {
+        eprintln!("[UNDO] Failed to record push for session {}: {}", session_id, e);
+    }
+}
+
+// Keeps what the showcase held before a clear, along with the set it was last filled from
+pub fn snapshot_session(app: &AppHandle, session_id: &str, shopee_account_id: i32, items: Vec<LiveShowcaseItem>) -> Result<(), String> {
+    storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| {
+        let product_set_id = store.last_pushes.remove(session_id).map(|push| push.product_set_id);
+        let cleared = ClearedShowcase {
+            shopee_account_id,
+            product_set_id,
+            items,
+            cleared_at: unix_now(),
+        };
+        store.cleared_sessions.insert(session_id.to_string(), cleared);
+    })
+}
+
+pub fn take_session(app: &AppHandle, session_id: &str) -> Result<Option<ClearedShowcase>, String> {
+    storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| store.cleared_sessions.remove(session_id))
+}
+
+// Puts a session snapshot back, e.g. when restoring it failed
+pub fn restore_session(app: &AppHandle, session_id: &str, cleared: ClearedShowcase) -> Result<(), String> {
+    storage::update_json(app, UNDO_FILE, |store: &mut UndoStore| {
+        store.cleared_sessions.insert(session_id.to_string(), cleared);
+    })
+}
@@ -0,0 +1,89 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::shopee_url::{self, ParsedShopeeUrl};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+// A pasted list is parsed up to this many URLs
+const MAX_URLS_PER_COPY: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+struct ProductUrlCopiedEvent {
+    #[serde(flatten)]
+    product: ParsedShopeeUrl,
+    copied_at: u64,
+}
+
+// Off unless the user turns it on; clipboard contents never leave this module except as
+// parsed product IDs
+#[derive(Default)]
+pub struct ClipboardWatchState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ClipboardWatchState {
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+}
+
+// Words of the copied text that look like Shopee links, e.g. from a chat message
+fn candidate_urls(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| matches!(c, '"' | '\'' | '<' | '>' | '(' | ')' | ',')))
+        .filter(|word| word.starts_with("http://") || word.starts_with("https://"))
+        .filter(|word| url::Url::parse(word).ok().and_then(|u| u.host_str().map(shopee_url::is_shopee_host)).unwrap_or(false))
+        .take(MAX_URLS_PER_COPY)
+        .collect()
+}
+
+async fn announce(app: &AppHandle, text: &str) {
+    for raw in candidate_urls(text) {
+        match shopee_url::parse(raw).await {
+            Ok(product) => {
+                let event = ProductUrlCopiedEvent {
+                    product,
+                    copied_at: crate::unix_now(),
+                };
+                if let Err(e) = app.emit("product-url-copied", event) {
+                    eprintln!("Failed to emit product-url-copied: {}", e);
+                }
+            }
+            // Shop pages and other non-product links are expected here
+            Err(e) => println!("[CLIPBOARD] Ignored copied link: {}", e),
+        }
+    }
+}
+
+// Polls the clipboard and emits `product-url-copied` for each Shopee product link in newly
+// copied text. Whatever is on the clipboard when watching starts is not reported.
+pub fn start(app: &AppHandle, state: &ClipboardWatchState) {
+    state.stop();
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut last = app.clipboard().read_text().ok();
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            // Images and other non-text contents fail to read and are skipped
+            let Ok(text) = app.clipboard().read_text() else {
+                continue;
+            };
+            if last.as_deref() == Some(text.as_str()) {
+                continue;
+            }
+            announce(&app, &text).await;
+            last = Some(text);
+        }
+    });
+    *state.task.lock().unwrap() = Some(task);
+}
@@ -0,0 +1,69 @@
+use rand::Rng;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::CommandError;
+
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(30);
+
+// What an irreversible command is about to destroy, shown before the user confirms
+#[derive(Debug, Clone, Serialize)]
+pub struct Impact {
+    pub summary: String,
+    pub product_sets: usize,
+    pub items: usize,
+}
+
+// Result of a destructive command: the first call only returns a token and the impact,
+// the action runs when the token is echoed back before it expires
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DestructiveOutcome {
+    ConfirmationRequired { token: String, expires_in_secs: u64, impact: Impact },
+    Done,
+}
+
+struct PendingConfirmation {
+    action: String,
+    issued_at: Instant,
+}
+
+#[derive(Default)]
+pub struct ConfirmationState {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+}
+
+impl ConfirmationState {
+    // `action` identifies both the command and its target, e.g. "delete_niche:12"
+    pub fn issue(&self, action: &str, impact: Impact) -> DestructiveOutcome {
+        let token: String = (0..16).map(|_| format!("{:x}", rand::thread_rng().gen_range(0..16u8))).collect();
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, p| p.issued_at.elapsed() < CONFIRMATION_TTL);
+        pending.insert(
+            token.clone(),
+            PendingConfirmation {
+                action: action.to_string(),
+                issued_at: Instant::now(),
+            },
+        );
+
+        DestructiveOutcome::ConfirmationRequired {
+            token,
+            expires_in_secs: CONFIRMATION_TTL.as_secs(),
+            impact,
+        }
+    }
+
+    // Tokens are single use and only valid for the action they were issued for
+    pub fn consume(&self, token: &str, action: &str) -> Result<(), CommandError> {
+        let pending = self.pending.lock().unwrap().remove(token);
+        match pending {
+            Some(p) if p.action == action && p.issued_at.elapsed() < CONFIRMATION_TTL => Ok(()),
+            Some(p) if p.action == action => Err("Confirmation expired, please try again".into()),
+            _ => Err("Invalid confirmation token".into()),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+
+use crate::storage;
+use crate::{ShopeeAccount, ShopeeAccountInfo, ShopeeApiFailure};
+
+const HEALTH_FILE: &str = "account_health.json";
+const MAX_CONCURRENT_CHECKS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CookieStatus {
+    Valid,
+    Expired,
+    Banned,
+    RateLimited,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CookieHealth {
+    pub account_id: i32,
+    pub status: CookieStatus,
+    pub username: Option<String>,
+    pub detail: Option<String>,
+    pub checked_at: u64,
+}
+
+// Shopee error codes seen from get_account_info
+const NOT_LOGGED_IN_ERRORS: &[i32] = &[19, 20];
+const RATE_LIMIT_ERRORS: &[i32] = &[90309999];
+
+pub fn evaluate(account_id: i32, result: &Result<ShopeeAccountInfo, ShopeeApiFailure>) -> CookieHealth {
+    let (status, username, detail) = match result {
+        Ok(info) => (CookieStatus::Valid, Some(info.username.clone()), None),
+        Err(failure) => (classify(failure), None, Some(failure.message.clone())),
+    };
+
+    CookieHealth {
+        account_id,
+        status,
+        username,
+        detail,
+        checked_at: crate::unix_now(),
+    }
+}
+
+fn classify(failure: &ShopeeApiFailure) -> CookieStatus {
+    let message = failure.message.to_lowercase();
+
+    if failure.http_status == Some(429)
+        || failure.error_code.is_some_and(|c| RATE_LIMIT_ERRORS.contains(&c))
+        || message.contains("too many")
+    {
+        return CookieStatus::RateLimited;
+    }
+    if message.contains("ban") || message.contains("suspend") || message.contains("frozen") {
+        return CookieStatus::Banned;
+    }
+    if matches!(failure.http_status, Some(401) | Some(403))
+        || failure.error_code.is_some_and(|c| NOT_LOGGED_IN_ERRORS.contains(&c))
+        || message.contains("not login")
+        || message.contains("no account info")
+    {
+        return CookieStatus::Expired;
+    }
+
+    CookieStatus::Unknown
+}
+
+impl CookieStatus {
+    // Needs a new login before the account can be used again
+    fn is_dead(self) -> bool {
+        matches!(self, CookieStatus::Expired | CookieStatus::Banned)
+    }
+}
+
+fn all_dead(map: &HashMap<i32, CookieHealth>) -> bool {
+    !map.is_empty() && map.values().all(|h| h.status.is_dead())
+}
+
+// Remember the latest result per account so exports and dashboards can show it. Raises the
+// AllCookiesDead alert when this result leaves no account with a usable cookie.
+pub fn record(app: &AppHandle, health: &CookieHealth) {
+    let result = storage::update_json(app, HEALTH_FILE, |map: &mut HashMap<i32, CookieHealth>| {
+        let was_all_dead = all_dead(map);
+        map.insert(health.account_id, health.clone());
+        (!was_all_dead && all_dead(map)).then_some(map.len())
+    });
+    match result {
+        Ok(Some(accounts)) => crate::notifications::all_cookies_dead(app, accounts),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to record cookie health for account {}: {}", health.account_id, e),
+    }
+}
+
+// A deleted account shouldn't count towards (or against) the AllCookiesDead alert
+pub fn remove(app: &AppHandle, account_id: i32) -> Result<(), String> {
+    storage::update_json(app, HEALTH_FILE, |map: &mut HashMap<i32, CookieHealth>| {
+        map.remove(&account_id);
+    })
+}
+
+pub fn load_all(app: &AppHandle) -> HashMap<i32, CookieHealth> {
+    storage::load_json(app, HEALTH_FILE).unwrap_or_default()
+}
+
+async fn check_account(account: &ShopeeAccount, proxy: Option<&str>) -> CookieHealth {
+    let Some(cookie) = account.cookie.as_deref().filter(|c| !c.is_empty()) else {
+        return CookieHealth {
+            account_id: account.id,
+            status: CookieStatus::Unknown,
+            username: None,
+            detail: Some("No cookie stored for this account".to_string()),
+            checked_at: crate::unix_now(),
+        };
+    };
+    evaluate(account.id, &crate::fetch_account_info(cookie, proxy).await)
+}
+
+// Checks every account concurrently, emitting `account-checked` as each one finishes
+pub async fn check_all(app: &AppHandle, accounts: Vec<ShopeeAccount>) -> Vec<CookieHealth> {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_CHECKS));
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for account in accounts {
+        let semaphore = semaphore.clone();
+        let proxy = crate::account_proxies::proxy_for(app, account.id);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            check_account(&account, proxy.as_deref()).await
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let Ok(health) = joined else {
+            continue;
+        };
+        record(app, &health);
+        if let Err(e) = app.emit("account-checked", health.clone()) {
+            eprintln!("Failed to emit account-checked: {}", e);
+        }
+        results.push(health);
+    }
+
+    results.sort_by_key(|h| h.account_id);
+    results
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::encrypted_store;
+
+const HISTORY_FILE: &str = "cookie_history.enc";
+const MAX_VERSIONS: usize = 5;
+
+// A cookie that was replaced, kept so a broken replacement can be rolled back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CookieVersion {
+    pub cookie: String,
+    pub replaced_at: u64,
+}
+
+// What the UI gets to see: when each kept version was replaced, never the cookie itself
+#[derive(Debug, Clone, Serialize)]
+pub struct CookieVersionSummary {
+    pub replaced_at: u64,
+}
+
+type HistoryMap = HashMap<i32, Vec<CookieVersion>>;
+
+// Newest version is last
+pub fn record(app: &AppHandle, account_id: i32, previous_cookie: &str) {
+    if previous_cookie.is_empty() {
+        return;
+    }
+    let version = CookieVersion {
+        cookie: previous_cookie.to_string(),
+        replaced_at: crate::unix_now(),
+    };
+    if let Err(e) = encrypted_store::update(app, HISTORY_FILE, |map: &mut HistoryMap| {
+        let versions = map.entry(account_id).or_default();
+        versions.push(version);
+        if versions.len() > MAX_VERSIONS {
+            versions.remove(0);
+        }
+    }) {
+        eprintln!("Failed to record cookie history for account {}: {}", account_id, e);
+    }
+}
+
+pub fn list(app: &AppHandle, account_id: i32) -> Result<Vec<CookieVersionSummary>, String> {
+    let map: HistoryMap = encrypted_store::load(app, HISTORY_FILE)?;
+    Ok(map
+        .get(&account_id)
+        .map(|versions| {
+            versions
+                .iter()
+                .rev()
+                .map(|v| CookieVersionSummary { replaced_at: v.replaced_at })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+pub fn pop_latest(app: &AppHandle, account_id: i32) -> Result<Option<CookieVersion>, String> {
+    encrypted_store::update(app, HISTORY_FILE, |map: &mut HistoryMap| {
+        map.get_mut(&account_id).and_then(|versions| versions.pop())
+    })
+}
+
+// Put a version back when the rollback itself failed to upload
+pub fn restore(app: &AppHandle, account_id: i32, version: CookieVersion) {
+    if let Err(e) = encrypted_store::update(app, HISTORY_FILE, |map: &mut HistoryMap| {
+        map.entry(account_id).or_default().push(version);
+    }) {
+        eprintln!("Failed to restore cookie history for account {}: {}", account_id, e);
+    }
+}
+
+pub fn remove(app: &AppHandle, account_id: i32) -> Result<(), String> {
+    encrypted_store::update(app, HISTORY_FILE, |map: &mut HistoryMap| {
+        map.remove(&account_id);
+    })
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+use crate::cookie_health::{self, CookieHealth, CookieStatus};
+use crate::cookies::CookieJar;
+
+const MIN_INTERVAL_MINUTES: u64 = 10;
+
+#[derive(Debug, Clone, Serialize)]
+struct CookieRefreshedEvent {
+    health: CookieHealth,
+    cookie_updated: bool,
+}
+
+#[derive(Default)]
+pub struct CookieKeepAliveState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl CookieKeepAliveState {
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+}
+
+// Apply Set-Cookie headers to an existing Cookie header value, so cookies Shopee deletes
+// are dropped as well as new ones added
+fn merge_cookies(existing: &str, set_cookie_headers: &[String]) -> String {
+    let mut jar = CookieJar::parse_header(existing);
+    for header in set_cookie_headers {
+        jar.apply_set_cookie(header);
+    }
+    jar.to_header()
+}
+
+async fn refresh_all(app: &AppHandle, email: &str, password: &str) -> Result<(), String> {
+    let accounts = crate::fetch_shopee_accounts(email, password)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for account in accounts.data {
+        let Some(cookie) = account.cookie.clone().filter(|c| !c.is_empty()) else {
+            continue;
+        };
+
+        let proxy = crate::account_proxies::proxy_for(app, account.id);
+        let result = crate::request_account_info(&cookie, proxy.as_deref()).await;
+        let mut cookie_updated = false;
+
+        if let Ok((_, set_cookie_headers)) = &result {
+            let merged = merge_cookies(&cookie, set_cookie_headers);
+            if merged != cookie {
+                match crate::put_shopee_account(
+                    app,
+                    email.to_string(),
+                    password.to_string(),
+                    account.id,
+                    account.name.clone(),
+                    merged,
+                    account.is_active,
+                )
+                .await
+                {
+                    Ok(_) => {
+                        crate::cookie_history::record(app, account.id, &cookie);
+                        cookie_updated = true;
+                    }
+                    Err(e) => eprintln!("[COOKIE KEEPALIVE] Failed to save cookie for account {}: {}", account.id, e),
+                }
+            }
+        }
+
+        let health = cookie_health::evaluate(account.id, &result.map(|(info, _)| info));
+        cookie_health::record(app, &health);
+        if health.status != CookieStatus::Valid {
+            println!("[COOKIE KEEPALIVE] Account {} cookie status: {:?}", account.id, health.status);
+        }
+        if let Err(e) = app.emit("cookie-refreshed", CookieRefreshedEvent { health, cookie_updated }) {
+            eprintln!("Failed to emit cookie-refreshed: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+// Periodically exercises every stored cookie against Shopee and saves any rotated
+// session cookies back to livekenceng so accounts stay logged in longer
+pub fn start(app: &AppHandle, state: &CookieKeepAliveState, email: String, password: String, interval_minutes: u64) {
+    state.stop();
+
+    let interval = Duration::from_secs(interval_minutes.max(MIN_INTERVAL_MINUTES) * 60);
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = refresh_all(&app, &email, &password).await {
+                eprintln!("[COOKIE KEEPALIVE] Refresh failed: {}", e);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    *state.task.lock().unwrap() = Some(task);
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, NaiveDateTime};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    // Unix seconds; None for session cookies
+    pub expires_at: Option<i64>,
+}
+
+// Name=value cookie store built from Cookie/Set-Cookie headers. Attributes such as
+// Path, Domain and HttpOnly are dropped; later values for the same name win.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CookieJar {
+    cookies: Vec<Cookie>,
+}
+
+fn parse_expires(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date.timestamp());
+    }
+    // Older servers use dashes: "Wed, 21-Oct-2026 07:28:00 GMT"
+    NaiveDateTime::parse_from_str(value, "%a, %d-%b-%Y %H:%M:%S GMT")
+        .ok()
+        .map(|date| date.and_utc().timestamp())
+}
+
+impl CookieJar {
+    // Parse a request Cookie header ("a=1; b=2")
+    pub fn parse_header(header: &str) -> Self {
+        let mut jar = CookieJar::default();
+        for pair in header.split(';') {
+            if let Some((name, value)) = pair.trim().split_once('=') {
+                jar.insert(Cookie {
+                    name: name.trim().to_string(),
+                    value: value.trim().to_string(),
+                    expires_at: None,
+                });
+            }
+        }
+        jar
+    }
+
+    pub fn from_set_cookie_headers(headers: &[String]) -> Self {
+        let mut jar = CookieJar::default();
+        for header in headers {
+            jar.apply_set_cookie(header);
+        }
+        jar
+    }
+
+    // Apply one Set-Cookie header; a cookie that is already expired is removed
+    pub fn apply_set_cookie(&mut self, header: &str) {
+        let mut parts = header.split(';');
+        let Some((name, value)) = parts.next().and_then(|p| p.trim().split_once('=')) else {
+            return;
+        };
+
+        let now = crate::unix_now() as i64;
+        let mut expires_at = None;
+        for attribute in parts {
+            let Some((key, attr_value)) = attribute.trim().split_once('=') else {
+                continue;
+            };
+            match key.trim().to_ascii_lowercase().as_str() {
+                // Max-Age takes precedence over Expires
+                "max-age" => {
+                    if let Ok(seconds) = attr_value.trim().parse::<i64>() {
+                        expires_at = Some(now + seconds);
+                        break;
+                    }
+                }
+                "expires" => expires_at = parse_expires(attr_value),
+                _ => {}
+            }
+        }
+
+        let name = name.trim().to_string();
+        if expires_at.is_some_and(|at| at <= now) {
+            self.cookies.retain(|c| c.name != name);
+            return;
+        }
+
+        self.insert(Cookie {
+            name,
+            value: value.trim().to_string(),
+            expires_at,
+        });
+    }
+
+    fn insert(&mut self, cookie: Cookie) {
+        match self.cookies.iter_mut().find(|c| c.name == cookie.name) {
+            Some(existing) => *existing = cookie,
+            None => self.cookies.push(cookie),
+        }
+    }
+
+    // Earliest expiry among cookies that carry one
+    pub fn earliest_expiry(&self) -> Option<i64> {
+        self.cookies.iter().filter_map(|c| c.expires_at).min()
+    }
+
+    // Render as a Cookie request header, skipping anything that has expired
+    pub fn to_header(&self) -> String {
+        let now = crate::unix_now() as i64;
+        self.cookies
+            .iter()
+            .filter(|c| c.expires_at.is_none_or(|at| at > now))
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookie<'a>(jar: &'a CookieJar, name: &str) -> Option<&'a Cookie> {
+        jar.cookies.iter().find(|c| c.name == name)
+    }
+
+    fn jar(headers: &[&str]) -> CookieJar {
+        CookieJar::from_set_cookie_headers(&headers.iter().map(|h| h.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn session_cookie_has_no_expiry() {
+        let jar = jar(&["SPC_EC=abc; Path=/; HttpOnly; Secure"]);
+        let c = cookie(&jar, "SPC_EC").unwrap();
+        assert_eq!(c.value, "abc");
+        assert_eq!(c.expires_at, None);
+        assert_eq!(jar.to_header(), "SPC_EC=abc");
+    }
+
+    #[test]
+    fn value_may_contain_equals_signs() {
+        let jar = jar(&["token=a=b==; Path=/"]);
+        assert_eq!(cookie(&jar, "token").unwrap().value, "a=b==");
+    }
+
+    #[test]
+    fn max_age_sets_expiry_from_now() {
+        let before = crate::unix_now() as i64;
+        let jar = jar(&["a=1; Max-Age=3600"]);
+        let at = cookie(&jar, "a").unwrap().expires_at.unwrap();
+        assert!(at >= before + 3600 && at <= crate::unix_now() as i64 + 3600);
+    }
+
+    #[test]
+    fn max_age_wins_over_expires_in_either_order() {
+        let past = "Wed, 21 Oct 2015 07:28:00 GMT";
+        let jar = jar(&[&format!("a=1; Max-Age=3600; Expires={}", past), &format!("b=2; Expires={}; Max-Age=3600", past)]);
+        let now = crate::unix_now() as i64;
+        assert!(cookie(&jar, "a").unwrap().expires_at.unwrap() > now);
+        assert!(cookie(&jar, "b").unwrap().expires_at.unwrap() > now);
+    }
+
+    #[test]
+    fn invalid_max_age_falls_back_to_expires() {
+        let jar = jar(&["a=1; Max-Age=soon; Expires=Fri, 01 Jan 2100 00:00:00 GMT"]);
+        assert_eq!(cookie(&jar, "a").unwrap().expires_at, Some(4102444800));
+    }
+
+    #[test]
+    fn expires_accepts_rfc_2822_and_dashed_dates() {
+        let jar = jar(&["a=1; Expires=Fri, 01 Jan 2100 00:00:00 GMT", "b=2; expires=Fri, 01-Jan-2100 00:00:00 GMT"]);
+        assert_eq!(cookie(&jar, "a").unwrap().expires_at, Some(4102444800));
+        assert_eq!(cookie(&jar, "b").unwrap().expires_at, Some(4102444800));
+    }
+
+    #[test]
+    fn unparseable_expires_is_a_session_cookie() {
+        let jar = jar(&["a=1; Expires=someday"]);
+        assert_eq!(cookie(&jar, "a").unwrap().expires_at, None);
+    }
+
+    #[test]
+    fn attribute_names_are_case_insensitive() {
+        let jar = jar(&["a=1; MAX-AGE=60", "b=2; EXPIRES=Fri, 01 Jan 2100 00:00:00 GMT", "c=3; max-age=0"]);
+        assert!(cookie(&jar, "a").unwrap().expires_at.is_some());
+        assert_eq!(cookie(&jar, "b").unwrap().expires_at, Some(4102444800));
+        assert!(cookie(&jar, "c").is_none());
+    }
+
+    #[test]
+    fn later_duplicate_replaces_earlier() {
+        let jar = jar(&["a=1", "b=2", "a=3; Max-Age=60"]);
+        assert_eq!(jar.cookies.len(), 2);
+        let a = cookie(&jar, "a").unwrap();
+        assert_eq!(a.value, "3");
+        assert!(a.expires_at.is_some());
+        assert_eq!(jar.to_header(), "a=3; b=2");
+    }
+
+    #[test]
+    fn expired_set_cookie_deletes_existing_cookie() {
+        let jar = jar(&["a=1", "b=2", "a=deleted; Expires=Thu, 01 Jan 1970 00:00:00 GMT"]);
+        assert!(cookie(&jar, "a").is_none());
+        assert_eq!(jar.to_header(), "b=2");
+    }
+
+    #[test]
+    fn expired_set_cookie_deletes_cookie_from_a_request_header() {
+        let mut jar = CookieJar::parse_header("a=1; b=2; c=3");
+        jar.apply_set_cookie("a=; Max-Age=0");
+        jar.apply_set_cookie("b=deleted; Expires=Thu, 01 Jan 1970 00:00:00 GMT");
+        jar.apply_set_cookie("c=4");
+        assert_eq!(jar.to_header(), "c=4");
+    }
+
+    #[test]
+    fn headers_without_a_pair_are_ignored() {
+        let jar = jar(&["", "garbage", "a=1"]);
+        assert_eq!(jar.to_header(), "a=1");
+    }
+
+    #[test]
+    fn earliest_expiry_skips_session_cookies() {
+        let jar = jar(&["a=1", "b=2; Expires=Fri, 01 Jan 2100 00:00:00 GMT", "c=3; Expires=Thu, 01 Jan 2099 00:00:00 GMT"]);
+        assert_eq!(jar.earliest_expiry(), Some(4070908800));
+    }
+}
@@ -0,0 +1,198 @@
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::storage;
+
+pub const DB_FILE: &str = "botgacor.db";
+
+// Applied in order; the database's user_version is the number already applied. Never edit
+// a shipped migration, add a new one instead.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE product_metadata (
+        shop_id INTEGER NOT NULL,
+        item_id INTEGER NOT NULL,
+        data TEXT NOT NULL,
+        fetched_at INTEGER NOT NULL,
+        PRIMARY KEY (shop_id, item_id)
+    );
+    CREATE INDEX product_metadata_item ON product_metadata (item_id);
+
+    CREATE TABLE job_history (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        shopee_account_id INTEGER,
+        state TEXT NOT NULL,
+        finished_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX job_history_finished ON job_history (finished_at);
+
+    CREATE TABLE session_reports (
+        session_id TEXT PRIMARY KEY,
+        shopee_account_id INTEGER NOT NULL,
+        ended_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX session_reports_ended ON session_reports (ended_at);
+
+    CREATE TABLE schedules (
+        id TEXT PRIMARY KEY,
+        data TEXT NOT NULL
+    );
+
+    CREATE TABLE showcase_snapshots (
+        session_id TEXT PRIMARY KEY,
+        shopee_account_id INTEGER NOT NULL,
+        taken_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );",
+    "CREATE TABLE annotations (
+        target TEXT NOT NULL,
+        target_id INTEGER NOT NULL,
+        data TEXT NOT NULL,
+        updated_at INTEGER NOT NULL,
+        PRIMARY KEY (target, target_id)
+    );",
+    "CREATE TABLE audit_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        at INTEGER NOT NULL,
+        operator TEXT,
+        action TEXT NOT NULL,
+        target TEXT,
+        details TEXT NOT NULL
+    );
+    CREATE INDEX audit_log_at ON audit_log (at);
+    CREATE INDEX audit_log_action ON audit_log (action);
+    CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+        BEGIN SELECT RAISE(ABORT, 'The audit log is append-only'); END;
+    CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+        BEGIN SELECT RAISE(ABORT, 'The audit log is append-only'); END;",
+    "CREATE TABLE job_checkpoints (
+        job_id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        label TEXT NOT NULL,
+        shopee_account_id INTEGER,
+        updated_at INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );",
+    "CREATE TABLE live_samples (
+        session_id TEXT NOT NULL,
+        shopee_account_id INTEGER NOT NULL,
+        at INTEGER NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (session_id, at)
+    );
+    CREATE INDEX live_samples_at ON live_samples (at);",
+];
+
+// The app's SQLite database, opened during setup
+#[derive(Default)]
+pub struct DbState {
+    conn: Mutex<Option<Connection>>,
+}
+
+fn migrate(conn: &mut Connection) -> Result<(), String> {
+    let applied: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read database version: {}", e))?;
+    if applied > MIGRATIONS.len() {
+        return Err(format!(
+            "Database was created by a newer version of the app (version {}), please update",
+            applied
+        ));
+    }
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = conn.transaction().map_err(|e| format!("Failed to start migration: {}", e))?;
+        tx.execute_batch(sql).map_err(|e| format!("Migration {} failed: {}", index + 1, e))?;
+        tx.pragma_update(None, "user_version", index + 1)
+            .map_err(|e| format!("Failed to record migration {}: {}", index + 1, e))?;
+        tx.commit().map_err(|e| format!("Failed to commit migration {}: {}", index + 1, e))?;
+    }
+    Ok(())
+}
+
+// Opens the database in the app data dir and brings its schema up to date
+pub fn open(app: &AppHandle) -> Result<(), String> {
+    let path = storage::data_dir(app)?.join(DB_FILE);
+    let mut conn = Connection::open(&path).map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+    migrate(&mut conn)?;
+    *app.state::<DbState>().conn.lock().unwrap() = Some(conn);
+    Ok(())
+}
+
+pub fn with<T>(app: &AppHandle, f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let state = app.state::<DbState>();
+    let mut conn = state.conn.lock().unwrap();
+    let conn = conn.as_mut().ok_or("Database is not open")?;
+    f(conn).map_err(|e| format!("Database error: {}", e))
+}
+
+pub fn to_json<T: serde::Serialize>(value: &T) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+pub fn from_json<T: serde::de::DeserializeOwned>(data: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(data).map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+type Row = serde_json::Map<String, serde_json::Value>;
+
+// Every row of `table` as column -> value, for backups
+pub fn dump_table(app: &AppHandle, table: &str) -> Result<Vec<Row>, String> {
+    with(app, |conn| {
+        let mut statement = conn.prepare(&format!("SELECT * FROM {}", table))?;
+        let columns: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+        let mut rows = statement.query([])?;
+        let mut dumped = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut values = Row::new();
+            for (index, column) in columns.iter().enumerate() {
+                let value = match row.get::<_, Value>(index)? {
+                    Value::Null => serde_json::Value::Null,
+                    Value::Integer(n) => n.into(),
+                    Value::Real(n) => n.into(),
+                    Value::Text(s) => s.into(),
+                    Value::Blob(_) => continue,
+                };
+                values.insert(column.clone(), value);
+            }
+            dumped.push(values);
+        }
+        Ok(dumped)
+    })
+}
+
+// Inserts rows produced by `dump_table`, replacing rows with the same key
+pub fn load_table(app: &AppHandle, table: &str, rows: &[Row]) -> Result<usize, String> {
+    for column in rows.iter().flat_map(|row| row.keys()) {
+        if column.is_empty() || !column.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid column '{}' for {}", column, table));
+        }
+    }
+    with(app, |conn| {
+        let tx = conn.transaction()?;
+        for row in rows {
+            let columns: Vec<&str> = row.keys().map(String::as_str).collect();
+            let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+            let values: Vec<Value> = row
+                .values()
+                .map(|value| match value {
+                    serde_json::Value::Null => Value::Null,
+                    serde_json::Value::Number(n) => n.as_i64().map(Value::Integer).unwrap_or_else(|| Value::Real(n.as_f64().unwrap_or_default())),
+                    serde_json::Value::String(s) => Value::Text(s.clone()),
+                    other => Value::Text(other.to_string()),
+                })
+                .collect();
+            tx.execute(
+                &format!("INSERT OR REPLACE INTO {} ({}) VALUES ({})", table, columns.join(", "), placeholders.join(", ")),
+                rusqlite::params_from_iter(values),
+            )?;
+        }
+        tx.commit()?;
+        Ok(rows.len())
+    })
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
+use url::Url;
+
+use crate::{CommandError, RedeemLicenseResponse};
+
+pub const SCHEME: &str = "livekenceng";
+const MAX_EMAIL_LEN: usize = 254;
+const LICENSE_KEY_LEN: std::ops::RangeInclusive<usize> = 8..=64;
+
+// What a link asked for, after validation. Links from the purchase email look like
+// livekenceng://redeem?key=XXXX-XXXX&email=buyer@example.com&auto=1
+#[derive(Debug, Clone, PartialEq)]
+enum Route {
+    Redeem {
+        license_key: String,
+        email: Option<String>,
+        // Redeem right away instead of only filling in the form
+        auto_run: bool,
+    },
+}
+
+// Sent to the UI as `deep-link` and kept until the UI takes it, since a link that launched
+// the app arrives before the window is listening
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeepLinkEvent {
+    // Fill in the redeem form and wait for the user
+    RedeemPrefill { license_key: String, email: Option<String> },
+    Redeemed { license_key: String, email: String, response: RedeemLicenseResponse },
+    RedeemFailed { license_key: String, email: String, error: CommandError },
+}
+
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<Option<DeepLinkEvent>>,
+}
+
+fn valid_license_key(key: &str) -> bool {
+    LICENSE_KEY_LEN.contains(&key.len()) && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn valid_email(email: &str) -> bool {
+    email.len() <= MAX_EMAIL_LEN
+        && email.split_once('@').is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'))
+        && !email.chars().any(char::is_whitespace)
+}
+
+fn parse(url: &Url) -> Result<Route, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {}:// link", SCHEME));
+    }
+    // livekenceng://redeem?... puts the action in the host, livekenceng:redeem?... in the path
+    let action = url.host_str().unwrap_or_else(|| url.path()).trim_matches('/');
+    match action {
+        "redeem" => {
+            let param = |names: &[&str]| {
+                url.query_pairs()
+                    .find(|(name, _)| names.contains(&name.as_ref()))
+                    .map(|(_, value)| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let license_key = param(&["key", "license_key"]).ok_or("Link has no license key")?.to_uppercase();
+            if !valid_license_key(&license_key) {
+                return Err("Link has an invalid license key".to_string());
+            }
+            let email = param(&["email"]).map(|e| e.to_lowercase());
+            if email.as_deref().is_some_and(|e| !valid_email(e)) {
+                return Err("Link has an invalid email address".to_string());
+            }
+            let auto_run = param(&["auto"]).is_some_and(|a| a == "1" || a == "true");
+            Ok(Route::Redeem { license_key, email, auto_run })
+        }
+        other => Err(format!("Unknown link action: {}", other)),
+    }
+}
+
+fn deliver(app: &AppHandle, event: DeepLinkEvent) {
+    *app.state::<DeepLinkState>().pending.lock().unwrap() = Some(event.clone());
+    if let Err(e) = app.emit("deep-link", event) {
+        eprintln!("Failed to emit deep-link: {}", e);
+    }
+}
+
+fn handle(app: &AppHandle, url: &Url) {
+    let route = match parse(url) {
+        Ok(route) => route,
+        Err(e) => {
+            eprintln!("[DEEP LINK] Ignored link: {}", e);
+            return;
+        }
+    };
+    crate::background::show_main_window(app);
+    match route {
+        Route::Redeem { license_key, email: Some(email), auto_run: true } => {
+            println!("[DEEP LINK] Redeeming license for {}", email);
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let event = match crate::redeem_license(email.clone(), license_key.clone()).await {
+                    Ok(response) => DeepLinkEvent::Redeemed { license_key, email, response },
+                    Err(error) => DeepLinkEvent::RedeemFailed { license_key, email, error },
+                };
+                deliver(&app, event);
+            });
+        }
+        // Without an email there's nothing to redeem against, so the form is filled in instead
+        Route::Redeem { license_key, email, .. } => deliver(app, DeepLinkEvent::RedeemPrefill { license_key, email }),
+    }
+}
+
+// Handles the link the app was launched with and any opened while it runs. On Windows and
+// Linux a second launch is forwarded here by the single-instance plugin.
+pub fn setup(app: &AppHandle) {
+    // Installers register the scheme; this covers dev builds and AppImages
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        eprintln!("[DEEP LINK] Failed to register {}:// scheme: {}", SCHEME, e);
+    }
+
+    let handler = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle(&handler, &url);
+        }
+    });
+    match app.deep_link().get_current() {
+        Ok(urls) => {
+            for url in urls.unwrap_or_default() {
+                handle(app, &url);
+            }
+        }
+        Err(e) => eprintln!("[DEEP LINK] Failed to read launch link: {}", e),
+    }
+}
+
+// The latest link result the UI hasn't picked up yet
+pub fn take_pending(app: &AppHandle) -> Option<DeepLinkEvent> {
+    app.state::<DeepLinkState>().pending.lock().unwrap().take()
+}
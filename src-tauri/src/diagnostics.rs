@@ -0,0 +1,508 @@
+use crate::audit::{AuditEntry, AuditFilter};
+use crate::cookie_health::{CookieHealth, CookieStatus};
+use crate::error::CommandError;
+use crate::jobs::JobsState;
+use crate::session_reports::ReportError;
+use crate::unix_now;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+// Audit entries read per target when explaining a failure
+const AUDIT_LOOKBACK: u32 = 50;
+// Account and showcase changes this long before a failure are treated as related
+const RECENT_CHANGE_SECS: u64 = 60 * 60;
+// This many session errors within the window look like a failing loop rather than a blip
+const BREAKER_THRESHOLD: usize = 5;
+const BREAKER_WINDOW_SECS: u64 = 10 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureRecord {
+    pub operation: String,
+    pub error: String,
+    pub upgrade_required: bool,
+    pub occurred_at: u64,
+    pub operator: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbableCause {
+    pub cause: String,
+    pub suggestion: String,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FailureExplanation {
+    pub session_id: String,
+    pub last_failure: Option<FailureRecord>,
+    pub causes: Vec<ProbableCause>,
+}
+
+#[derive(Default)]
+pub struct DiagnosticsState {
+    last_failures: Mutex<HashMap<String, FailureRecord>>,
+}
+
+pub fn record_failure(app: &AppHandle, session_id: &str, operation: &str, error: &CommandError) {
+    let record = FailureRecord {
+        operation: operation.to_string(),
+        error: error.to_string(),
+        upgrade_required: matches!(error, CommandError::UpgradeRequired { .. }),
+        occurred_at: unix_now(),
+        operator: crate::operators::current(app),
+    };
+    crate::session_reports::record_error(app, session_id, operation, &record.error);
+    crate::notifications::error(app, session_id, operation, &record.error);
+    app.state::<DiagnosticsState>()
+        .last_failures
+        .lock()
+        .unwrap()
+        .insert(session_id.to_string(), record);
+}
+
+pub fn recent(app: &AppHandle) -> Vec<(String, FailureRecord)> {
+    let mut records: Vec<_> = app
+        .state::<DiagnosticsState>()
+        .last_failures
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(session_id, record)| (session_id.clone(), record.clone()))
+        .collect();
+    records.sort_by_key(|(_, record)| record.occurred_at);
+    records
+}
+
+// Everything the explanation is based on, gathered up front so the diagnosis itself
+// doesn't touch app state
+struct Evidence {
+    last_failure: Option<FailureRecord>,
+    // Errors recorded in the session's report, oldest first
+    session_errors: Vec<ReportError>,
+    // Latest cookie check for the session's account
+    cookie: Option<CookieHealth>,
+    // Audit entries for the session and its account, newest first
+    audit: Vec<AuditEntry>,
+    automation_paused: bool,
+    blackout_active: bool,
+}
+
+pub fn explain(app: &AppHandle, session_id: &str) -> FailureExplanation {
+    let last_failure = app
+        .state::<DiagnosticsState>()
+        .last_failures
+        .lock()
+        .unwrap()
+        .get(session_id)
+        .cloned();
+    let report = crate::session_reports::get(app, session_id).ok().flatten();
+    let account_id = report.as_ref().map(|r| r.shopee_account_id);
+
+    let mut targets = vec![format!("session:{}", session_id)];
+    targets.extend(account_id.map(|id| format!("account:{}", id)));
+    let mut audit = Vec::new();
+    for target in targets {
+        let filter = AuditFilter {
+            target: Some(target),
+            limit: Some(AUDIT_LOOKBACK),
+            ..Default::default()
+        };
+        match crate::audit::query(app, &filter) {
+            Ok(entries) => audit.extend(entries),
+            Err(e) => eprintln!("[DIAGNOSTICS] Failed to read audit log: {}", e),
+        }
+    }
+    audit.sort_by_key(|entry| std::cmp::Reverse(entry.id));
+
+    let evidence = Evidence {
+        last_failure: last_failure.clone(),
+        session_errors: report.map(|r| r.errors).unwrap_or_default(),
+        cookie: account_id.and_then(|id| crate::cookie_health::load_all(app).remove(&id)),
+        audit,
+        automation_paused: app.state::<JobsState>().status().paused,
+        blackout_active: crate::blackout::active_remaining(app).is_some(),
+    };
+
+    FailureExplanation {
+        session_id: session_id.to_string(),
+        last_failure,
+        causes: diagnose(&evidence),
+    }
+}
+
+// Probable causes, most likely first
+fn diagnose(evidence: &Evidence) -> Vec<ProbableCause> {
+    let Some(failure) = &evidence.last_failure else {
+        return vec![];
+    };
+    let error = failure.error.to_lowercase();
+    let mut causes = Vec::new();
+
+    // Plan quota
+    if failure.upgrade_required {
+        causes.push(cause(
+            "Your plan does not include this feature",
+            "Upgrade your livekenceng plan or contact support",
+            0.95,
+        ));
+    }
+
+    // Actions taken on the session or its account before the failure
+    let before = |entry: &&AuditEntry| entry.at <= failure.occurred_at;
+    if let Some(entry) = evidence.audit.iter().filter(before).find(|e| e.action == "end_live_session") {
+        causes.push(cause(
+            &format!("The live was ended from the app {} before the failure", ago(failure.occurred_at, entry.at)),
+            "Start a new live session and run the automation on it",
+            0.9,
+        ));
+    }
+    if evidence.audit.iter().filter(before).any(|e| e.action == "delete_shopee_account") {
+        causes.push(cause(
+            "The Shopee account was deleted from the app",
+            "Restore the account from the archive or add it again",
+            0.9,
+        ));
+    }
+    let cookie_change = evidence.audit.iter().filter(before).find(|e| {
+        failure.occurred_at - e.at <= RECENT_CHANGE_SECS
+            && (e.action == "rollback_account_cookie" || (e.action == "update_shopee_account" && e.details["cookie_changed"] == true))
+    });
+    if let Some(entry) = cookie_change {
+        causes.push(cause(
+            &format!("The account's cookie was replaced {} before the failure", ago(failure.occurred_at, entry.at)),
+            "Check the new cookie, or roll back to the previous one from the cookie history",
+            0.65,
+        ));
+    }
+    if let Some(entry) = evidence.audit.iter().filter(before).find(|e| e.action == "clear_products") {
+        if failure.occurred_at - entry.at <= RECENT_CHANGE_SECS {
+            causes.push(cause(
+                "The session's showcase was cleared shortly before the failure",
+                "Push a product set again, or undo the clear",
+                0.4,
+            ));
+        }
+    }
+
+    // Cookie validity, as last checked. A cookie that checked fine after the failure rules it out.
+    let cookie_error = error.contains("http 401") || error.contains("cookie") || error.contains("unauthorized") || error.contains("login");
+    let rate_limit_error = |e: &str| {
+        let e = e.to_lowercase();
+        e.contains("http 429") || e.contains("rate limit") || e.contains("too many")
+    };
+    match &evidence.cookie {
+        Some(health) if health.status == CookieStatus::Expired => causes.push(cause(
+            "The Shopee account cookie has expired or was logged out",
+            "Log in to the Shopee account again with the QR code and update the account",
+            0.9,
+        )),
+        Some(health) if health.status == CookieStatus::Banned => causes.push(cause(
+            "The Shopee account has been banned or suspended",
+            "Check the account in the Shopee app and contact Shopee support",
+            0.9,
+        )),
+        Some(health) if health.status == CookieStatus::Valid && health.checked_at >= failure.occurred_at => {}
+        _ if cookie_error => causes.push(cause(
+            "The Shopee account cookie may have expired or been logged out",
+            "Run a cookie check on the account; if it fails, log in again with the QR code",
+            0.6,
+        )),
+        _ => {}
+    }
+
+    // Shopee's request quota
+    let rate_limited_errors = evidence.session_errors.iter().filter(|e| rate_limit_error(&e.error)).count();
+    if evidence.cookie.as_ref().is_some_and(|h| h.status == CookieStatus::RateLimited) || rate_limit_error(&error) || rate_limited_errors > 0 {
+        causes.push(cause(
+            "Shopee is rate limiting requests from this account",
+            "Wait a few minutes before rotating again, or rotate less often",
+            if rate_limited_errors >= 2 { 0.8 } else { 0.7 },
+        ));
+    }
+
+    // Repeated failures stop the automation loops, like a tripped breaker
+    let streak = evidence
+        .session_errors
+        .iter()
+        .filter(|e| e.occurred_at + BREAKER_WINDOW_SECS >= failure.occurred_at && e.occurred_at <= failure.occurred_at)
+        .count();
+    if streak >= BREAKER_THRESHOLD {
+        causes.push(cause(
+            &format!("{} errors in the last {} minutes; automation stops after repeated failures", streak, BREAKER_WINDOW_SECS / 60),
+            "Fix the underlying cause, then start the automation again",
+            0.55,
+        ));
+    }
+    if evidence.automation_paused {
+        causes.push(cause(
+            "All automation is paused",
+            "Resume automation from the tray or the dashboard",
+            0.5,
+        ));
+    }
+    if evidence.blackout_active {
+        causes.push(cause(
+            "A blackout window is holding automated actions",
+            "Wait for the window to end or change the blackout schedule",
+            0.45,
+        ));
+    }
+
+    // Signatures in the error itself
+    if error.contains("session") && (error.contains("not found") || error.contains("ended") || error.contains("invalid")) {
+        causes.push(cause(
+            "The live session has ended or the session ID is stale",
+            "Refresh the session list and pick the currently active live session",
+            0.75,
+        ));
+    }
+    if error.contains("request failed") || error.contains("timed out") || error.contains("connect") {
+        causes.push(cause(
+            "The network connection to the server failed",
+            "Check your internet connection and try again",
+            0.6,
+        ));
+    }
+    if error.contains("http 5") {
+        causes.push(cause(
+            "The livekenceng server returned an internal error",
+            "Try again shortly; contact support if it keeps happening",
+            0.5,
+        ));
+    }
+    if error.contains("product") && (error.contains("empty") || error.contains("no item")) {
+        causes.push(cause(
+            "The product set has no items to push",
+            "Add items to the product set before replacing products",
+            0.5,
+        ));
+    }
+    if causes.is_empty() {
+        causes.push(cause(
+            "Unrecognized error",
+            "Send the error message to support",
+            0.1,
+        ));
+    }
+
+    causes.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    causes
+}
+
+fn ago(now: u64, at: u64) -> String {
+    let secs = now.saturating_sub(at);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{} min", secs / 60)
+    }
+}
+
+fn cause(cause: &str, suggestion: &str, confidence: f32) -> ProbableCause {
+    ProbableCause {
+        cause: cause.to_string(),
+        suggestion: suggestion.to_string(),
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAILED_AT: u64 = 1_000_000;
+
+    fn failure(error: &str) -> FailureRecord {
+        FailureRecord {
+            operation: "replace_products".to_string(),
+            error: error.to_string(),
+            upgrade_required: false,
+            occurred_at: FAILED_AT,
+            operator: None,
+        }
+    }
+
+    fn evidence(error: &str) -> Evidence {
+        Evidence {
+            last_failure: Some(failure(error)),
+            session_errors: Vec::new(),
+            cookie: None,
+            audit: Vec::new(),
+            automation_paused: false,
+            blackout_active: false,
+        }
+    }
+
+    fn audit(action: &str, at: u64, details: serde_json::Value) -> AuditEntry {
+        AuditEntry {
+            id: at as i64,
+            at,
+            operator: None,
+            action: action.to_string(),
+            target: None,
+            details,
+        }
+    }
+
+    fn cookie(status: CookieStatus, checked_at: u64) -> Option<CookieHealth> {
+        Some(CookieHealth {
+            account_id: 1,
+            status,
+            username: None,
+            detail: None,
+            checked_at,
+        })
+    }
+
+    fn session_error(error: &str, occurred_at: u64) -> ReportError {
+        ReportError {
+            operation: "replace_products".to_string(),
+            error: error.to_string(),
+            occurred_at,
+        }
+    }
+
+    fn top(evidence: &Evidence) -> String {
+        diagnose(evidence).remove(0).cause
+    }
+
+    fn mentions(evidence: &Evidence, text: &str) -> bool {
+        diagnose(evidence).iter().any(|c| c.cause.contains(text))
+    }
+
+    #[test]
+    fn nothing_to_explain_without_a_failure() {
+        let mut evidence = evidence("x");
+        evidence.last_failure = None;
+        assert!(diagnose(&evidence).is_empty());
+    }
+
+    #[test]
+    fn plan_quota_ranks_first() {
+        let mut evidence = evidence("Please upgrade your plan");
+        evidence.last_failure.as_mut().unwrap().upgrade_required = true;
+        evidence.automation_paused = true;
+        assert_eq!(top(&evidence), "Your plan does not include this feature");
+    }
+
+    #[test]
+    fn session_ended_from_the_app() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.audit = vec![audit("end_live_session", FAILED_AT - 120, serde_json::json!({}))];
+        assert_eq!(top(&evidence), "The live was ended from the app 2 min before the failure");
+    }
+
+    #[test]
+    fn audit_entries_after_the_failure_are_ignored() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.audit = vec![audit("end_live_session", FAILED_AT + 10, serde_json::json!({}))];
+        assert!(!mentions(&evidence, "ended from the app"));
+    }
+
+    #[test]
+    fn account_deleted() {
+        let mut evidence = evidence("HTTP 404: not found");
+        evidence.audit = vec![audit("delete_shopee_account", FAILED_AT - 5, serde_json::json!({ "archived": true }))];
+        assert_eq!(top(&evidence), "The Shopee account was deleted from the app");
+    }
+
+    #[test]
+    fn recent_cookie_change() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.audit = vec![audit("update_shopee_account", FAILED_AT - 30, serde_json::json!({ "cookie_changed": true }))];
+        assert!(mentions(&evidence, "cookie was replaced 30s before"));
+
+        evidence.audit = vec![audit("update_shopee_account", FAILED_AT - 30, serde_json::json!({ "cookie_changed": false }))];
+        assert!(!mentions(&evidence, "cookie was replaced"));
+
+        evidence.audit = vec![audit("rollback_account_cookie", FAILED_AT - RECENT_CHANGE_SECS - 1, serde_json::json!({}))];
+        assert!(!mentions(&evidence, "cookie was replaced"));
+    }
+
+    #[test]
+    fn showcase_cleared() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.audit = vec![audit("clear_products", FAILED_AT - 60, serde_json::json!({}))];
+        assert!(mentions(&evidence, "showcase was cleared"));
+    }
+
+    #[test]
+    fn expired_cookie_from_health_check() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.cookie = cookie(CookieStatus::Expired, FAILED_AT - 600);
+        assert_eq!(top(&evidence), "The Shopee account cookie has expired or was logged out");
+    }
+
+    #[test]
+    fn banned_account_from_health_check() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.cookie = cookie(CookieStatus::Banned, FAILED_AT - 600);
+        assert_eq!(top(&evidence), "The Shopee account has been banned or suspended");
+    }
+
+    #[test]
+    fn valid_cookie_checked_after_the_failure_rules_out_the_cookie() {
+        let mut evidence = evidence("HTTP 401: unauthorized");
+        evidence.cookie = cookie(CookieStatus::Valid, FAILED_AT + 60);
+        assert!(!mentions(&evidence, "cookie"));
+
+        evidence.cookie = cookie(CookieStatus::Valid, FAILED_AT - 60);
+        assert!(mentions(&evidence, "cookie may have expired"));
+    }
+
+    #[test]
+    fn unchecked_cookie_error_is_a_possible_cause() {
+        let evidence = evidence("HTTP 401: unauthorized");
+        assert_eq!(top(&evidence), "The Shopee account cookie may have expired or been logged out");
+    }
+
+    #[test]
+    fn rate_limited_from_health_check_or_session_errors() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.cookie = cookie(CookieStatus::RateLimited, FAILED_AT - 60);
+        assert_eq!(top(&evidence), "Shopee is rate limiting requests from this account");
+
+        let mut evidence = self::evidence("HTTP 400: bad request");
+        evidence.session_errors = vec![session_error("HTTP 429: too many requests", FAILED_AT - 300), session_error("HTTP 429", FAILED_AT - 200)];
+        let causes = diagnose(&evidence);
+        assert_eq!(causes[0].cause, "Shopee is rate limiting requests from this account");
+        assert_eq!(causes[0].confidence, 0.8);
+    }
+
+    #[test]
+    fn repeated_failures_look_like_a_tripped_breaker() {
+        let mut evidence = evidence("HTTP 400: bad request");
+        evidence.session_errors = (0..BREAKER_THRESHOLD as u64).map(|i| session_error("HTTP 400", FAILED_AT - i * 60)).collect();
+        assert!(mentions(&evidence, "5 errors in the last 10 minutes"));
+
+        evidence.session_errors.pop();
+        evidence.session_errors.push(session_error("HTTP 400", FAILED_AT - BREAKER_WINDOW_SECS - 1));
+        assert!(!mentions(&evidence, "errors in the last"));
+    }
+
+    #[test]
+    fn paused_automation_and_blackout() {
+        let mut evidence = evidence("something odd");
+        evidence.automation_paused = true;
+        evidence.blackout_active = true;
+        let causes: Vec<_> = diagnose(&evidence).into_iter().map(|c| c.cause).collect();
+        assert_eq!(causes, ["All automation is paused", "A blackout window is holding automated actions"]);
+    }
+
+    #[test]
+    fn error_signatures() {
+        assert_eq!(top(&evidence("Session not found")), "The live session has ended or the session ID is stale");
+        assert_eq!(top(&evidence("Request failed: connection refused")), "The network connection to the server failed");
+        assert_eq!(top(&evidence("HTTP 502: bad gateway")), "The livekenceng server returned an internal error");
+        assert_eq!(top(&evidence("Product set is empty")), "The product set has no items to push");
+    }
+
+    #[test]
+    fn unrecognized_error() {
+        let causes = diagnose(&evidence("something odd"));
+        assert_eq!(causes.len(), 1);
+        assert_eq!(causes[0].cause, "Unrecognized error");
+    }
+}
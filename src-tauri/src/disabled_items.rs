@@ -0,0 +1,37 @@
+use std::collections::{BTreeSet, HashMap};
+use tauri::AppHandle;
+
+use crate::storage;
+
+// Items temporarily pulled from the showcase without deleting them from the set
+const DISABLED_FILE: &str = "disabled_items.json";
+
+type DisabledMap = HashMap<i32, BTreeSet<i32>>;
+
+pub fn get(app: &AppHandle, product_set_id: i32) -> BTreeSet<i32> {
+    storage::load_json::<DisabledMap>(app, DISABLED_FILE)
+        .ok()
+        .and_then(|mut map| map.remove(&product_set_id))
+        .unwrap_or_default()
+}
+
+pub fn set_enabled(app: &AppHandle, product_set_id: i32, item_id: i32, enabled: bool) -> Result<Vec<i32>, String> {
+    storage::update_json(app, DISABLED_FILE, |map: &mut DisabledMap| {
+        let disabled = map.entry(product_set_id).or_default();
+        if enabled {
+            disabled.remove(&item_id);
+        } else {
+            disabled.insert(item_id);
+        }
+        let result = disabled.iter().copied().collect();
+        if disabled.is_empty() {
+            map.remove(&product_set_id);
+        }
+        result
+    })
+}
+
+// Items in push order with disabled ones left out
+pub fn enabled_in_order(order: &[i32], disabled: &BTreeSet<i32>) -> Vec<i32> {
+    order.iter().copied().filter(|id| !disabled.contains(id)).collect()
+}
@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+// One step a live command would take, for showing the plan before anything is sent
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedAction {
+    pub action: String,
+    pub detail: String,
+}
+
+impl PlannedAction {
+    pub fn new(action: &str, detail: impl Into<String>) -> Self {
+        PlannedAction {
+            action: action.to_string(),
+            detail: detail.into(),
+        }
+    }
+}
+
+// Result of a live command that supports `dry_run`: the plan when dry-running, otherwise
+// the command's normal result
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LiveOutcome<T> {
+    Planned { actions: Vec<PlannedAction> },
+    Done { result: T },
+}
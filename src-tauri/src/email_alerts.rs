@@ -0,0 +1,203 @@
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::encrypted_store;
+use crate::notifications::{self, NotificationEvent};
+
+// Encrypted because it holds the SMTP password
+const SMTP_FILE: &str = "smtp.enc";
+const MAX_RECIPIENTS: usize = 10;
+// Only events that need the owner's attention even when nobody is at the computer
+const CRITICAL_EVENTS: &[NotificationEvent] = &[NotificationEvent::LicenseExpired, NotificationEvent::AllCookiesDead];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    // Implicit TLS, usually port 465
+    Tls,
+    // Plain connection upgraded with STARTTLS, usually port 587
+    StartTls,
+    // Unencrypted, only for relays on the local network
+    None,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    pub events: BTreeSet<NotificationEvent>,
+    // The same event isn't emailed again within this many minutes
+    pub cooldown_minutes: u32,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        SmtpConfig {
+            enabled: false,
+            host: String::new(),
+            port: 587,
+            security: SmtpSecurity::StartTls,
+            username: None,
+            password: None,
+            from: String::new(),
+            to: Vec::new(),
+            events: CRITICAL_EVENTS.iter().copied().collect(),
+            cooldown_minutes: 60,
+        }
+    }
+}
+
+impl SmtpConfig {
+    fn validate(&mut self) -> Result<(), String> {
+        self.host = self.host.trim().to_string();
+        self.from = self.from.trim().to_string();
+        self.username = self.username.take().map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+        self.password = self.password.take().filter(|p| !p.is_empty());
+        self.to = self.to.iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+        self.to.dedup();
+
+        if !self.from.is_empty() {
+            self.from.parse::<Mailbox>().map_err(|e| format!("Invalid sender address: {}", e))?;
+        }
+        for to in &self.to {
+            to.parse::<Mailbox>().map_err(|e| format!("Invalid recipient {}: {}", to, e))?;
+        }
+        if self.to.len() > MAX_RECIPIENTS {
+            return Err(format!("At most {} recipients can be set", MAX_RECIPIENTS));
+        }
+        if self.port == 0 {
+            return Err("SMTP port must be between 1 and 65535".to_string());
+        }
+        if self.username.is_some() != self.password.is_some() {
+            return Err("Enter both the SMTP username and password, or neither".to_string());
+        }
+        if let Some(event) = self.events.iter().find(|e| !CRITICAL_EVENTS.contains(e)) {
+            return Err(format!("{:?} can't be sent by email; use Discord or a webhook for it", event));
+        }
+        if self.enabled && (self.host.is_empty() || self.from.is_empty() || self.to.is_empty()) {
+            return Err("Enter the SMTP host, sender and at least one recipient to enable email alerts".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct EmailAlertState {
+    // When each event was last emailed
+    last_sent: Mutex<BTreeMap<NotificationEvent, Instant>>,
+}
+
+pub fn config(app: &AppHandle) -> Result<SmtpConfig, String> {
+    encrypted_store::load(app, SMTP_FILE)
+}
+
+pub fn set_config(app: &AppHandle, mut config: SmtpConfig) -> Result<SmtpConfig, String> {
+    config.validate()?;
+    let _guard = crate::storage::lock();
+    encrypted_store::save(app, SMTP_FILE, &config)?;
+    Ok(config)
+}
+
+// Subject and body template for an event; placeholders are the same as for Discord
+fn email_text(event: NotificationEvent) -> (&'static str, &'static str) {
+    match event {
+        NotificationEvent::LicenseExpired => (
+            "Bot Gacor license expired",
+            "The Bot Gacor license for {email} expired on {expiry_date}.\n\nLives, schedules and showcase pushes stop working until the license is renewed. Redeem a new license key in the app to continue.",
+        ),
+        NotificationEvent::AllCookiesDead => (
+            "All Shopee account cookies are dead",
+            "None of the {accounts} Shopee account(s) in Bot Gacor has a working cookie any more.\n\nScheduled and running lives can't be managed until the accounts are logged in again, e.g. with QR login.",
+        ),
+        _ => ("Bot Gacor alert", "{event}"),
+    }
+}
+
+async fn send_email(config: &SmtpConfig, subject: &str, body: String) -> Result<(), String> {
+    let mut message = Message::builder()
+        .from(config.from.parse::<Mailbox>().map_err(|e| format!("Invalid sender address: {}", e))?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN);
+    for to in &config.to {
+        message = message.to(to.parse::<Mailbox>().map_err(|e| format!("Invalid recipient {}: {}", to, e))?);
+    }
+    let message = message.body(body).map_err(|e| format!("Failed to build email: {}", e))?;
+
+    let builder = match config.security {
+        SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host),
+        SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host),
+        SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)),
+    }
+    .map_err(|e| format!("Invalid SMTP host: {}", e))?;
+    let mut builder = builder.port(config.port).timeout(Some(crate::settings::current().request_timeout()));
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+    builder
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| format!("Failed to send email: {}", e))?;
+    Ok(())
+}
+
+// Claims the event for this cooldown period, or returns false if it was emailed recently
+fn take_slot(app: &AppHandle, event: NotificationEvent, cooldown: Duration) -> bool {
+    let state = app.state::<EmailAlertState>();
+    let mut last_sent = state.last_sent.lock().unwrap();
+    let now = Instant::now();
+    if last_sent.get(&event).is_some_and(|at| now.duration_since(*at) < cooldown) {
+        return false;
+    }
+    last_sent.insert(event, now);
+    true
+}
+
+pub fn send(app: &AppHandle, event: NotificationEvent, fields: &[(&str, String)]) {
+    if !CRITICAL_EVENTS.contains(&event) {
+        return;
+    }
+    let config = match config(app) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("[EMAIL] Failed to load SMTP config: {}", e);
+            return;
+        }
+    };
+    if !config.enabled || !config.events.contains(&event) {
+        return;
+    }
+    if !take_slot(app, event, Duration::from_secs(config.cooldown_minutes as u64 * 60)) {
+        return;
+    }
+    let (subject, template) = email_text(event);
+    let body = notifications::render(template, fields);
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = send_email(&config, subject, body).await {
+            eprintln!("[EMAIL] {:?} alert failed: {}", event, e);
+        }
+    });
+}
+
+// Sends a sample email with the saved settings, enabled or not, and waits for the result
+pub async fn test(app: &AppHandle) -> Result<(), String> {
+    let config = config(app)?;
+    if config.host.is_empty() || config.from.is_empty() || config.to.is_empty() {
+        return Err("Save the SMTP host, sender and recipients first".to_string());
+    }
+    send_email(&config, "Bot Gacor test email", "🔔 Test email alert from Bot Gacor".to_string()).await
+}
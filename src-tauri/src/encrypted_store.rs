@@ -0,0 +1,213 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+use crate::storage;
+
+const NONCE_LEN: usize = 12;
+const KEYRING_SERVICE: &str = "botgacor";
+const KEYRING_USER: &str = "encrypted-store";
+
+// Random secret kept in the OS keyring (Keychain, Credential Manager, Secret Service),
+// created on first use. None when no keyring is available.
+static KEYRING_SECRET: OnceLock<Option<Vec<u8>>> = OnceLock::new();
+
+fn keyring_secret() -> Option<&'static [u8]> {
+    KEYRING_SECRET
+        .get_or_init(|| {
+            let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+                .inspect_err(|e| eprintln!("[ENCRYPTED STORE] OS keyring unavailable: {}", e))
+                .ok()?;
+            match entry.get_password() {
+                Ok(encoded) => base64::engine::general_purpose::STANDARD.decode(encoded).ok(),
+                Err(keyring::Error::NoEntry) => {
+                    let mut secret = vec![0u8; 32];
+                    rand::thread_rng().fill_bytes(&mut secret);
+                    let encoded = base64::engine::general_purpose::STANDARD.encode(&secret);
+                    match entry.set_password(&encoded) {
+                        Ok(()) => Some(secret),
+                        Err(e) => {
+                            eprintln!("[ENCRYPTED STORE] Failed to store keyring secret: {}", e);
+                            None
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("[ENCRYPTED STORE] Failed to read keyring secret: {}", e);
+                    None
+                }
+            }
+        })
+        .as_deref()
+}
+
+// Local secrets are bound to this machine and OS user: a copied data dir is useless without
+// both the machine ID and the keyring secret. Falls back to the machine ID alone when the
+// OS has no keyring.
+fn key() -> [u8; 32] {
+    let machine_id = crate::get_or_generate_machine_id();
+    match keyring_secret() {
+        Some(secret) => keyring_key(&machine_id, secret),
+        None => machine_key(&machine_id),
+    }
+}
+
+fn keyring_key(machine_id: &str, secret: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"botgacor-encrypted-store-v2:");
+    hasher.update(machine_id.as_bytes());
+    hasher.update(b":");
+    hasher.update(secret);
+    digest_to_key(hasher)
+}
+
+fn machine_key(machine_id: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"botgacor-encrypted-store:");
+    hasher.update(machine_id.as_bytes());
+    digest_to_key(hasher)
+}
+
+fn digest_to_key(hasher: Sha256) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&hasher.finalize());
+    key
+}
+
+// Returns base64(nonce || ciphertext)
+pub fn encrypt(plaintext: &[u8]) -> Result<String, String> {
+    encrypt_with_key(&key(), plaintext)
+}
+
+// Data written before the keyring secret, or before the hardware machine ID switch, is still
+// readable with the key of the time; `rekey_files` moves it to the current key
+pub fn decrypt(encoded: &str) -> Result<Vec<u8>, String> {
+    decrypt_with_key(&key(), encoded).or_else(|e| {
+        [crate::get_or_generate_machine_id(), crate::machine_id::legacy_machine_id()]
+            .iter()
+            .find_map(|machine_id| decrypt_with_key(&machine_key(machine_id), encoded).ok())
+            .ok_or(e)
+    })
+}
+
+pub fn encrypt_with_key(key: &[u8; 32], plaintext: &[u8]) -> Result<String, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| "Invalid encryption key".to_string())?;
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), plaintext)
+        .map_err(|_| "Failed to encrypt data".to_string())?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(base64::engine::general_purpose::STANDARD.encode(out))
+}
+
+pub fn decrypt_with_key(key: &[u8; 32], encoded: &str) -> Result<Vec<u8>, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| format!("Invalid encrypted data: {}", e))?;
+    if bytes.len() < NONCE_LEN {
+        return Err("Invalid encrypted data: too short".to_string());
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| "Invalid encrypted data: bad nonce".to_string())?;
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| "Invalid encryption key".to_string())?;
+    cipher
+        .decrypt(&Nonce::from(nonce), ciphertext)
+        .map_err(|_| "Failed to decrypt data (wrong key or corrupted file)".to_string())
+}
+
+pub const PASSPHRASE_SALT_LEN: usize = 16;
+
+// Derive a key from a user passphrase, used for data that has to leave this machine.
+// Argon2id makes each guess expensive, so a leaked backup can't be brute-forced cheaply.
+pub fn passphrase_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    // 64 MiB, 3 passes, 1 lane
+    let params = argon2::Params::new(64 * 1024, 3, 1, Some(32)).map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
+}
+
+pub fn new_passphrase_salt() -> [u8; PASSPHRASE_SALT_LEN] {
+    let mut salt = [0u8; PASSPHRASE_SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+// Unsalted key used by version 1 backups; only kept so those can still be imported
+pub fn legacy_passphrase_key(passphrase: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"botgacor-passphrase:");
+    hasher.update(passphrase.as_bytes());
+    digest_to_key(hasher)
+}
+
+pub fn load<T: DeserializeOwned + Default>(app: &AppHandle, name: &str) -> Result<T, String> {
+    match storage::read_file(app, name)? {
+        Some(bytes) => {
+            let encoded = String::from_utf8(bytes).map_err(|e| format!("Invalid encrypted file {}: {}", name, e))?;
+            let plaintext = decrypt(&encoded)?;
+            serde_json::from_slice(&plaintext).map_err(|e| format!("Failed to parse {}: {}", name, e))
+        }
+        None => Ok(T::default()),
+    }
+}
+
+pub fn save<T: Serialize>(app: &AppHandle, name: &str, value: &T) -> Result<(), String> {
+    let plaintext = serde_json::to_vec(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    storage::write_file(app, name, encrypt(&plaintext)?.as_bytes())
+}
+
+pub fn update<T, R>(app: &AppHandle, name: &str, f: impl FnOnce(&mut T) -> R) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let _guard = storage::lock();
+    let mut value: T = load(app, name)?;
+    let result = f(&mut value);
+    save(app, name, &value)?;
+    Ok(result)
+}
+
+// Re-encrypts every .enc file in the data dir with the current key; run once at startup
+pub fn rekey_files(app: &AppHandle) {
+    let dir = match storage::data_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("[ENCRYPTED STORE] {}", e);
+            return;
+        }
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return;
+    };
+    let current = key();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".enc") {
+            continue;
+        }
+        let _guard = storage::lock();
+        let result = storage::read_file(app, &name).and_then(|bytes| {
+            let encoded = String::from_utf8(bytes.unwrap_or_default()).map_err(|e| format!("Invalid encrypted file: {}", e))?;
+            if decrypt_with_key(&current, &encoded).is_ok() {
+                return Ok(());
+            }
+            let plaintext = decrypt(&encoded)?;
+            storage::write_file(app, &name, encrypt_with_key(&current, &plaintext)?.as_bytes())
+        });
+        if let Err(e) = result {
+            eprintln!("[ENCRYPTED STORE] Failed to re-encrypt {}: {}", name, e);
+        }
+    }
+}
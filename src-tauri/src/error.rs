@@ -0,0 +1,202 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use botgacor_core::members::is_upgrade_message;
+
+// Error returned by commands. Plain messages serialize as a bare string so existing
+// frontend code doing String(error) keeps working; typed variants serialize as an
+// object with a `kind` field the UI can branch on.
+#[derive(Debug, Clone)]
+pub enum CommandError {
+    Message(String),
+    // Non-success HTTP status from the member API; `message` includes the status
+    Http { status: u16, message: String },
+    UpgradeRequired { feature: String, plan: Option<String>, message: String },
+    ReadOnlySet { product_set_id: i32 },
+    DuplicateAccount { existing_id: i32 },
+    Aggregate(AggregateError),
+}
+
+// One failed target of a batch command. `index` is the position in the request and
+// `target` identifies the row for display (account name, item URL, ID...).
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetError {
+    pub index: usize,
+    pub target: String,
+    pub error: CommandError,
+}
+
+// Returned by batch commands when any target fails, so every failing row can be shown
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregateError {
+    pub total: usize,
+    pub succeeded: usize,
+    pub errors: Vec<TargetError>,
+}
+
+impl AggregateError {
+    pub fn new(total: usize) -> Self {
+        AggregateError {
+            total,
+            succeeded: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, index: usize, target: impl Into<String>, error: impl Into<CommandError>) {
+        self.errors.push(TargetError {
+            index,
+            target: target.into(),
+            error: error.into(),
+        });
+    }
+
+    // Records a failed batch request against each of its targets, `offset` being the batch's
+    // position in the request. A batch that was rejected row by row keeps its own errors.
+    pub fn push_batch(&mut self, offset: usize, targets: &[String], error: CommandError) {
+        match error {
+            CommandError::Aggregate(inner) => {
+                self.errors.extend(inner.errors.into_iter().map(|e| TargetError {
+                    index: offset + e.index,
+                    ..e
+                }));
+            }
+            error => {
+                for (i, target) in targets.iter().enumerate() {
+                    self.push(offset + i, target.clone(), error.clone());
+                }
+            }
+        }
+    }
+
+    // Ok when nothing failed, otherwise the aggregate as an error sorted by index
+    pub fn into_result(mut self) -> Result<usize, CommandError> {
+        if self.errors.is_empty() {
+            return Ok(self.succeeded);
+        }
+        self.errors.sort_by_key(|e| e.index);
+        Err(CommandError::Aggregate(self))
+    }
+}
+
+impl CommandError {
+    // Build an error from a failed member API response, recognizing entitlement failures
+    pub fn from_response(status: u16, endpoint: &str, text: &str) -> Self {
+        let body: Option<serde_json::Value> = serde_json::from_str(text).ok();
+        let message = body
+            .as_ref()
+            .and_then(|b| b["message"].as_str())
+            .unwrap_or(text)
+            .to_string();
+
+        let paywalled = status == 402
+            || (matches!(status, 200..=299 | 403) && is_upgrade_message(&message));
+        if !paywalled {
+            if status >= 400 {
+                return CommandError::Http {
+                    status,
+                    message: format!("HTTP {}: {}", status, text),
+                };
+            }
+            return CommandError::Message(message);
+        }
+
+        let feature = body
+            .as_ref()
+            .and_then(|b| b["feature"].as_str())
+            .map(|f| f.to_string())
+            .unwrap_or_else(|| feature_from_endpoint(endpoint));
+        let plan = body.as_ref().and_then(|b| {
+            b["required_plan"]
+                .as_str()
+                .or_else(|| b["plan"].as_str())
+                .map(|p| p.to_string())
+        });
+
+        CommandError::UpgradeRequired { feature, plan, message }
+    }
+}
+
+// "/api/members/product-sets/12/items" -> "product-sets"
+fn feature_from_endpoint(endpoint: &str) -> String {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    path.trim_start_matches("/api/")
+        .split('/')
+        .find(|s| !s.is_empty() && *s != "members")
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Message(message) | CommandError::Http { message, .. } => write!(f, "{}", message),
+            CommandError::UpgradeRequired { message, .. } => write!(f, "{}", message),
+            CommandError::ReadOnlySet { product_set_id } => {
+                write!(f, "Product set {} is managed by the admin and cannot be changed", product_set_id)
+            }
+            CommandError::DuplicateAccount { existing_id } => {
+                write!(f, "This Shopee account has already been added (account {})", existing_id)
+            }
+            CommandError::Aggregate(aggregate) => {
+                write!(f, "{} of {} failed", aggregate.errors.len(), aggregate.total)?;
+                if let Some(first) = aggregate.errors.first() {
+                    write!(f, " (first: {}: {})", first.target, first.error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Message(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::Message(message.to_string())
+    }
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CommandError::Message(message) | CommandError::Http { message, .. } => serializer.serialize_str(message),
+            CommandError::UpgradeRequired { feature, plan, message } => {
+                let mut s = serializer.serialize_struct("UpgradeRequired", 4)?;
+                s.serialize_field("kind", "upgrade_required")?;
+                s.serialize_field("feature", feature)?;
+                s.serialize_field("plan", plan)?;
+                s.serialize_field("message", message)?;
+                s.end()
+            }
+            CommandError::ReadOnlySet { product_set_id } => {
+                let mut s = serializer.serialize_struct("ReadOnlySet", 3)?;
+                s.serialize_field("kind", "read_only_set")?;
+                s.serialize_field("product_set_id", product_set_id)?;
+                s.serialize_field("message", &self.to_string())?;
+                s.end()
+            }
+            CommandError::DuplicateAccount { existing_id } => {
+                let mut s = serializer.serialize_struct("DuplicateAccount", 3)?;
+                s.serialize_field("kind", "duplicate_account")?;
+                s.serialize_field("existing_id", existing_id)?;
+                s.serialize_field("message", &self.to_string())?;
+                s.end()
+            }
+            CommandError::Aggregate(aggregate) => {
+                let mut s = serializer.serialize_struct("Aggregate", 5)?;
+                s.serialize_field("kind", "aggregate")?;
+                s.serialize_field("total", &aggregate.total)?;
+                s.serialize_field("succeeded", &aggregate.succeeded)?;
+                s.serialize_field("errors", &aggregate.errors)?;
+                s.serialize_field("message", &self.to_string())?;
+                s.end()
+            }
+        }
+    }
+}
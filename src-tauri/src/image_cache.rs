@@ -0,0 +1,183 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::storage;
+
+// Thumbnails and QR images kept on disk so the UI doesn't hit Shopee's CDN on every render
+const CACHE_DIR: &str = "image_cache";
+const INDEX_FILE: &str = "image_cache_index.json";
+const MAX_CACHE_BYTES: u64 = 100 * 1024 * 1024;
+const MAX_IMAGE_BYTES: usize = 2 * 1024 * 1024;
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    file: String,
+    mime: String,
+    size: u64,
+    last_used: u64,
+}
+
+type CacheIndex = HashMap<String, CacheEntry>;
+
+fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = storage::data_dir(app)?.join(CACHE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create image cache dir: {}", e))?;
+    Ok(dir)
+}
+
+// Keys are used as file names, so anything outside [A-Za-z0-9_-] is replaced
+fn file_name(key: &str) -> String {
+    key.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+fn data_url(mime: &str, bytes: &[u8]) -> String {
+    format!("data:{};base64,{}", mime, base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+// Cached image as a data URL, bumping its last-used time
+pub fn get(app: &AppHandle, key: &str) -> Result<Option<String>, String> {
+    let dir = cache_dir(app)?;
+    let entry = storage::update_json(app, INDEX_FILE, |index: &mut CacheIndex| {
+        index.get_mut(key).map(|entry| {
+            entry.last_used = crate::unix_now();
+            entry.clone()
+        })
+    })?;
+    let Some(entry) = entry else {
+        return Ok(None);
+    };
+    match fs::read(dir.join(&entry.file)) {
+        Ok(bytes) => Ok(Some(data_url(&entry.mime, &bytes))),
+        Err(_) => {
+            // The file was removed behind our back; forget it so it gets downloaded again
+            storage::update_json(app, INDEX_FILE, |index: &mut CacheIndex| index.remove(key))?;
+            Ok(None)
+        }
+    }
+}
+
+pub fn put(app: &AppHandle, key: &str, mime: &str, bytes: &[u8]) -> Result<String, String> {
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return Err(format!("Image is larger than {} bytes", MAX_IMAGE_BYTES));
+    }
+    let dir = cache_dir(app)?;
+    let file = file_name(key);
+    fs::write(dir.join(&file), bytes).map_err(|e| format!("Failed to write cached image: {}", e))?;
+
+    let evicted = storage::update_json(app, INDEX_FILE, |index: &mut CacheIndex| {
+        index.insert(
+            key.to_string(),
+            CacheEntry {
+                file,
+                mime: mime.to_string(),
+                size: bytes.len() as u64,
+                last_used: crate::unix_now(),
+            },
+        );
+        evict(index)
+    })?;
+    for file in evicted {
+        let _ = fs::remove_file(dir.join(file));
+    }
+    Ok(data_url(mime, bytes))
+}
+
+// Drops least recently used entries until the cache fits, returning their files
+fn evict(index: &mut CacheIndex) -> Vec<String> {
+    let mut total: u64 = index.values().map(|e| e.size).sum();
+    let mut by_age: Vec<(String, u64)> = index.iter().map(|(k, e)| (k.clone(), e.last_used)).collect();
+    by_age.sort_by_key(|(_, last_used)| *last_used);
+
+    let mut evicted = Vec::new();
+    for (key, _) in by_age {
+        if total <= MAX_CACHE_BYTES {
+            break;
+        }
+        if let Some(entry) = index.remove(&key) {
+            total -= entry.size;
+            evicted.push(entry.file);
+        }
+    }
+    evicted
+}
+
+// Returns the cached copy, downloading and storing it on a miss
+pub async fn fetch(app: &AppHandle, key: &str, url: &str) -> Result<String, String> {
+    if let Some(cached) = get(app, key)? {
+        return Ok(cached);
+    }
+
+    let client = crate::shopee_client(botgacor_core::shopee::DESKTOP_USER_AGENT, None)?;
+    let response = client
+        .get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download image: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download image: HTTP {}", response.status()));
+    }
+    let mime = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| v.starts_with("image/"))
+        .unwrap_or("image/jpeg")
+        .to_string();
+    let bytes = response.bytes().await.map_err(|e| format!("Failed to read image: {}", e))?;
+    put(app, key, &mime, &bytes)
+}
+
+pub fn thumbnail_key(item_id: i64) -> String {
+    format!("item-{}", item_id)
+}
+
+pub fn qr_key(qrcode_id: &str) -> String {
+    format!("qr-{}", qrcode_id)
+}
+
+// QR codes arrive as base64 PNGs; caching them lets the login dialog re-render without a refetch
+pub fn put_qr(app: &AppHandle, qr: &crate::ShopeeQRData) {
+    let encoded = qr.qrcode_base64.split_once(',').map_or(qr.qrcode_base64.as_str(), |(_, data)| data);
+    let result = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| format!("Invalid QR image: {}", e))
+        .and_then(|bytes| put(app, &qr_key(&qr.qrcode_id), "image/png", &bytes));
+    if let Err(e) = result {
+        eprintln!("[IMAGE CACHE] Failed to cache QR {}: {}", qr.qrcode_id, e);
+    }
+}
+
+// Number of cached images and their total size
+pub fn usage(app: &AppHandle) -> Result<(usize, u64), String> {
+    let index: CacheIndex = storage::load_json(app, INDEX_FILE)?;
+    Ok((index.len(), index.values().map(|e| e.size).sum()))
+}
+
+// Removes images not used since `cutoff` (unix seconds), returning how many and their size
+pub fn prune(app: &AppHandle, cutoff: u64) -> Result<(usize, u64), String> {
+    let dir = cache_dir(app)?;
+    let removed = storage::update_json(app, INDEX_FILE, |index: &mut CacheIndex| {
+        let stale: Vec<String> = index.iter().filter(|(_, e)| e.last_used < cutoff).map(|(k, _)| k.clone()).collect();
+        stale.into_iter().filter_map(|key| index.remove(&key)).collect::<Vec<_>>()
+    })?;
+    for entry in &removed {
+        let _ = fs::remove_file(dir.join(&entry.file));
+    }
+    Ok((removed.len(), removed.iter().map(|e| e.size).sum()))
+}
+
+pub fn clear(app: &AppHandle) -> Result<(), String> {
+    let dir = cache_dir(app)?;
+    let index: CacheIndex = storage::update_json(app, INDEX_FILE, std::mem::take)?;
+    for entry in index.into_values() {
+        let _ = fs::remove_file(dir.join(entry.file));
+    }
+    Ok(())
+}
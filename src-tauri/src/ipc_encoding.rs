@@ -0,0 +1,32 @@
+use serde::Serialize;
+use tauri::ipc::Response;
+
+// Encodings the frontend can ask for when pulling large lists over IPC. MessagePack
+// skips JSON string building on our side and is decoded straight from an ArrayBuffer.
+pub const SUPPORTED_ENCODINGS: &[&str] = &["json", "msgpack"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IpcEncoding {
+    Json,
+    MessagePack,
+}
+
+impl IpcEncoding {
+    pub fn parse(encoding: Option<&str>) -> Result<Self, String> {
+        match encoding.map(|e| e.to_ascii_lowercase()).as_deref() {
+            None | Some("json") => Ok(IpcEncoding::Json),
+            Some("msgpack") | Some("messagepack") => Ok(IpcEncoding::MessagePack),
+            Some(other) => Err(format!("Unsupported IPC encoding: {}", other)),
+        }
+    }
+}
+
+// Serialize a value into a raw IPC response body in the requested encoding
+pub fn encode<T: Serialize>(value: &T, encoding: IpcEncoding) -> Result<Response, String> {
+    let bytes = match encoding {
+        IpcEncoding::Json => serde_json::to_vec(value).map_err(|e| format!("Failed to encode JSON: {}", e))?,
+        // Named encoding keeps field names so the frontend decodes plain objects
+        IpcEncoding::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| format!("Failed to encode MessagePack: {}", e))?,
+    };
+    Ok(Response::new(bytes))
+}
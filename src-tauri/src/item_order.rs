@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::storage;
+
+// Local showcase order per product set, used when the backend can't store one
+const ORDER_FILE: &str = "item_order.json";
+
+type OrderMap = HashMap<i32, Vec<i32>>;
+
+pub fn get(app: &AppHandle, product_set_id: i32) -> Option<Vec<i32>> {
+    let map: OrderMap = storage::load_json(app, ORDER_FILE).ok()?;
+    map.get(&product_set_id).cloned()
+}
+
+pub fn set(app: &AppHandle, product_set_id: i32, ordered_item_ids: Vec<i32>) -> Result<(), String> {
+    storage::update_json(app, ORDER_FILE, |map: &mut OrderMap| {
+        map.insert(product_set_id, ordered_item_ids);
+    })
+}
+
+pub fn clear(app: &AppHandle, product_set_id: i32) -> Result<(), String> {
+    storage::update_json(app, ORDER_FILE, |map: &mut OrderMap| {
+        map.remove(&product_set_id);
+    })
+}
+
+// Full order for the set's current items: the requested order first, then any item the
+// request left out in its existing position. Unknown IDs are rejected.
+pub fn normalize(current_item_ids: &[i32], ordered_item_ids: &[i32]) -> Result<Vec<i32>, String> {
+    let mut order = Vec::with_capacity(current_item_ids.len());
+    for id in ordered_item_ids {
+        if !current_item_ids.contains(id) {
+            return Err(format!("Item {} is not in this product set", id));
+        }
+        if !order.contains(id) {
+            order.push(*id);
+        }
+    }
+    for id in current_item_ids {
+        if !order.contains(id) {
+            order.push(*id);
+        }
+    }
+    Ok(order)
+}
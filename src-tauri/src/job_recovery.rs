@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::db;
+use crate::job_runner::{self, JobRequest, JobRunnerState};
+use crate::jobs::{self, Job, JobsState};
+use crate::showcase::ItemFailure;
+
+// How far a showcase push got. Batch 0 is the replace call, later batches are appends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceCheckpoint {
+    pub shopee_account_id: i32,
+    pub session_id: String,
+    pub product_set_id: i32,
+    // Everything being pushed, in order
+    pub item_ids: Vec<i32>,
+    // First batch not yet sent
+    pub next_batch: usize,
+    pub failures: Vec<ItemFailure>,
+}
+
+impl ReplaceCheckpoint {
+    pub fn remaining_items(&self) -> usize {
+        self.item_ids.len().saturating_sub(self.next_batch * crate::showcase::PUSH_BATCH_SIZE)
+    }
+}
+
+// Enough of an unfinished job to carry on with it. Credentials aren't stored; resuming
+// takes them again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Checkpoint {
+    // Waiting in the job runner's queue, not started yet
+    Queued { request: JobRequest },
+    Replace(ReplaceCheckpoint),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InterruptedJob {
+    pub job_id: String,
+    pub kind: String,
+    pub label: String,
+    pub shopee_account_id: Option<i32>,
+    pub updated_at: u64,
+    pub checkpoint: Checkpoint,
+    // Items not yet pushed, for showcase pushes
+    pub remaining_items: Option<usize>,
+}
+
+// Checkpoints left behind by the previous run, loaded once at startup. Checkpoints written
+// during this run belong to jobs that are still going and aren't listed.
+#[derive(Default)]
+pub struct JobRecoveryState {
+    interrupted: Mutex<Vec<InterruptedJob>>,
+}
+
+// Records how far the job got; replaces its previous checkpoint
+pub fn save(app: &AppHandle, job_id: &str, checkpoint: &Checkpoint) {
+    let Some(job) = app.state::<JobsState>().get(job_id) else {
+        return;
+    };
+    let result = db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO job_checkpoints (job_id, kind, label, shopee_account_id, updated_at, data)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                job_id,
+                &job.kind,
+                &job.label,
+                job.shopee_account_id,
+                crate::unix_now() as i64,
+                db::to_json(checkpoint)?,
+            ),
+        )
+    });
+    if let Err(e) = result {
+        eprintln!("[JOB RECOVERY] Failed to save checkpoint for job {}: {}", job_id, e);
+    }
+}
+
+// Called when a job finishes, however it finishes
+pub fn clear(app: &AppHandle, job_id: &str) {
+    if let Err(e) = delete(app, job_id) {
+        eprintln!("[JOB RECOVERY] Failed to clear checkpoint for job {}: {}", job_id, e);
+    }
+}
+
+fn delete(app: &AppHandle, job_id: &str) -> Result<(), String> {
+    db::with(app, |conn| {
+        conn.execute("DELETE FROM job_checkpoints WHERE job_id = ?1", [job_id]).map(|_| ())
+    })
+}
+
+// Loads checkpoints of jobs that were cut off when the app last stopped. Must run before
+// anything starts new jobs.
+pub fn restore(app: &AppHandle) {
+    let loaded = db::with(app, |conn| {
+        let mut statement =
+            conn.prepare("SELECT job_id, kind, label, shopee_account_id, updated_at, data FROM job_checkpoints ORDER BY updated_at")?;
+        let mut rows = statement.query([])?;
+        let mut interrupted = Vec::new();
+        while let Some(row) = rows.next()? {
+            let data: String = row.get(5)?;
+            let checkpoint: Checkpoint = db::from_json(&data)?;
+            interrupted.push(InterruptedJob {
+                job_id: row.get(0)?,
+                kind: row.get(1)?,
+                label: row.get(2)?,
+                shopee_account_id: row.get(3)?,
+                updated_at: row.get::<_, i64>(4)? as u64,
+                remaining_items: match &checkpoint {
+                    Checkpoint::Replace(replace) => Some(replace.remaining_items()),
+                    Checkpoint::Queued { .. } => None,
+                },
+                checkpoint,
+            });
+        }
+        Ok(interrupted)
+    });
+    match loaded {
+        Ok(interrupted) => {
+            if !interrupted.is_empty() {
+                println!("[JOB RECOVERY] {} job(s) were interrupted", interrupted.len());
+            }
+            *app.state::<JobRecoveryState>().interrupted.lock().unwrap() = interrupted;
+        }
+        Err(e) => eprintln!("[JOB RECOVERY] Failed to load checkpoints: {}", e),
+    }
+}
+
+pub fn list(app: &AppHandle) -> Vec<InterruptedJob> {
+    app.state::<JobRecoveryState>().interrupted.lock().unwrap().clone()
+}
+
+// Takes the selected interrupted jobs (all of them when `job_ids` is None) off the list
+fn take(app: &AppHandle, job_ids: Option<&[String]>) -> Result<Vec<InterruptedJob>, String> {
+    let state = app.state::<JobRecoveryState>();
+    let mut interrupted = state.interrupted.lock().unwrap();
+    let (taken, kept): (Vec<_>, Vec<_>) = interrupted
+        .drain(..)
+        .partition(|job| job_ids.is_none_or(|ids| ids.contains(&job.job_id)));
+    *interrupted = kept;
+    drop(interrupted);
+    for job in &taken {
+        delete(app, &job.job_id)?;
+    }
+    Ok(taken)
+}
+
+pub fn discard(app: &AppHandle, job_ids: Option<&[String]>) -> Result<usize, String> {
+    Ok(take(app, job_ids)?.len())
+}
+
+// Starts the interrupted jobs again as new jobs. Queued jobs go back into the job runner;
+// showcase pushes carry on from the batch they stopped at.
+pub fn resume(
+    app: &AppHandle,
+    runner: &JobRunnerState,
+    email: String,
+    password: String,
+    job_ids: Option<&[String]>,
+) -> Result<Vec<Job>, String> {
+    let mut resumed = Vec::new();
+    let mut requests = Vec::new();
+    for job in take(app, job_ids)? {
+        match job.checkpoint {
+            Checkpoint::Queued { request } => requests.push(request),
+            Checkpoint::Replace(checkpoint) => {
+                let label = format!("Resume push of set {} to session {}", checkpoint.product_set_id, checkpoint.session_id);
+                let job_id = jobs::begin(app, "replace_products", label, Some(checkpoint.shopee_account_id));
+                save(app, &job_id, &Checkpoint::Replace(checkpoint.clone()));
+                resumed.extend(app.state::<JobsState>().get(&job_id));
+
+                let app = app.clone();
+                let (email, password) = (email.clone(), password.clone());
+                tauri::async_runtime::spawn(async move {
+                    let result = crate::resume_replace(&app, &job_id, &email, &password, checkpoint).await;
+                    jobs::finish(&app, &job_id, &result);
+                });
+            }
+        }
+    }
+    if !requests.is_empty() {
+        resumed.extend(job_runner::submit(app, runner, email, password, requests));
+    }
+    Ok(resumed)
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio::sync::Semaphore;
+
+use crate::job_recovery::{self, Checkpoint};
+use crate::jobs::{self, Job, JobHandle, JobsState};
+
+// Jobs running at once across all accounts; each account still runs one job at a time
+const MAX_CONCURRENT_JOBS: usize = 5;
+
+// Work that can be queued for an account. Without `session_id` the job targets whichever
+// live is active on the account when it starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobKind {
+    ReplaceProducts { product_set_id: i32, session_id: Option<String> },
+    PinProduct { item_id: i64, session_id: Option<String> },
+    PostComment { content: String, session_id: Option<String> },
+}
+
+impl JobKind {
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            JobKind::ReplaceProducts { session_id, .. } | JobKind::PinProduct { session_id, .. } | JobKind::PostComment { session_id, .. } => {
+                session_id.as_deref()
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            JobKind::ReplaceProducts { .. } => "replace_products",
+            JobKind::PinProduct { .. } => "pin_product",
+            JobKind::PostComment { .. } => "post_comment",
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            JobKind::ReplaceProducts { product_set_id, .. } => format!("Push set {}", product_set_id),
+            JobKind::PinProduct { item_id, .. } => format!("Pin item {}", item_id),
+            JobKind::PostComment { content, .. } => format!("Comment \"{}\"", content),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRequest {
+    pub shopee_account_id: i32,
+    pub kind: JobKind,
+}
+
+struct QueuedJob {
+    handle: JobHandle,
+    email: String,
+    password: String,
+    kind: JobKind,
+}
+
+// One worker per account drains that account's queue in order; workers share the global slots
+pub struct JobRunnerState {
+    slots: Arc<Semaphore>,
+    workers: Mutex<HashMap<i32, UnboundedSender<QueuedJob>>>,
+}
+
+impl Default for JobRunnerState {
+    fn default() -> Self {
+        JobRunnerState {
+            slots: Arc::new(Semaphore::new(MAX_CONCURRENT_JOBS)),
+            workers: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+async fn execute(app: &AppHandle, job_id: &str, email: &str, password: &str, shopee_account_id: i32, kind: &JobKind) -> Result<(), String> {
+    let session_id = match kind.session_id() {
+        Some(session_id) => session_id.to_string(),
+        None => crate::active_session_id(email, password, shopee_account_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Account is not live")?,
+    };
+    let result = match kind {
+        JobKind::ReplaceProducts { product_set_id, .. } => {
+            crate::replace_products_in_job(app, job_id, email, password, shopee_account_id, &session_id, *product_set_id)
+                .await
+                .map(|_| ())
+        }
+        JobKind::PinProduct { item_id, .. } => crate::pin_product_request(app, email, password, shopee_account_id, &session_id, *item_id).await,
+        JobKind::PostComment { content, .. } => crate::post_live_comment(app, email, password, shopee_account_id, &session_id, content).await,
+    };
+    result.map_err(|e| {
+        crate::diagnostics::record_failure(app, &session_id, "job_runner", &e);
+        e.to_string()
+    })
+}
+
+fn spawn_worker(app: &AppHandle, slots: Arc<Semaphore>, shopee_account_id: i32) -> UnboundedSender<QueuedJob> {
+    let (sender, mut receiver) = unbounded_channel::<QueuedJob>();
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(job) = receiver.recv().await {
+            // A paused job holds its account's queue, but not a global slot
+            job.handle.checkpoint().await;
+            if job.handle.is_cancelled() {
+                continue;
+            }
+            let Ok(_slot) = slots.acquire().await else {
+                return;
+            };
+            crate::blackout::wait_until_clear(&app).await;
+            job.handle.set_running();
+            let result = execute(&app, job.handle.id(), &job.email, &job.password, shopee_account_id, &job.kind).await;
+            job.handle.finish(&result);
+        }
+    });
+    sender
+}
+
+// Queues the jobs and returns immediately. Jobs for different accounts run in parallel up
+// to the global cap; jobs for the same account run one after another in submission order.
+pub fn submit(app: &AppHandle, state: &JobRunnerState, email: String, password: String, requests: Vec<JobRequest>) -> Vec<Job> {
+    let mut queued = Vec::new();
+    for request in requests {
+        let handle = jobs::enqueue(app, request.kind.name(), request.kind.label(), Some(request.shopee_account_id));
+        let id = handle.id().to_string();
+        // Kept until the job finishes, so it can be offered again after a crash
+        job_recovery::save(app, &id, &Checkpoint::Queued { request: request.clone() });
+
+        let mut workers = state.workers.lock().unwrap();
+        let worker = workers
+            .entry(request.shopee_account_id)
+            .or_insert_with(|| spawn_worker(app, state.slots.clone(), request.shopee_account_id));
+        let sent = worker.send(QueuedJob {
+            handle,
+            email: email.clone(),
+            password: password.clone(),
+            kind: request.kind,
+        });
+        drop(workers);
+        if let Err(unsent) = sent {
+            unsent.0.handle.finish::<(), _>(&Err("Job worker stopped"));
+        }
+        queued.extend(app.state::<JobsState>().get(&id));
+    }
+    queued
+}
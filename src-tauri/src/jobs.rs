@@ -0,0 +1,491 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db;
+
+// Jobs kept for the task manager; unfinished jobs are never dropped
+const MAX_JOBS: usize = 200;
+const MAX_LOG_LINES: usize = 100;
+const PAUSE_CHECK: Duration = Duration::from_secs(1);
+// Finished jobs kept in the database
+const MAX_HISTORY: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Paused,
+    Done,
+    // Finished, but some of its targets failed; `error` says which
+    Partial,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobProgress {
+    pub done: usize,
+    // None while the total isn't known yet, e.g. when listing a shop page by page
+    pub total: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobLog {
+    pub at: u64,
+    pub message: String,
+}
+
+// A long operation (import, showcase replace, queued live action) as shown in the task manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: String,
+    pub label: String,
+    pub shopee_account_id: Option<i32>,
+    pub state: JobState,
+    pub progress: Option<JobProgress>,
+    pub logs: Vec<JobLog>,
+    pub error: Option<String>,
+    pub created_at: u64,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+}
+
+type CancelFn = Box<dyn Fn(&AppHandle) + Send + Sync>;
+
+// How a pausable job is controlled. `cancel` stops the job's task; queued jobs without one
+// are cancelled by marking them, and their worker skips them.
+struct JobControl {
+    paused: Arc<AtomicBool>,
+    cancel: Option<CancelFn>,
+}
+
+// Totals for the tray and dashboard header
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationStatus {
+    // Set by pause_all until resume_all; jobs started meanwhile start paused
+    pub paused: bool,
+    pub running: usize,
+    pub queued: usize,
+    // Failed jobs since the app started
+    pub failed: usize,
+}
+
+#[derive(Default)]
+pub struct JobsState {
+    jobs: Mutex<VecDeque<Job>>,
+    controls: Mutex<HashMap<String, JobControl>>,
+    next_id: Mutex<u64>,
+    // Jobs paused by pause_all, while everything is paused. Jobs the user had paused
+    // themselves aren't in it, so resume_all leaves them paused.
+    paused_all: Mutex<Option<HashSet<String>>>,
+}
+
+impl JobsState {
+    // Newest first, optionally only one account's
+    pub fn list(&self, shopee_account_id: Option<i32>) -> Vec<Job> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter()
+            .rev()
+            .filter(|job| shopee_account_id.is_none_or(|id| job.shopee_account_id == Some(id)))
+            .map(|job| self.view(job))
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.jobs.lock().unwrap().iter().find(|job| job.id == id).map(|job| self.view(job))
+    }
+
+    // Jobs report Paused while their flag is set, whoever set it
+    fn view(&self, job: &Job) -> Job {
+        let mut job = job.clone();
+        let paused = self.controls.lock().unwrap().get(&job.id).is_some_and(|c| c.paused.load(Ordering::SeqCst));
+        if paused && matches!(job.state, JobState::Queued | JobState::Running) {
+            job.state = JobState::Paused;
+        }
+        job
+    }
+
+    fn insert(&self, kind: &str, label: String, shopee_account_id: Option<i32>) -> Job {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            format!("job-{}-{}", crate::unix_now(), next_id)
+        };
+        let job = Job {
+            id,
+            kind: kind.to_string(),
+            label,
+            shopee_account_id,
+            state: JobState::Queued,
+            progress: None,
+            logs: Vec::new(),
+            error: None,
+            created_at: crate::unix_now(),
+            started_at: None,
+            finished_at: None,
+        };
+
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.push_back(job.clone());
+        let mut excess = jobs.len().saturating_sub(MAX_JOBS);
+        jobs.retain(|job| {
+            if excess > 0 && job.finished_at.is_some() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+        job
+    }
+
+    fn modify(&self, id: &str, f: impl FnOnce(&mut Job)) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.iter_mut().find(|job| job.id == id)?;
+        f(job);
+        let job = job.clone();
+        drop(jobs);
+        Some(self.view(&job))
+    }
+
+    pub fn status(&self) -> AutomationStatus {
+        let mut status = AutomationStatus {
+            paused: self.paused_all.lock().unwrap().is_some(),
+            running: 0,
+            queued: 0,
+            failed: 0,
+        };
+        for job in self.list(None) {
+            match job.state {
+                JobState::Running => status.running += 1,
+                JobState::Queued => status.queued += 1,
+                JobState::Failed | JobState::Partial => status.failed += 1,
+                JobState::Paused | JobState::Done | JobState::Cancelled => {}
+            }
+        }
+        status
+    }
+
+    fn paused_flag(&self, id: &str) -> Result<Arc<AtomicBool>, String> {
+        self.controls
+            .lock()
+            .unwrap()
+            .get(id)
+            .map(|c| c.paused.clone())
+            .ok_or_else(|| format!("Job {} can't be paused", id))
+    }
+}
+
+fn emit(app: &AppHandle, job: Job) {
+    if let Err(e) = app.emit("job-updated", job) {
+        eprintln!("Failed to emit job-updated: {}", e);
+    }
+    crate::background::refresh_tray(app);
+}
+
+fn modify(app: &AppHandle, id: &str, f: impl FnOnce(&mut Job)) {
+    if let Some(job) = app.state::<JobsState>().modify(id, f) {
+        if job.finished_at.is_some() {
+            if let Err(e) = save_history(app, &job) {
+                eprintln!("[JOBS] Failed to save job {} to history: {}", job.id, e);
+            }
+            crate::job_recovery::clear(app, &job.id);
+        }
+        emit(app, job);
+    }
+}
+
+fn save_history(app: &AppHandle, job: &Job) -> Result<(), String> {
+    let state = serde_json::to_value(job.state).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default();
+    db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO job_history (id, kind, shopee_account_id, state, finished_at, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                &job.id,
+                &job.kind,
+                job.shopee_account_id,
+                state,
+                job.finished_at.unwrap_or_default() as i64,
+                db::to_json(job)?,
+            ),
+        )?;
+        conn.execute(
+            "DELETE FROM job_history WHERE id NOT IN (SELECT id FROM job_history ORDER BY finished_at DESC LIMIT ?1)",
+            [MAX_HISTORY as i64],
+        )?;
+        Ok(())
+    })
+}
+
+// Finished jobs from this and earlier runs, newest first
+pub fn history(app: &AppHandle, shopee_account_id: Option<i32>, limit: usize) -> Result<Vec<Job>, String> {
+    db::with(app, |conn| {
+        let mut statement = conn.prepare(
+            "SELECT data FROM job_history WHERE ?1 IS NULL OR shopee_account_id = ?1 ORDER BY finished_at DESC LIMIT ?2",
+        )?;
+        let mut jobs = Vec::new();
+        for data in statement.query_map((shopee_account_id, limit as i64), |row| row.get::<_, String>(0))? {
+            jobs.push(db::from_json(&data?)?);
+        }
+        Ok(jobs)
+    })
+}
+
+// Adds a job that is waiting to run, returning its ID
+pub fn register(app: &AppHandle, kind: &str, label: impl Into<String>, shopee_account_id: Option<i32>) -> String {
+    let job = app.state::<JobsState>().insert(kind, label.into(), shopee_account_id);
+    let id = job.id.clone();
+    emit(app, job);
+    id
+}
+
+// Adds a job that starts right away
+pub fn begin(app: &AppHandle, kind: &str, label: impl Into<String>, shopee_account_id: Option<i32>) -> String {
+    let id = register(app, kind, label, shopee_account_id);
+    set_running(app, &id);
+    id
+}
+
+pub fn set_running(app: &AppHandle, id: &str) {
+    modify(app, id, |job| {
+        job.state = JobState::Running;
+        job.started_at = Some(crate::unix_now());
+    });
+}
+
+pub fn progress(app: &AppHandle, id: &str, done: usize, total: Option<usize>) {
+    modify(app, id, |job| job.progress = Some(JobProgress { done, total }));
+}
+
+pub fn log(app: &AppHandle, id: &str, message: impl Into<String>) {
+    let message = message.into();
+    modify(app, id, |job| {
+        if job.logs.len() >= MAX_LOG_LINES {
+            job.logs.remove(0);
+        }
+        job.logs.push(JobLog { at: crate::unix_now(), message });
+    });
+}
+
+// Marks a job that is still running as having failed some of its targets, so `finish`
+// reports it as Partial rather than Done
+pub fn partial(app: &AppHandle, id: &str, error: impl Into<String>) {
+    let error = error.into();
+    modify(app, id, |job| job.error = Some(error));
+}
+
+pub fn finish<T, E: Display>(app: &AppHandle, id: &str, result: &Result<T, E>) {
+    let state = app.state::<JobsState>();
+    state.controls.lock().unwrap().remove(id);
+    modify(app, id, |job| {
+        job.finished_at = Some(crate::unix_now());
+        match result {
+            Ok(_) if job.error.is_some() => job.state = JobState::Partial,
+            Ok(_) => job.state = JobState::Done,
+            Err(e) => {
+                job.state = JobState::Failed;
+                job.error = Some(e.to_string());
+            }
+        }
+    });
+    if let Some(job) = state.get(id) {
+        crate::notifications::job_finished(app, &job);
+    }
+}
+
+// Held by a pausable job's task. Dropping it without `finish`, as happens when the task is
+// aborted, marks the job cancelled.
+pub struct JobHandle {
+    app: AppHandle,
+    id: String,
+    paused: Arc<AtomicBool>,
+    finished: bool,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // Shared pause flag, for tasks that also expose their own pause commands
+    pub fn paused_flag(&self) -> Arc<AtomicBool> {
+        self.paused.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        app_job_state(&self.app, &self.id) == Some(JobState::Cancelled)
+    }
+
+    // Waits here while the job is paused
+    pub async fn checkpoint(&self) {
+        while self.paused.load(Ordering::SeqCst) {
+            tokio::time::sleep(PAUSE_CHECK).await;
+        }
+    }
+
+    // Sleeps in short steps that don't count while paused, so a paused timer stands still
+    pub async fn sleep(&self, duration: Duration) {
+        let mut waited = Duration::ZERO;
+        while waited < duration {
+            let step = PAUSE_CHECK.min(duration - waited);
+            tokio::time::sleep(step).await;
+            if !self.paused.load(Ordering::SeqCst) {
+                waited += step;
+            }
+        }
+    }
+
+    pub fn set_running(&self) {
+        set_running(&self.app, &self.id);
+    }
+
+    pub fn finish<T, E: Display>(mut self, result: &Result<T, E>) {
+        self.finished = true;
+        finish(&self.app, &self.id, result);
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.app.state::<JobsState>().controls.lock().unwrap().remove(&self.id);
+        modify(&self.app, &self.id, |job| {
+            if job.finished_at.is_none() {
+                job.state = JobState::Cancelled;
+                job.finished_at = Some(crate::unix_now());
+            }
+        });
+    }
+}
+
+fn app_job_state(app: &AppHandle, id: &str) -> Option<JobState> {
+    app.state::<JobsState>().get(id).map(|job| job.state)
+}
+
+fn controlled(app: &AppHandle, id: String, cancel: Option<CancelFn>) -> JobHandle {
+    let state = app.state::<JobsState>();
+    let paused_all = state.paused_all.lock().unwrap().as_mut().map(|ids| ids.insert(id.clone())).is_some();
+    let paused = Arc::new(AtomicBool::new(paused_all));
+    state.controls.lock().unwrap().insert(
+        id.clone(),
+        JobControl {
+            paused: paused.clone(),
+            cancel,
+        },
+    );
+    JobHandle {
+        app: app.clone(),
+        id,
+        paused,
+        finished: false,
+    }
+}
+
+// Registers a background loop that runs until stopped; `cancel` must stop its task
+pub fn start_background(
+    app: &AppHandle,
+    kind: &str,
+    label: impl Into<String>,
+    shopee_account_id: Option<i32>,
+    cancel: impl Fn(&AppHandle) + Send + Sync + 'static,
+) -> JobHandle {
+    let id = begin(app, kind, label, shopee_account_id);
+    controlled(app, id, Some(Box::new(cancel)))
+}
+
+// Registers a job that waits in a queue; it can be paused or cancelled until it starts
+pub fn enqueue(app: &AppHandle, kind: &str, label: impl Into<String>, shopee_account_id: Option<i32>) -> JobHandle {
+    let id = register(app, kind, label, shopee_account_id);
+    controlled(app, id, None)
+}
+
+fn set_paused(app: &AppHandle, id: &str, paused: bool) -> Result<Job, String> {
+    let state = app.state::<JobsState>();
+    state.paused_flag(id)?.store(paused, Ordering::SeqCst);
+    let job = state.get(id).ok_or_else(|| format!("Job {} not found", id))?;
+    emit(app, job.clone());
+    Ok(job)
+}
+
+pub fn pause(app: &AppHandle, id: &str) -> Result<Job, String> {
+    set_paused(app, id, true)
+}
+
+pub fn resume(app: &AppHandle, id: &str) -> Result<Job, String> {
+    set_paused(app, id, false)
+}
+
+// Pauses every pausable job: schedules, rotations, automation rules, queued actions...
+pub fn pause_all(app: &AppHandle) -> AutomationStatus {
+    let state = app.state::<JobsState>();
+    let newly_paused: Vec<String> = {
+        let mut paused_all = state.paused_all.lock().unwrap();
+        let ids = paused_all.get_or_insert_with(HashSet::new);
+        let controls = state.controls.lock().unwrap();
+        controls
+            .iter()
+            .filter(|(_, control)| !control.paused.swap(true, Ordering::SeqCst))
+            .map(|(id, _)| id.clone())
+            .inspect(|id| {
+                ids.insert(id.clone());
+            })
+            .collect()
+    };
+    for id in newly_paused {
+        if let Some(job) = state.get(&id) {
+            emit(app, job);
+        }
+    }
+    crate::background::refresh_tray(app);
+    state.status()
+}
+
+// Resumes what pause_all paused
+pub fn resume_all(app: &AppHandle) -> AutomationStatus {
+    let state = app.state::<JobsState>();
+    let ids = state.paused_all.lock().unwrap().take().unwrap_or_default();
+    for id in ids {
+        // Jobs that finished while paused have no flag left to clear
+        let _ = set_paused(app, &id, false);
+    }
+    crate::background::refresh_tray(app);
+    state.status()
+}
+
+pub fn cancel(app: &AppHandle, id: &str) -> Result<Job, String> {
+    let state = app.state::<JobsState>();
+    let job = state.get(id).ok_or_else(|| format!("Job {} not found", id))?;
+    if job.finished_at.is_some() {
+        return Err(format!("Job {} has already finished", id));
+    }
+
+    let control = {
+        let mut controls = state.controls.lock().unwrap();
+        match controls.get(id) {
+            Some(control) if control.cancel.is_none() && job.state == JobState::Running => {
+                return Err(format!("Job {} is already running and can't be cancelled", id));
+            }
+            Some(_) => controls.remove(id),
+            None => return Err(format!("Job {} can't be cancelled", id)),
+        }
+    };
+    if let Some(cancel) = control.and_then(|c| c.cancel) {
+        cancel(app);
+    }
+    modify(app, id, |job| {
+        if job.finished_at.is_none() {
+            job.state = JobState::Cancelled;
+            job.finished_at = Some(crate::unix_now());
+        }
+    });
+    state.get(id).ok_or_else(|| format!("Job {} not found", id))
+}
@@ -1,17 +1,125 @@
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use tauri::{Emitter, Manager};
 
-const BASE_URL: &str = "https://livekenceng.com";
+mod account_archive;
+mod account_export;
+mod account_identity;
+mod account_import;
+mod account_notes;
+mod account_proxies;
+mod account_tags;
+mod accounts_cache;
+mod announcements;
+mod annotations;
+mod audit;
+mod auto_reply;
+mod automation;
+mod background;
+mod backup;
+mod blackout;
+mod clear_undo;
+mod clipboard_watch;
+mod cookie_health;
+mod cookie_history;
+mod cookie_keepalive;
+mod confirmations;
+mod cookies;
+mod db;
+mod deep_link;
+mod diagnostics;
+mod disabled_items;
+mod dry_run;
+mod email_alerts;
+mod encrypted_store;
+mod error;
+mod image_cache;
+mod ipc_encoding;
+mod item_order;
+mod job_recovery;
+mod job_runner;
+mod jobs;
+mod live_session;
+mod live_timeseries;
+mod live_sync;
+#[cfg(feature = "load-test")]
+mod load_test;
+mod machine_binding;
+mod machine_id;
+mod migrations;
+mod moderation;
+mod notifications;
+mod operators;
+mod pacing;
+mod pin_rotation;
+mod price_format;
+mod product_import;
+mod product_items;
+mod product_metadata;
+mod product_search;
+mod product_validation;
+mod promo_comments;
+mod remote_assist;
+mod scheduler;
+mod shop_import;
+mod session_reports;
+mod session_watchdog;
+mod settings;
+mod shopee_url;
+mod showcase;
+mod showcase_snapshots;
+mod storage;
+mod storage_cleanup;
+mod updates;
+mod watch_folder;
+mod webhooks;
 
-// ==================== Data Structures ====================
+use account_import::ImportSummary;
+use account_notes::AccountNotes;
+use accounts_cache::AccountsCacheState;
+use annotations::{Annotation, AnnotationEntry, AnnotationTarget};
+use auto_reply::AutoReplyState;
+use automation::AutomationState;
+use blackout::BlackoutWindow;
+use clipboard_watch::ClipboardWatchState;
+use cookie_health::{CookieHealth, CookieStatus};
+use cookie_keepalive::CookieKeepAliveState;
+use confirmations::{ConfirmationState, DestructiveOutcome, Impact};
+use cookies::CookieJar;
+use db::DbState;
+use deep_link::DeepLinkState;
+use diagnostics::{DiagnosticsState, FailureExplanation};
+use dry_run::{LiveOutcome, PlannedAction};
+use email_alerts::EmailAlertState;
+use error::{AggregateError, CommandError};
+use ipc_encoding::IpcEncoding;
+use job_recovery::JobRecoveryState;
+use job_runner::JobRunnerState;
+use jobs::JobsState;
+use live_session::{HeartbeatState, SessionWatchState, ViewerPollingState};
+use live_sync::{LiveSyncBinding, LiveSyncState};
+use machine_binding::MachineBindingState;
+use moderation::ModerationState;
+use notifications::NotificationState;
+use operators::{OperatorState, OperatorSummary};
+use pacing::{PacedAction, PacingState};
+use pin_rotation::{PinOrder, PinRotationState};
+use product_items::{AddItemsSummary, ProductSetItemInput};
+use promo_comments::PromoCommentState;
+use remote_assist::{AssistSession, RemoteAssistState};
+use scheduler::SchedulerState;
+use session_reports::SessionReportState;
+use session_watchdog::SessionWatchdogState;
+use settings::LogLevel;
+use showcase::ShowcaseRotationState;
+use updates::UpdateState;
+use watch_folder::{WatchFolderConfig, WatchFolderState};
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ApiResponse<T> {
-    success: bool,
-    #[serde(flatten)]
-    data: Option<T>,
-    message: Option<String>,
-}
+pub use botgacor_core::models::*;
+pub use botgacor_core::shopee::ApiFailure as ShopeeApiFailure;
+use botgacor_core::{members, shopee};
+
+
+// ==================== Data Structures ====================
 
 #[derive(Debug, Serialize, Deserialize)]
 struct LoginRequest {
@@ -26,29 +134,12 @@ struct LoginResponse {
     user: User,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct User {
-    pub id: i32,
-    pub email: String,
-    pub telegram_username: Option<String>,
-    pub expiry_date: Option<String>,
-    pub machine_id: String,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct RedeemLicenseRequest {
     email: String,
     license_key: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct RedeemLicenseResponse {
-    pub expiry_date: Option<String>,
-    pub days_added: Option<i32>,
-    pub is_new_member: bool,
-    pub password: Option<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct ChangePasswordRequest {
     email: String,
@@ -57,61 +148,6 @@ struct ChangePasswordRequest {
     machine_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShopeeAccount {
-    pub id: i32,
-    pub name: String,
-    pub is_active: bool,
-    pub created_at: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShopeeAccountsResponse {
-    pub data: Vec<ShopeeAccount>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Niche {
-    pub id: i32,
-    pub name: String,
-    pub description: Option<String>,
-    #[serde(default)]
-    pub product_sets: Vec<ProductSet>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct NichesResponse {
-    pub niches: Vec<Niche>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProductSet {
-    pub id: i32,
-    pub name: String,
-    pub description: Option<String>,
-    pub niche_id: Option<i32>,
-    #[serde(default)]
-    pub items: Vec<ProductSetItem>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProductSetsResponse {
-    pub product_sets: Vec<ProductSet>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProductSetItem {
-    pub id: i32,
-    pub url: String,
-    pub shop_id: Option<i64>,
-    pub item_id: Option<i64>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SessionIdsResponse {
-    pub session_ids: Vec<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct ActiveSessionApiResponse {
     pub success: bool,
@@ -186,103 +222,101 @@ where
     deserializer.deserialize_option(SessionIdVisitor)
 }
 
-// QR Code structures
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShopeeQRData {
-    pub qrcode_id: String,
-    pub qrcode_base64: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ShopeeQRResponse {
-    error: i32,
-    error_msg: Option<String>,
-    data: Option<ShopeeQRData>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct QRStatusData {
-    pub qrcode_token: String,
-    pub status: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ShopeeQRStatusResponse {
-    error: i32,
-    error_msg: Option<String>,
-    data: Option<QRStatusData>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppQRStatus {
     pub status: String,
     pub qrcode_token: Option<String>,
+    // Set when the QR expired and a replacement was generated; poll with its qrcode_id from now on
+    pub regenerated: Option<ShopeeQRData>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct QRCodeLoginRequest {
-    qrcode_token: String,
-    device_sz_fingerprint: String,
-    client_identifier: ClientIdentifier,
+#[derive(Debug, Serialize)]
+pub struct LoginResult {
+    pub success: bool,
+    pub cookies: Option<String>,
+    pub error_msg: Option<String>,
+    // Present when Shopee wants the user to solve a captcha or verify the login first
+    pub challenge: Option<LoginChallenge>,
+    // Earliest expiry (unix seconds) among the returned cookies
+    pub cookies_expire_at: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ClientIdentifier {
-    security_device_fingerprint: String,
+impl LoginResult {
+    fn signed_in(set_cookie_headers: &[String]) -> Self {
+        let jar = CookieJar::from_set_cookie_headers(set_cookie_headers);
+        LoginResult {
+            success: true,
+            cookies: Some(jar.to_header()),
+            error_msg: None,
+            challenge: None,
+            cookies_expire_at: jar.earliest_expiry(),
+        }
+    }
+
+    fn failed(error_msg: Option<String>, challenge: Option<LoginChallenge>) -> Self {
+        LoginResult {
+            success: false,
+            cookies: None,
+            error_msg,
+            challenge,
+            cookies_expire_at: None,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct QRCodeLoginResponse {
-    bff_meta: Option<serde_json::Value>,
-    error: i32,
-    error_msg: Option<String>,
-    data: Option<serde_json::Value>,
+#[derive(Debug, Serialize)]
+pub struct LoginChallenge {
+    pub kind: String,
+    pub verify_url: String,
+    pub flow_no: Option<String>,
+    pub raw: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct LoginResult {
-    pub success: bool,
-    pub cookies: Option<String>,
+pub struct OtpRequestResult {
+    pub sent: bool,
     pub error_msg: Option<String>,
+    pub challenge: Option<LoginChallenge>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ShopeeAccountInfo {
-    pub userid: i64,
-    pub username: String,
-    #[serde(default)]
-    pub nickname: Option<String>,
-    pub email: Option<String>,
-    pub phone: Option<String>,
+// Phone number and Shopee's OTP seed from the last request_shopee_otp, consumed by verify_shopee_otp
+#[derive(Debug, Clone)]
+struct PendingOtp {
+    phone: String,
+    otp_seed: Option<String>,
+}
+
+#[derive(Default)]
+struct OtpState {
+    pending: std::sync::Mutex<Option<PendingOtp>>,
+}
+
+// Replacement QR for each expired qrcode_id, so repeat polls of an expired code don't mint another
+#[derive(Default)]
+struct QrState {
+    regenerated: std::sync::Mutex<std::collections::HashMap<String, ShopeeQRData>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-struct ShopeeAccountInfoResponse {
-    error: i32,
-    error_msg: Option<String>,
-    data: Option<ShopeeAccountInfo>,
+pub struct ShopProfile {
+    pub account_id: i32,
+    pub shop_id: i64,
+    pub shop_name: String,
+    pub avatar_url: Option<String>,
+    pub follower_count: i64,
 }
 
 // ==================== Utility Functions ====================
 
-fn generate_machine_id() -> String {
-    // Generate consistent machine ID based on hardware info
-    // For simplicity, we'll use a hash of system info
-    // In production, you might want to use systeminfo crate or similar
-    use std::env;
-    let hostname = env::var("COMPUTERNAME").or_else(|_| env::var("HOSTNAME")).unwrap_or_else(|_| "unknown".to_string());
-    let user = env::var("USER").or_else(|_| env::var("USERNAME")).unwrap_or_else(|_| "unknown".to_string());
-    
-    let combined = format!("{}-{}", hostname, user);
-    let mut hasher = Sha256::new();
-    hasher.update(combined.as_bytes());
-    hex::encode(hasher.finalize())[..16].to_string()
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 fn get_or_generate_machine_id() -> String {
-    // Try to get from a simple file-based storage or generate new
-    // For now, just generate consistently
-    generate_machine_id()
+    machine_id::current()
 }
 
 async fn make_api_request<T: for<'de> Deserialize<'de>>(
@@ -290,77 +324,115 @@ async fn make_api_request<T: for<'de> Deserialize<'de>>(
     endpoint: &str,
     body: Option<&serde_json::Value>,
     query_params: Option<&str>,
-) -> Result<T, String> {
-    let client = reqwest::Client::new();
-    let mut url = format!("{}{}", BASE_URL, endpoint);
+) -> Result<T, CommandError> {
+    let base_url = settings::current().environment.base_url().to_string();
+    make_api_request_at(&base_url, method, endpoint, body, query_params).await
+}
+
+// Endpoints whose responses carry secrets (the push URL embeds the stream key), so their
+// bodies are never logged
+const REDACTED_RESPONSE_ENDPOINTS: &[&str] = &["/api/shopee-live/push-url"];
+
+async fn make_api_request_at<T: for<'de> Deserialize<'de>>(
+    base_url: &str,
+    method: &str,
+    endpoint: &str,
+    body: Option<&serde_json::Value>,
+    query_params: Option<&str>,
+) -> Result<T, CommandError> {
+    let client = members::Client::with_base_url(base_url).with_timeout(settings::current().request_timeout());
+    let mut url = format!("{}{}", client.base_url(), endpoint);
     
     if let Some(query) = query_params {
         url = format!("{}?{}", url, query);
     }
     
-    // Log API request
-    println!("[API REQUEST] {} {}", method, url);
-    if let Some(json_body) = body {
-        let body_str = serde_json::to_string_pretty(json_body).unwrap_or_else(|_| "Failed to serialize".to_string());
-        println!("[API REQUEST BODY]\n{}", body_str);
-    }
-    if let Some(query) = query_params {
-        println!("[API REQUEST QUERY] {}", query);
-    }
-    
-    let mut request = match method {
-        "GET" => client.get(&url),
-        "POST" => client.post(&url),
-        "PUT" => client.put(&url),
-        "DELETE" => client.delete(&url),
-        _ => return Err("Invalid HTTP method".to_string()),
-    };
-    
-    if let Some(json_body) = body {
-        request = request.json(json_body);
+    // Log API request; bodies carry credentials and are only logged at debug level
+    if settings::logs(LogLevel::Info) {
+        println!("[API REQUEST] {} {}", method, url);
     }
-    
-    if method != "GET" || body.is_some() {
-        request = request.header("Content-Type", "application/json");
+    if settings::logs(LogLevel::Debug) {
+        if let Some(json_body) = body {
+            let body_str = serde_json::to_string_pretty(json_body).unwrap_or_else(|_| "Failed to serialize".to_string());
+            println!("[API REQUEST BODY]\n{}", body_str);
+        }
+        if let Some(query) = query_params {
+            println!("[API REQUEST QUERY] {}", query);
+        }
     }
     
-    let response = request
-        .send()
+    let (status, text) = client
+        .send(method, endpoint, body, query_params)
         .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+        .map_err(|e| e.to_string())?;
+    let status = reqwest::StatusCode::from_u16(status).map_err(|e| format!("Invalid HTTP status: {}", e))?;
     
     // Log API response
-    println!("[API RESPONSE] HTTP {} {}", status, endpoint);
-    if text.len() < 500 {
-        println!("[API RESPONSE BODY]\n{}", text);
-    } else {
-        println!("[API RESPONSE BODY] (truncated, {} chars)\n{}", text.len(), &text[..500]);
+    if settings::logs(LogLevel::Info) {
+        println!("[API RESPONSE] HTTP {} {}", status, endpoint);
     }
-    
-    if !status.is_success() {
-        println!("[API ERROR] HTTP {}: {}", status, text);
-        return Err(format!("HTTP {}: {}", status, text));
+    let redacted = REDACTED_RESPONSE_ENDPOINTS.contains(&endpoint);
+    if settings::logs(LogLevel::Debug) {
+        if redacted {
+            println!("[API RESPONSE BODY] (redacted, {} chars)", text.len());
+        } else if text.len() < 500 {
+            println!("[API RESPONSE BODY]\n{}", text);
+        } else {
+            println!("[API RESPONSE BODY] (truncated, {} chars)\n{}", text.len(), &text[..500]);
+        }
     }
     
-    match serde_json::from_str::<T>(&text) {
+    match members::decode::<T>(status.as_u16(), &text) {
         Ok(parsed) => {
-            println!("[API SUCCESS] Parsed response successfully");
+            if settings::logs(LogLevel::Debug) {
+                println!("[API SUCCESS] Parsed response successfully");
+            }
             Ok(parsed)
         }
-        Err(e) => {
-            println!("[API PARSE ERROR] {} - Response: {}", e, text);
-            Err(format!("Failed to parse response: {} - {}", e, text))
+        Err(members::Error::Status { status: code, body }) => {
+            if !status.is_success() && settings::logs(LogLevel::Error) {
+                println!("[API ERROR] HTTP {}: {}", status, body);
+            }
+            Err(CommandError::from_response(code, endpoint, &body))
+        }
+        Err(members::Error::Parse { message, .. }) => {
+            let shown = if redacted { "(redacted)" } else { text.as_str() };
+            if settings::logs(LogLevel::Error) {
+                println!("[API PARSE ERROR] {} - Response: {}", message, shown);
+            }
+            Err(format!("Failed to parse response: {} - {}", message, shown).into())
         }
+        Err(e) => Err(e.to_string().into()),
+    }
+}
+
+// Shopee requests made on behalf of an account go through its bound proxy, if any.
+// Live operations are sent to the livekenceng backend, which talks to Shopee itself.
+fn shopee_client(user_agent: &str, proxy: Option<&str>) -> Result<reqwest::Client, String> {
+    shopee::Client::with_timeout(user_agent, proxy, Some(settings::current().request_timeout())).map(|client| client.http().clone())
+}
+
+// Desktop Shopee client with the request timeout from settings
+pub(crate) fn shopee_api_client(proxy: Option<&str>) -> Result<shopee::Client, String> {
+    shopee::Client::with_timeout(shopee::DESKTOP_USER_AGENT, proxy, Some(settings::current().request_timeout()))
+}
+
+// Shopee client for the login endpoints, which expect the web login page's user agent
+fn shopee_login_client(proxy: Option<&str>) -> Result<shopee::Client, String> {
+    shopee::Client::with_timeout(shopee::LOGIN_USER_AGENT, proxy, Some(settings::current().request_timeout()))
+}
+
+fn account_proxy(app: &tauri::AppHandle, account_id: Option<i32>) -> Option<String> {
+    match account_id {
+        Some(id) => account_proxies::proxy_for(app, id),
+        None => settings::current().default_proxy,
     }
 }
 
 // ==================== Tauri Commands ====================
 
 #[tauri::command]
-async fn get_machine_id() -> Result<String, String> {
+async fn get_machine_id() -> Result<String, CommandError> {
     Ok(get_or_generate_machine_id())
 }
 
@@ -373,7 +445,7 @@ struct MachineIdResponse {
 }
 
 #[tauri::command]
-async fn get_user_machine_id(email: String) -> Result<MachineIdResponse, String> {
+async fn get_user_machine_id(email: String) -> Result<MachineIdResponse, CommandError> {
     let encoded_email = urlencoding::encode(&email);
     // Endpoint already includes query param in URL
     let endpoint = format!("/api/members/machine-id/{}?app_identifier=botgacor", encoded_email);
@@ -383,16 +455,76 @@ async fn get_user_machine_id(email: String) -> Result<MachineIdResponse, String>
     let response: MachineIdResponse = make_api_request("GET", &endpoint, None, None).await?;
     
     if !response.success {
-        return Err("Failed to get machine ID from server".to_string());
+        return Err("Failed to get machine ID from server".to_string().into());
     }
     
     Ok(response)
 }
 
 #[tauri::command]
-async fn login(email: String, password: String, machine_id: String) -> Result<LoginResponse, String> {
+async fn start_machine_binding_watch(app: tauri::AppHandle, state: tauri::State<'_, MachineBindingState>, email: String) -> Result<(), String> {
+    machine_binding::start(&app, &state, email);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_machine_binding_watch(state: tauri::State<'_, MachineBindingState>) -> Result<(), String> {
+    state.stop();
+    Ok(())
+}
+
+// Remote assist (opt-in, time-limited telemetry for support)
+#[tauri::command]
+async fn start_remote_assist(app: tauri::AppHandle, email: String, password: String, minutes: u64) -> Result<AssistSession, String> {
+    remote_assist::start(&app, email, password, minutes)
+}
+
+#[tauri::command]
+async fn stop_remote_assist(app: tauri::AppHandle) -> Result<(), String> {
+    remote_assist::stop(&app);
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_remote_assist(state: tauri::State<'_, RemoteAssistState>) -> Result<Option<AssistSession>, String> {
+    Ok(state.current())
+}
+
+// Operator profiles
+#[tauri::command]
+async fn list_operators(app: tauri::AppHandle) -> Result<Vec<OperatorSummary>, String> {
+    operators::list(&app)
+}
+
+#[tauri::command]
+async fn add_operator(app: tauri::AppHandle, name: String, pin: Option<String>) -> Result<(), String> {
+    operators::add(&app, &name, pin.as_deref())
+}
+
+#[tauri::command]
+async fn remove_operator(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    operators::remove(&app, &name)
+}
+
+#[tauri::command]
+async fn switch_operator(app: tauri::AppHandle, name: String, pin: Option<String>) -> Result<(), String> {
+    operators::switch(&app, &name, pin.as_deref())
+}
+
+#[tauri::command]
+async fn get_active_operator(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    Ok(operators::current(&app))
+}
+
+#[tauri::command]
+async fn login(app: tauri::AppHandle, email: String, password: String, machine_id: String) -> Result<LoginResponse, CommandError> {
+    // Move licenses still bound to the old hostname-based ID over before logging in
+    if let Err(e) = machine_id::migrate_if_needed(&app, &email, &password).await {
+        eprintln!("[MACHINE ID] Legacy ID migration failed: {}", e);
+    }
+    
     let request = LoginRequest {
-        email,
+        email: email.clone(),
         password,
         machine_id,
         app_identifier: "botgacor".to_string(),
@@ -401,14 +533,63 @@ async fn login(email: String, password: String, machine_id: String) -> Result<Lo
     let response: ApiResponse<LoginResponse> = make_api_request("POST", "/api/members/login", Some(&serde_json::to_value(request).unwrap()), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Login failed".to_string()));
+        let message = response.message.unwrap_or_else(|| "Login failed".to_string());
+        if is_license_expired_message(&message) {
+            notifications::license_expired(&app, &email, None);
+        }
+        return Err(message.into());
     }
     
-    response.data.ok_or_else(|| "No user data in response".to_string())
+    let data = response.data.ok_or_else(|| CommandError::from("No user data in response"))?;
+    if let Some(expiry_date) = data.user.expiry_date.as_deref().filter(|d| expiry_passed(d)) {
+        notifications::license_expired(&app, &email, Some(expiry_date));
+    }
+    Ok(data)
+}
+
+fn is_license_expired_message(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    ["expired", "kedaluwarsa", "kadaluarsa"].iter().any(|p| lower.contains(p))
+}
+
+// The backend sends either a date or a full timestamp; a date is valid through the end of that day
+fn expiry_passed(expiry_date: &str) -> bool {
+    let now = chrono::Utc::now();
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(expiry_date) {
+        return at < now;
+    }
+    if let Ok(at) = chrono::NaiveDateTime::parse_from_str(expiry_date, "%Y-%m-%d %H:%M:%S") {
+        return at < now.naive_utc();
+    }
+    chrono::NaiveDate::parse_from_str(expiry_date, "%Y-%m-%d").is_ok_and(|date| date < chrono::Local::now().date_naive())
+}
+
+// Vendor news and maintenance notices, cached locally; `refresh` skips the cache
+#[tauri::command]
+async fn get_announcements(app: tauri::AppHandle, refresh: Option<bool>) -> Result<announcements::AnnouncementFeed, CommandError> {
+    announcements::get(&app, refresh.unwrap_or(false)).await
+}
+
+// All announcements when `ids` is omitted; returns how many are still unread
+#[tauri::command]
+async fn mark_announcements_read(app: tauri::AppHandle, ids: Option<Vec<String>>) -> Result<usize, String> {
+    announcements::mark_read(&app, ids)
+}
+
+// Latest release for this platform; `mandatory` means this version no longer works with the API
+#[tauri::command]
+async fn check_for_update(app: tauri::AppHandle) -> Result<updates::UpdateInfo, CommandError> {
+    updates::check(&app).await
+}
+
+// Result of a livekenceng:// link that arrived before the UI was listening for `deep-link`
+#[tauri::command]
+async fn take_pending_deep_link(app: tauri::AppHandle) -> Result<Option<deep_link::DeepLinkEvent>, String> {
+    Ok(deep_link::take_pending(&app))
 }
 
 #[tauri::command]
-async fn redeem_license(email: String, license_key: String) -> Result<RedeemLicenseResponse, String> {
+async fn redeem_license(email: String, license_key: String) -> Result<RedeemLicenseResponse, CommandError> {
     let request = RedeemLicenseRequest {
         email,
         license_key,
@@ -417,14 +598,14 @@ async fn redeem_license(email: String, license_key: String) -> Result<RedeemLice
     let response: ApiResponse<RedeemLicenseResponse> = make_api_request("POST", "/api/members/redeem-license", Some(&serde_json::to_value(request).unwrap()), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Redeem failed".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Redeem failed".to_string()).into());
     }
     
-    response.data.ok_or_else(|| "No data in response".to_string())
+    response.data.ok_or_else(|| "No data in response".into())
 }
 
 #[tauri::command]
-async fn update_machine_id(email: String, machine_id: String, password: Option<String>) -> Result<(), String> {
+async fn update_machine_id(email: String, machine_id: String, password: Option<String>) -> Result<(), CommandError> {
     let mut body = serde_json::json!({
         "email": email,
         "machine_id": machine_id,
@@ -439,7 +620,7 @@ async fn update_machine_id(email: String, machine_id: String, password: Option<S
     let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/machine-id", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to update machine ID".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to update machine ID".to_string()).into());
     }
     
     Ok(())
@@ -451,7 +632,7 @@ async fn change_password(
     new_password: String,
     machine_id: String,
     current_password: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let request = ChangePasswordRequest {
         email,
         current_password,
@@ -462,26 +643,75 @@ async fn change_password(
     let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/change-password", Some(&serde_json::to_value(request).unwrap()), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Change password failed".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Change password failed".to_string()).into());
     }
     
     Ok(())
 }
 
 #[tauri::command]
-async fn get_shopee_accounts(email: String, password: String) -> Result<ShopeeAccountsResponse, String> {
-    let query = format!("email={}&password={}", urlencoding::encode(&email), urlencoding::encode(&password));
+async fn get_shopee_accounts(
+    app: tauri::AppHandle,
+    cache: tauri::State<'_, AccountsCacheState>,
+    email: String,
+    password: String,
+) -> Result<ShopeeAccountsResponse, CommandError> {
+    let verifier = accounts_cache::verifier(&email, &password);
+    // Once the backend has accepted these credentials this run, serve the local copy instantly;
+    // the background refresh emits cache-updated if it changed
+    if cache.is_verified(&email, &verifier) {
+        if let Some(cached) = accounts_cache::get(&app, &email, &verifier) {
+            accounts_cache::reconcile(&app, email, password, cached.clone());
+            return Ok(ShopeeAccountsResponse { data: cached });
+        }
+    }
+
+    match fetch_shopee_accounts(&email, &password).await {
+        Ok(response) => {
+            cache.mark_verified(&email, &verifier);
+            accounts_cache::store(&app, &email, &verifier, &response.data);
+            Ok(response)
+        }
+        // Offline, the last list these credentials fetched is better than nothing
+        Err(e) if accounts_cache::is_unreachable(&e) => accounts_cache::get(&app, &email, &verifier)
+            .map(|data| ShopeeAccountsResponse { data })
+            .ok_or(e),
+        Err(e) => Err(e),
+    }
+}
+
+async fn fetch_shopee_accounts(email: &str, password: &str) -> Result<ShopeeAccountsResponse, CommandError> {
+    let query = format!("email={}&password={}", urlencoding::encode(email), urlencoding::encode(password));
     let response: ApiResponse<ShopeeAccountsResponse> = make_api_request("GET", "/api/members/shopee-accounts", None, Some(&query)).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to get accounts".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to get accounts".to_string()).into());
     }
     
-    response.data.ok_or_else(|| "No data in response".to_string())
+    response.data.ok_or_else(|| "No data in response".into())
 }
 
 #[tauri::command]
-async fn add_shopee_account(email: String, password: String, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, String> {
+async fn add_shopee_account(app: tauri::AppHandle, email: String, password: String, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, CommandError> {
+    let proxy = account_proxy(&app, None);
+    let info = fetch_account_info(&cookie, proxy.as_deref()).await.map_err(|e| {
+        if e.transport {
+            format!("Could not reach Shopee to check the cookie: {}", e.message)
+        } else {
+            format!("Cookie is not valid: {}", e.message)
+        }
+    })?;
+    if let Some(existing_id) = account_identity::find_duplicate(&app, &email, &password, info.userid).await? {
+        return Err(CommandError::DuplicateAccount { existing_id });
+    }
+
+    let account = post_shopee_account(&app, email, password, name, cookie, is_active).await?;
+    account_identity::record(&app, account.id, info.userid);
+    audit::record(&app, "add_shopee_account", Some(format!("account:{}", account.id)), serde_json::json!({ "name": account.name }));
+    Ok(account)
+}
+
+async fn post_shopee_account(app: &tauri::AppHandle, email: String, password: String, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, CommandError> {
     let body = serde_json::json!({
         "email": email,
         "password": password,
@@ -493,8 +723,9 @@ async fn add_shopee_account(email: String, password: String, name: String, cooki
     let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/shopee-accounts", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to add account".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to add account".to_string()).into());
     }
+    accounts_cache::invalidate(app, &email);
     
     // Parse the data field
     let data = response.data.ok_or_else(|| "No data in response".to_string())?;
@@ -503,7 +734,29 @@ async fn add_shopee_account(email: String, password: String, name: String, cooki
 }
 
 #[tauri::command]
-async fn update_shopee_account(email: String, password: String, account_id: i32, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, String> {
+async fn update_shopee_account(app: tauri::AppHandle, email: String, password: String, account_id: i32, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, CommandError> {
+    // Keep the cookie being replaced so it can be rolled back if the new one is broken
+    let previous_cookie = find_shopee_account(&email, &password, account_id)
+        .await
+        .ok()
+        .and_then(|a| a.cookie)
+        .filter(|c| *c != cookie);
+
+    let account = put_shopee_account(&app, email, password, account_id, name, cookie, is_active).await?;
+    let cookie_changed = previous_cookie.is_some();
+    if let Some(previous_cookie) = previous_cookie {
+        cookie_history::record(&app, account_id, &previous_cookie);
+    }
+    audit::record(
+        &app,
+        "update_shopee_account",
+        Some(format!("account:{}", account_id)),
+        serde_json::json!({ "name": account.name, "is_active": account.is_active, "cookie_changed": cookie_changed }),
+    );
+    Ok(account)
+}
+
+async fn put_shopee_account(app: &tauri::AppHandle, email: String, password: String, account_id: i32, name: String, cookie: String, is_active: bool) -> Result<ShopeeAccount, CommandError> {
     let body = serde_json::json!({
         "email": email,
         "password": password,
@@ -515,8 +768,9 @@ async fn update_shopee_account(email: String, password: String, account_id: i32,
     let response: ApiResponse<serde_json::Value> = make_api_request("PUT", &format!("/api/members/shopee-accounts/{}", account_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to update account".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to update account".to_string()).into());
     }
+    accounts_cache::invalidate(app, &email);
     
     let data = response.data.ok_or_else(|| "No data in response".to_string())?;
     let account: ShopeeAccount = serde_json::from_value(data["shopee_account"].clone()).map_err(|e| format!("Failed to parse account: {}", e))?;
@@ -524,113 +778,241 @@ async fn update_shopee_account(email: String, password: String, account_id: i32,
 }
 
 #[tauri::command]
-async fn delete_shopee_account(email: String, password: String, account_id: i32) -> Result<(), String> {
-    let body = serde_json::json!({
-        "email": email,
-        "password": password
-    });
-    
-    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/shopee-accounts/{}", account_id), Some(&body), None).await?;
-    
-    if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to delete account".to_string()));
+async fn get_cookie_history(app: tauri::AppHandle, account_id: i32) -> Result<Vec<cookie_history::CookieVersionSummary>, String> {
+    cookie_history::list(&app, account_id)
+}
+
+// Re-uploads the most recently replaced cookie for the account
+#[tauri::command]
+async fn rollback_account_cookie(app: tauri::AppHandle, email: String, password: String, account_id: i32) -> Result<ShopeeAccount, CommandError> {
+    let account = find_shopee_account(&email, &password, account_id).await?;
+    let version = cookie_history::pop_latest(&app, account_id)?
+        .ok_or_else(|| CommandError::from("No previous cookie to roll back to"))?;
+
+    match put_shopee_account(&app, email, password, account_id, account.name, version.cookie.clone(), account.is_active).await {
+        Ok(account) => {
+            println!("[COOKIE HISTORY] Rolled account {} back to cookie replaced at {}", account_id, version.replaced_at);
+            audit::record(
+                &app,
+                "rollback_account_cookie",
+                Some(format!("account:{}", account_id)),
+                serde_json::json!({ "replaced_at": version.replaced_at }),
+            );
+            Ok(account)
+        }
+        Err(e) => {
+            cookie_history::restore(&app, account_id, version);
+            Err(e)
+        }
     }
-    
-    Ok(())
 }
 
 #[tauri::command]
-async fn get_niches(email: String, password: String) -> Result<NichesResponse, String> {
+async fn delete_shopee_account(
+    app: tauri::AppHandle,
+    confirmations: tauri::State<'_, ConfirmationState>,
+    email: String,
+    password: String,
+    account_id: i32,
+    archive: Option<bool>,
+    confirmation_token: Option<String>,
+) -> Result<DestructiveOutcome, CommandError> {
+    let archive = archive.unwrap_or(false);
+    let action = format!("delete_shopee_account:{}", account_id);
+    let Some(token) = confirmation_token else {
+        let account = find_shopee_account(&email, &password, account_id).await?;
+        let summary = if archive {
+            format!("Shopee account \"{}\" will be removed and kept in the local archive", account.name)
+        } else {
+            format!("Shopee account \"{}\" will be deleted", account.name)
+        };
+        return Ok(confirmations.issue(&action, Impact {
+            summary,
+            product_sets: 0,
+            items: 0,
+        }));
+    };
+    confirmations.consume(&token, &action)?;
+    
+    // Archive before deleting so a failed snapshot never loses the account
+    if archive {
+        let account = find_shopee_account(&email, &password, account_id).await?;
+        account_archive::archive(&app, &account)?;
+    }
+    
     let body = serde_json::json!({
         "email": email,
         "password": password
     });
     
-    let response: ApiResponse<NichesResponse> = make_api_request("GET", "/api/members/niches", Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/shopee-accounts/{}", account_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to get niches".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to delete account".to_string()).into());
+    }
+    accounts_cache::invalidate(&app, &email);
+    
+    if let Err(e) = account_notes::remove(&app, account_id) {
+        eprintln!("Failed to remove notes for account {}: {}", account_id, e);
+    }
+    if let Err(e) = annotations::remove(&app, AnnotationTarget::Account, account_id.into()) {
+        eprintln!("Failed to remove annotation for account {}: {}", account_id, e);
+    }
+    if let Err(e) = cookie_history::remove(&app, account_id) {
+        eprintln!("Failed to remove cookie history for account {}: {}", account_id, e);
     }
+    if let Err(e) = cookie_health::remove(&app, account_id) {
+        eprintln!("Failed to remove cookie health for account {}: {}", account_id, e);
+    }
+    if let Err(e) = account_tags::remove(&app, account_id) {
+        eprintln!("Failed to remove tags for account {}: {}", account_id, e);
+    }
+    if let Err(e) = account_proxies::remove(&app, account_id) {
+        eprintln!("Failed to remove proxy for account {}: {}", account_id, e);
+    }
+    audit::record(&app, "delete_shopee_account", Some(format!("account:{}", account_id)), serde_json::json!({ "archived": archive }));
     
-    response.data.ok_or_else(|| "No data in response".to_string())
+    Ok(DestructiveOutcome::Done)
 }
 
 #[tauri::command]
-async fn create_niche(email: String, password: String, name: String, description: Option<String>) -> Result<Niche, String> {
-    let mut body = serde_json::json!({
-        "email": email,
-        "password": password,
-        "name": name
-    });
-    if let Some(desc) = description {
-        body["description"] = serde_json::Value::String(desc);
+async fn list_archived_accounts(app: tauri::AppHandle) -> Result<Vec<account_archive::ArchivedAccountSummary>, String> {
+    account_archive::list(&app)
+}
+
+// Re-uploads an archived account. It goes through add_shopee_account, so the cookie is
+// validated and an account that was added again in the meantime is rejected. It comes back
+// with a new ID, so its tags and notes are re-attached to that ID.
+#[tauri::command]
+async fn restore_account(app: tauri::AppHandle, email: String, password: String, archive_id: String) -> Result<ShopeeAccount, CommandError> {
+    let entry = account_archive::get(&app, &archive_id)?
+        .ok_or_else(|| CommandError::from("Archived account not found"))?;
+    let cookie = entry
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("Archived account has no cookie"))?;
+
+    let account = add_shopee_account(app.clone(), email, password, entry.name, cookie, entry.is_active).await?;
+
+    if !entry.tags.is_empty() {
+        if let Err(e) = account_tags::set(&app, account.id, entry.tags) {
+            eprintln!("Failed to restore tags for account {}: {}", account.id, e);
+        }
     }
-    
-    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/niches", Some(&body), None).await?;
-    
-    if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to create niche".to_string()));
+    if let Some(notes) = entry.notes {
+        if let Err(e) = account_notes::set(&app, account.id, notes) {
+            eprintln!("Failed to restore notes for account {}: {}", account.id, e);
+        }
     }
-    
-    let data = response.data.ok_or_else(|| "No data in response".to_string())?;
-    let niche: Niche = serde_json::from_value(data["niche"].clone()).map_err(|e| format!("Failed to parse niche: {}", e))?;
-    Ok(niche)
+    account_archive::remove(&app, &archive_id)?;
+    audit::record(&app, "restore_account", Some(format!("account:{}", account.id)), serde_json::json!({ "archive_id": archive_id }));
+    Ok(account)
 }
 
 #[tauri::command]
-async fn update_niche(email: String, password: String, niche_id: i32, name: String, description: Option<String>) -> Result<(), String> {
-    let mut body = serde_json::json!({
-        "email": email,
-        "password": password,
-        "name": name
-    });
-    if let Some(desc) = description {
-        body["description"] = serde_json::Value::String(desc);
-    }
-    
-    let response: ApiResponse<serde_json::Value> = make_api_request("PUT", &format!("/api/members/niches/{}", niche_id), Some(&body), None).await?;
-    
-    if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to update niche".to_string()));
+async fn import_shopee_accounts(app: tauri::AppHandle, email: String, password: String, path: String) -> Result<ImportSummary, CommandError> {
+    let accounts = account_import::parse_file(std::path::Path::new(&path))?;
+    if accounts.is_empty() {
+        return Err("No accounts found in file".into());
     }
-    
-    Ok(())
+    let summary = account_import::import(&app, email, password, accounts).await?;
+    audit::record(&app, "import_shopee_accounts", None, serde_json::json!({ "path": path, "summary": summary }));
+    Ok(summary)
 }
 
 #[tauri::command]
-async fn delete_niche(email: String, password: String, niche_id: i32) -> Result<(), String> {
-    let body = serde_json::json!({
-        "email": email,
-        "password": password
-    });
-    
-    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/niches/{}", niche_id), Some(&body), None).await?;
-    
-    if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to delete niche".to_string()));
-    }
-    
-    Ok(())
+async fn export_shopee_accounts(app: tauri::AppHandle, email: String, password: String, path: String) -> Result<usize, CommandError> {
+    let accounts = fetch_shopee_accounts(&email, &password).await?;
+    Ok(account_export::export(&app, accounts.data, std::path::Path::new(&path))?)
+}
+
+// Local account tags
+#[tauri::command]
+async fn set_account_tags(app: tauri::AppHandle, account_id: i32, tags: Vec<String>) -> Result<Vec<String>, String> {
+    account_tags::set(&app, account_id, tags)
+}
+
+#[tauri::command]
+async fn get_account_tags(app: tauri::AppHandle) -> Result<std::collections::HashMap<i32, Vec<String>>, String> {
+    Ok(account_tags::all(&app)?
+        .into_iter()
+        .map(|(id, tags)| (id, tags.into_iter().collect()))
+        .collect())
+}
+
+#[tauri::command]
+async fn get_accounts_by_tag(app: tauri::AppHandle, tag: String) -> Result<Vec<i32>, String> {
+    account_tags::accounts_with_tag(&app, &tag)
+}
+
+// Price formatting. Raw Shopee prices are formatted here so every surface shows the same values.
+#[tauri::command]
+async fn format_prices(region: Option<String>, prices: Vec<price_format::RawPrice>) -> Vec<price_format::FormattedPrice> {
+    let region = region.unwrap_or_else(|| settings::current().region);
+    prices.iter().map(|p| price_format::FormattedPrice::new(p, &region)).collect()
+}
+
+// Per-account proxies
+#[tauri::command]
+async fn set_account_proxy(app: tauri::AppHandle, account_id: i32, proxy_url: Option<String>) -> Result<(), String> {
+    account_proxies::set(&app, account_id, proxy_url)
+}
+
+#[tauri::command]
+async fn get_account_proxies(app: tauri::AppHandle) -> Result<std::collections::HashMap<i32, String>, String> {
+    account_proxies::all(&app)
+}
+
+// Local account notes
+#[tauri::command]
+async fn get_account_notes(app: tauri::AppHandle, account_id: i32) -> Result<Option<AccountNotes>, String> {
+    account_notes::get(&app, account_id)
 }
 
 #[tauri::command]
-async fn get_product_sets(email: String, password: String) -> Result<ProductSetsResponse, String> {
+async fn set_account_notes(app: tauri::AppHandle, account_id: i32, notes: AccountNotes) -> Result<AccountNotes, String> {
+    account_notes::set(&app, account_id, notes)
+}
+
+// Local labels, notes and custom fields on accounts and product sets
+#[tauri::command]
+async fn get_annotation(app: tauri::AppHandle, target: AnnotationTarget, target_id: i64) -> Result<Option<Annotation>, String> {
+    annotations::get(&app, target, target_id)
+}
+
+#[tauri::command]
+async fn list_annotations(app: tauri::AppHandle, target: AnnotationTarget) -> Result<Vec<AnnotationEntry>, String> {
+    annotations::list(&app, target)
+}
+
+#[tauri::command]
+async fn set_annotation(app: tauri::AppHandle, target: AnnotationTarget, target_id: i64, annotation: Annotation) -> Result<Annotation, String> {
+    annotations::set(&app, target, target_id, annotation)
+}
+
+#[tauri::command]
+async fn delete_annotation(app: tauri::AppHandle, target: AnnotationTarget, target_id: i64) -> Result<(), String> {
+    annotations::remove(&app, target, target_id)
+}
+
+#[tauri::command]
+async fn get_niches(email: String, password: String) -> Result<NichesResponse, CommandError> {
     let body = serde_json::json!({
         "email": email,
         "password": password
     });
     
-    let response: ApiResponse<ProductSetsResponse> = make_api_request("GET", "/api/members/product-sets", Some(&body), None).await?;
+    let response: ApiResponse<NichesResponse> = make_api_request("GET", "/api/members/niches", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to get product sets".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to get niches".to_string()).into());
     }
     
-    response.data.ok_or_else(|| "No data in response".to_string())
+    response.data.ok_or_else(|| "No data in response".into())
 }
 
 #[tauri::command]
-async fn create_product_set(email: String, password: String, name: String, description: Option<String>, niche_id: Option<i32>) -> Result<ProductSet, String> {
+async fn create_niche(app: tauri::AppHandle, email: String, password: String, name: String, description: Option<String>) -> Result<Niche, CommandError> {
     let mut body = serde_json::json!({
         "email": email,
         "password": password,
@@ -639,23 +1021,21 @@ async fn create_product_set(email: String, password: String, name: String, descr
     if let Some(desc) = description {
         body["description"] = serde_json::Value::String(desc);
     }
-    if let Some(nid) = niche_id {
-        body["niche_id"] = serde_json::Value::Number(nid.into());
-    }
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/product-sets", Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/niches", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to create product set".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to create niche".to_string()).into());
     }
     
     let data = response.data.ok_or_else(|| "No data in response".to_string())?;
-    let product_set: ProductSet = serde_json::from_value(data["product_set"].clone()).map_err(|e| format!("Failed to parse product set: {}", e))?;
-    Ok(product_set)
+    let niche: Niche = serde_json::from_value(data["niche"].clone()).map_err(|e| format!("Failed to parse niche: {}", e))?;
+    audit::record(&app, "create_niche", Some(format!("niche:{}", niche.id)), serde_json::json!({ "name": niche.name }));
+    Ok(niche)
 }
 
 #[tauri::command]
-async fn update_product_set(email: String, password: String, product_set_id: i32, name: String, description: Option<String>, niche_id: Option<i32>) -> Result<(), String> {
+async fn update_niche(app: tauri::AppHandle, email: String, password: String, niche_id: i32, name: String, description: Option<String>) -> Result<(), CommandError> {
     let mut body = serde_json::json!({
         "email": email,
         "password": password,
@@ -664,336 +1044,2800 @@ async fn update_product_set(email: String, password: String, product_set_id: i32
     if let Some(desc) = description {
         body["description"] = serde_json::Value::String(desc);
     }
-    if let Some(nid) = niche_id {
-        body["niche_id"] = serde_json::Value::Number(nid.into());
-    }
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("PUT", &format!("/api/members/product-sets/{}", product_set_id), Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("PUT", &format!("/api/members/niches/{}", niche_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to update product set".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to update niche".to_string()).into());
     }
     
+    audit::record(&app, "update_niche", Some(format!("niche:{}", niche_id)), serde_json::json!({ "name": name }));
     Ok(())
 }
 
 #[tauri::command]
-async fn delete_product_set(email: String, password: String, product_set_id: i32) -> Result<(), String> {
+async fn delete_niche(
+    app: tauri::AppHandle,
+    confirmations: tauri::State<'_, ConfirmationState>,
+    email: String,
+    password: String,
+    niche_id: i32,
+    confirmation_token: Option<String>,
+) -> Result<DestructiveOutcome, CommandError> {
+    let action = format!("delete_niche:{}", niche_id);
+    let Some(token) = confirmation_token else {
+        let product_sets = get_product_sets(email.clone(), password.clone()).await?.product_sets;
+        let affected: Vec<&ProductSet> = product_sets.iter().filter(|p| p.niche_id == Some(niche_id)).collect();
+        let items = affected.iter().map(|p| p.items.len()).sum();
+        return Ok(confirmations.issue(&action, Impact {
+            summary: format!("Niche will be deleted along with its link to {} product set(s)", affected.len()),
+            product_sets: affected.len(),
+            items,
+        }));
+    };
+    confirmations.consume(&token, &action)?;
+    
     let body = serde_json::json!({
         "email": email,
         "password": password
     });
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}", product_set_id), Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/niches/{}", niche_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to delete product set".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to delete niche".to_string()).into());
     }
     
-    Ok(())
+    audit::record(&app, "delete_niche", Some(format!("niche:{}", niche_id)), serde_json::Value::Null);
+    Ok(DestructiveOutcome::Done)
 }
 
 #[tauri::command]
-async fn add_product_set_items(email: String, password: String, product_set_id: i32, items: Vec<serde_json::Value>) -> Result<serde_json::Value, String> {
+async fn get_product_sets(email: String, password: String) -> Result<ProductSetsResponse, CommandError> {
     let body = serde_json::json!({
         "email": email,
-        "password": password,
-        "items": items
+        "password": password
     });
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("POST", &format!("/api/members/product-sets/{}/items", product_set_id), Some(&body), None).await?;
+    let response: ApiResponse<ProductSetsResponse> = make_api_request("GET", "/api/members/product-sets", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to add items".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to get product sets".to_string()).into());
     }
     
-    Ok(response.data.unwrap_or_else(|| serde_json::json!({})))
+    response.data.ok_or_else(|| "No data in response".into())
 }
 
+// Bulk variants of the list commands return a raw IPC body (JSON or MessagePack)
 #[tauri::command]
-async fn delete_product_set_item(email: String, password: String, product_set_id: i32, item_id: i32) -> Result<(), String> {
-    let body = serde_json::json!({
-        "email": email,
-        "password": password
-    });
-    
-    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}/items/{}", product_set_id, item_id), Some(&body), None).await?;
-    
-    if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to delete item".to_string()));
+async fn get_ipc_encodings() -> Result<Vec<String>, String> {
+    Ok(ipc_encoding::SUPPORTED_ENCODINGS.iter().map(|e| e.to_string()).collect())
+}
+
+#[tauri::command]
+async fn get_niches_bulk(email: String, password: String, encoding: Option<String>) -> Result<tauri::ipc::Response, CommandError> {
+    let encoding = IpcEncoding::parse(encoding.as_deref())?;
+    let niches = get_niches(email, password).await?;
+    Ok(ipc_encoding::encode(&niches, encoding)?)
+}
+
+#[tauri::command]
+async fn get_product_sets_bulk(email: String, password: String, encoding: Option<String>) -> Result<tauri::ipc::Response, CommandError> {
+    let encoding = IpcEncoding::parse(encoding.as_deref())?;
+    let product_sets = get_product_sets(email, password).await?;
+    Ok(ipc_encoding::encode(&product_sets, encoding)?)
+}
+
+async fn find_product_set(email: &str, password: &str, product_set_id: i32) -> Result<ProductSet, CommandError> {
+    let product_sets = get_product_sets(email.to_string(), password.to_string()).await?;
+    product_sets
+        .product_sets
+        .into_iter()
+        .find(|p| p.id == product_set_id)
+        .ok_or_else(|| format!("Product set {} not found", product_set_id).into())
+}
+
+async fn ensure_set_writable(email: &str, password: &str, product_set_id: i32) -> Result<(), CommandError> {
+    if find_product_set(email, password, product_set_id).await?.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id });
     }
-    
     Ok(())
 }
 
 #[tauri::command]
-async fn clear_product_set_items(email: String, password: String, product_set_id: i32) -> Result<(), String> {
-    let body = serde_json::json!({
+async fn create_product_set(app: tauri::AppHandle, email: String, password: String, name: String, description: Option<String>, niche_id: Option<i32>) -> Result<ProductSet, CommandError> {
+    let mut body = serde_json::json!({
         "email": email,
-        "password": password
+        "password": password,
+        "name": name
     });
+    if let Some(desc) = description {
+        body["description"] = serde_json::Value::String(desc);
+    }
+    if let Some(nid) = niche_id {
+        body["niche_id"] = serde_json::Value::Number(nid.into());
+    }
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}/items", product_set_id), Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/members/product-sets", Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to clear items".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to create product set".to_string()).into());
     }
     
-    Ok(())
+    let data = response.data.ok_or_else(|| "No data in response".to_string())?;
+    let product_set: ProductSet = serde_json::from_value(data["product_set"].clone()).map_err(|e| format!("Failed to parse product set: {}", e))?;
+    audit::record(
+        &app,
+        "create_product_set",
+        Some(format!("product_set:{}", product_set.id)),
+        serde_json::json!({ "name": product_set.name, "niche_id": product_set.niche_id }),
+    );
+    Ok(product_set)
 }
 
 #[tauri::command]
-async fn get_session_ids(email: String, password: String, shopee_account_id: i32) -> Result<SessionIdsResponse, String> {
-    let body = serde_json::json!({
+async fn update_product_set(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, name: String, description: Option<String>, niche_id: Option<i32>) -> Result<(), CommandError> {
+    ensure_set_writable(&email, &password, product_set_id).await?;
+    
+    let mut body = serde_json::json!({
         "email": email,
         "password": password,
-        "shopee_account_id": shopee_account_id
+        "name": name
     });
+    if let Some(desc) = description {
+        body["description"] = serde_json::Value::String(desc);
+    }
+    if let Some(nid) = niche_id {
+        body["niche_id"] = serde_json::Value::Number(nid.into());
+    }
     
-    // Use new active-session endpoint which returns only one active session or null
-    let response: ActiveSessionApiResponse = make_api_request("POST", "/api/shopee-live/active-session", Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("PUT", &format!("/api/members/product-sets/{}", product_set_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to get active session".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to update product set".to_string()).into());
     }
     
-    // Convert Option<String> to Vec<String> for compatibility with frontend
-    let session_ids = match response.session_id {
-        Some(sid) => vec![sid],
-        None => vec![],
-    };
-    
-    Ok(SessionIdsResponse {
-        session_ids,
-    })
+    audit::record(
+        &app,
+        "update_product_set",
+        Some(format!("product_set:{}", product_set_id)),
+        serde_json::json!({ "name": name, "niche_id": niche_id }),
+    );
+    Ok(())
 }
 
 #[tauri::command]
-async fn replace_products(email: String, password: String, shopee_account_id: i32, session_id: String, product_set_id: i32) -> Result<serde_json::Value, String> {
+async fn delete_product_set(app: tauri::AppHandle, email: String, password: String, product_set_id: i32) -> Result<(), CommandError> {
+    ensure_set_writable(&email, &password, product_set_id).await?;
+    
     let body = serde_json::json!({
         "email": email,
-        "password": password,
-        "shopee_account_id": shopee_account_id,
-        "session_id": session_id,
-        "product_set_id": product_set_id
+        "password": password
     });
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/shopee-live/replace-products", Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}", product_set_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to replace products".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to delete product set".to_string()).into());
     }
     
-    Ok(response.data.unwrap_or_else(|| serde_json::json!({})))
+    if let Err(e) = annotations::remove(&app, AnnotationTarget::ProductSet, product_set_id.into()) {
+        eprintln!("Failed to remove annotation for product set {}: {}", product_set_id, e);
+    }
+    audit::record(&app, "delete_product_set", Some(format!("product_set:{}", product_set_id)), serde_json::Value::Null);
+    Ok(())
 }
 
 #[tauri::command]
-async fn clear_products(email: String, password: String, shopee_account_id: i32, session_id: String) -> Result<(), String> {
+async fn add_product_set_items(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, items: Vec<ProductSetItemInput>) -> Result<AddItemsSummary, CommandError> {
+    let items = product_items::resolve_all(items).await?;
+    let product_set = find_product_set(&email, &password, product_set_id).await?;
+    if product_set.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id });
+    }
+    
+    // Resolved inputs always carry both IDs, so they're compared on those
+    let mut existing: std::collections::HashSet<(Option<i64>, Option<i64>)> =
+        product_set.items.iter().map(|item| (item.shop_id, item.item_id)).collect();
+    let (items, skipped): (Vec<_>, Vec<_>) = items.into_iter().partition(|item| existing.insert((item.shop_id, item.item_id)));
+    if items.is_empty() {
+        return Ok(AddItemsSummary { added: 0, skipped });
+    }
+    
     let body = serde_json::json!({
         "email": email,
         "password": password,
-        "shopee_account_id": shopee_account_id,
-        "session_id": session_id
+        "items": items
     });
     
-    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/shopee-live/clear-products", Some(&body), None).await?;
+    let response: ApiResponse<serde_json::Value> = make_api_request("POST", &format!("/api/members/product-sets/{}/items", product_set_id), Some(&body), None).await?;
     
     if !response.success {
-        return Err(response.message.unwrap_or_else(|| "Failed to clear products".to_string()));
+        return Err(response.message.unwrap_or_else(|| "Failed to add items".to_string()).into());
     }
     
-    Ok(())
+    live_sync::notify_set_changed(&app, product_set_id);
+    audit::record(
+        &app,
+        "add_product_set_items",
+        Some(format!("product_set:{}", product_set_id)),
+        serde_json::json!({ "added": items.len(), "skipped": skipped.len() }),
+    );
+    
+    Ok(AddItemsSummary { added: items.len(), skipped })
 }
 
-// QR Code commands
 #[tauri::command]
-async fn generate_shopee_qr() -> Result<ShopeeQRData, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create client: {}", e))?;
-    
-    let response = client
-        .get("https://shopee.co.id/api/v2/authentication/gen_qrcode")
-        .header("Accept", "application/json, text/plain")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Origin", "https://shopee.co.id")
-        .header("Referer", "https://shopee.co.id/")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
-    
-    if !status.is_success() {
-        return Err(format!("HTTP {}: {}", status, text));
+async fn import_product_set_items(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, path: String) -> Result<product_import::ItemImportSummary, CommandError> {
+    let items = product_import::parse_file(std::path::Path::new(&path))?;
+    if items.is_empty() {
+        return Err("No product URLs found in file".into());
     }
-    
-    let qr_response: ShopeeQRResponse = serde_json::from_str(&text)
-        .map_err(|e| format!("Failed to parse response: {} - Response: {}", e, text))?;
-    
-    if qr_response.error != 0 {
-        return Err(format!("Shopee API error: {} - {}", 
-            qr_response.error, 
-            qr_response.error_msg.unwrap_or("Unknown error".to_string())));
+    let job_id = jobs::begin(&app, "product_import", format!("Import {} rows into set {}", items.len(), product_set_id), None);
+    let result = product_import::import(&app, &job_id, &email, &password, product_set_id, items).await;
+    jobs::finish(&app, &job_id, &result);
+    result
+}
+
+// Dead-link check before a set goes into a live showcase. The account's cookie and proxy
+// are used when given, since Shopee's item API often needs a logged-in session.
+#[tauri::command]
+async fn validate_product_set(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    account_id: Option<i32>,
+) -> Result<product_validation::ValidationReport, CommandError> {
+    let set = find_product_set(&email, &password, product_set_id).await?;
+    let cookie = match account_id {
+        Some(id) => find_shopee_account(&email, &password, id).await?.cookie.filter(|c| !c.is_empty()),
+        None => None,
+    };
+    product_validation::validate(product_set_id, set.items, cookie, account_proxy(&app, account_id)).await
+}
+
+// Product cards (title, price, stock, rating, image) for the set editor, cached locally
+#[tauri::command]
+async fn get_product_set_metadata(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    account_id: Option<i32>,
+    refresh: Option<bool>,
+) -> Result<Vec<product_metadata::ProductCard>, CommandError> {
+    let set = find_product_set(&email, &password, product_set_id).await?;
+    let cookie = match account_id {
+        Some(id) => find_shopee_account(&email, &password, id).await?.cookie.filter(|c| !c.is_empty()),
+        None => None,
+    };
+    Ok(product_metadata::enrich(&app, set.items, cookie, account_proxy(&app, account_id), refresh.unwrap_or(false)).await?)
+}
+
+// Product thumbnail as a data URL, downloaded once from the item's cached metadata.
+// None when the item's metadata (and so its image) hasn't been fetched yet.
+#[tauri::command]
+async fn get_cached_image(app: tauri::AppHandle, item_id: i64) -> Result<Option<String>, String> {
+    let key = image_cache::thumbnail_key(item_id);
+    if let Some(cached) = image_cache::get(&app, &key)? {
+        return Ok(Some(cached));
     }
-    
-    qr_response.data.ok_or_else(|| "Invalid response from Shopee API".to_string())
+    let Some(url) = product_metadata::image_url(&app, item_id) else {
+        return Ok(None);
+    };
+    image_cache::fetch(&app, &key, &format!("{}_tn", url)).await.map(Some)
 }
 
 #[tauri::command]
-async fn check_qr_status(qrcode_id: String) -> Result<AppQRStatus, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create client: {}", e))?;
-    
-    let url = format!("https://shopee.co.id/api/v2/authentication/qrcode_status?qrcode_id={}", 
-                     urlencoding::encode(&qrcode_id));
-    
-    let response = client
-        .get(&url)
-        .header("Accept", "application/json, text/plain")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Origin", "https://shopee.co.id")
-        .header("Referer", "https://shopee.co.id/")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
-    
-    if !status.is_success() {
-        return Err(format!("HTTP {}: {}", status, text));
+async fn get_cached_qr_image(app: tauri::AppHandle, qrcode_id: String) -> Result<Option<String>, String> {
+    image_cache::get(&app, &image_cache::qr_key(&qrcode_id))
+}
+
+#[tauri::command]
+async fn clear_image_cache(app: tauri::AppHandle) -> Result<(), String> {
+    image_cache::clear(&app)
+}
+
+// Saves the showcase order server-side, or locally when the backend doesn't support it
+#[tauri::command]
+async fn reorder_product_set_items(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, ordered_item_ids: Vec<i32>) -> Result<Vec<i32>, CommandError> {
+    let set = find_product_set(&email, &password, product_set_id).await?;
+    if set.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id });
     }
+    let current: Vec<i32> = set.items.iter().map(|item| item.id).collect();
+    let order = item_order::normalize(&current, &ordered_item_ids)?;
     
-    let status_response: ShopeeQRStatusResponse = serde_json::from_str(&text)
-        .map_err(|e| format!("Failed to parse response: {} - Response: {}", e, text))?;
-    
-    if status_response.error != 0 {
-        return Err(format!("Shopee API error: {} - {}",
-            status_response.error,
-            status_response.error_msg.unwrap_or("Unknown error".to_string())));
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "item_ids": order
+    });
+    let endpoint = format!("/api/members/product-sets/{}/items/order", product_set_id);
+    match make_api_request::<ApiResponse<serde_json::Value>>("PUT", &endpoint, Some(&body), None).await {
+        Ok(response) if response.success => item_order::clear(&app, product_set_id)?,
+        Ok(response) => return Err(response.message.unwrap_or_else(|| "Failed to reorder items".to_string()).into()),
+        // The backend has no order endpoint, so the order is kept locally
+        Err(CommandError::Http { status: 404 | 405, .. }) => {
+            item_order::set(&app, product_set_id, order.clone())?;
+        }
+        Err(e) => return Err(e),
     }
     
-    let data = status_response.data.ok_or_else(|| "No data in response".to_string())?;
-    
-    Ok(AppQRStatus {
-        status: data.status,
-        qrcode_token: if data.qrcode_token.is_empty() { None } else { Some(data.qrcode_token) },
-    })
+    live_sync::notify_set_changed(&app, product_set_id);
+    audit::record(&app, "reorder_product_set_items", Some(format!("product_set:{}", product_set_id)), serde_json::json!({ "order": order }));
+    Ok(order)
 }
 
+// Local toggle: a disabled item is kept in the set but skipped by replace_products
 #[tauri::command]
-async fn qr_login(qrcode_token: String) -> Result<LoginResult, String> {
-    let device_sz_fingerprint = "Eci2goR2Eb+MxmnU3gKNBQ==|U4oBUb+lXscV+6i8liMV/0lL2YjLYCw6ZgvAg3AVpmc=|WYw++VlzfflxOp1j|08|3".to_string();
-    let security_device_fingerprint = "vRr1CLNxsx/YWsLqNCAeGQ==|3UI1dXTNSZRQkHYpKyn3MGV94+BUZv/37sidjlGODXY=|77wWZwahX4xYgzK9BHP57A==".to_string();
+async fn set_product_set_item_enabled(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    item_id: i32,
+    enabled: bool,
+) -> Result<Vec<i32>, CommandError> {
+    let set = find_product_set(&email, &password, product_set_id).await?;
+    if !set.items.iter().any(|item| item.id == item_id) {
+        return Err(format!("Item {} is not in this product set", item_id).into());
+    }
+    let disabled = disabled_items::set_enabled(&app, product_set_id, item_id, enabled)?;
+    live_sync::notify_set_changed(&app, product_set_id);
+    audit::record(
+        &app,
+        "set_product_set_item_enabled",
+        Some(format!("product_set:{}", product_set_id)),
+        serde_json::json!({ "item_id": item_id, "enabled": enabled }),
+    );
+    Ok(disabled)
+}
 
-    let payload = QRCodeLoginRequest {
-        qrcode_token,
-        device_sz_fingerprint,
-        client_identifier: ClientIdentifier {
-            security_device_fingerprint,
-        },
-    };
+#[tauri::command]
+async fn get_disabled_product_set_items(app: tauri::AppHandle, product_set_id: i32) -> Result<Vec<i32>, String> {
+    Ok(disabled_items::get(&app, product_set_id).into_iter().collect())
+}
 
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create client: {}", e))?;
-
-    let response = client
-        .post("https://shopee.co.id/api/v2/authentication/qrcode_login")
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .header("X-Sz-Sdk-Version", "3.3.0-2&1.6.6")
-        .header("X-Api-Source", "pc")
-        .header("X-Shopee-Language", "id")
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("Af-Ac-Enc-Sz-Token", "LKhci5u+IZWG5pLadxISkw==|KnTeDESKZrvJIH7v/k87MkjZgllq1OIb4WNTbBMjqiX47UKmLiYT/5gQveB5AcnnWrX7QOH0K22Cyg==|WYw++VlzfflxOp1j|08|3")
-        .header("Sec-Ch-Ua-Platform", "\"macOS\"")
-        .header("Origin", "https://shopee.co.id")
-        .header("Referer", "https://shopee.co.id/buyer/login/qr?next=https%3A%2F%2Fshopee.co.id%2F")
-        .json(&payload)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+#[tauri::command]
+async fn get_product_set_item_order(app: tauri::AppHandle, product_set_id: i32) -> Result<Option<Vec<i32>>, String> {
+    Ok(item_order::get(&app, product_set_id))
+}
 
-    let status = response.status();
-    
-    let set_cookie_headers: Vec<String> = response
-        .headers()
-        .get_all("set-cookie")
-        .iter()
-        .filter_map(|v| v.to_str().ok().map(|s| s.to_string()))
-        .collect();
+#[tauri::command]
+async fn import_shop_products(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    account_id: i32,
+    shop_url: String,
+    product_set_id: i32,
+    limit: Option<usize>,
+) -> Result<shop_import::ShopImportSummary, CommandError> {
+    ensure_set_writable(&email, &password, product_set_id).await?;
+    let cookie = find_shopee_account(&email, &password, account_id).await?.cookie.filter(|c| !c.is_empty());
+    let client = shopee_api_client(account_proxies::proxy_for(&app, account_id).as_deref())?;
+    let shop_id = shop_import::shop_id_from_url(&client, &shop_url).await?;
+    // Sets are capped at 100 items
+    let limit = limit.unwrap_or(100).clamp(1, 100);
+    let job_id = jobs::begin(&app, "shop_import", format!("Import shop {} into set {}", shop_id, product_set_id), Some(account_id));
+    let result = shop_import::import(&app, &job_id, &email, &password, &client, cookie.as_deref(), shop_id, product_set_id, limit).await;
+    jobs::finish(&app, &job_id, &result);
+    result
+}
 
-    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
-    
-    if !status.is_success() {
-        return Ok(LoginResult {
-            success: false,
-            cookies: None,
-            error_msg: Some(format!("HTTP {}: {}", status, text)),
-        });
+// Keyword discovery for building niche sets; the account's cookie and proxy are used when given
+#[tauri::command]
+async fn search_shopee_products(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    account_id: Option<i32>,
+    keyword: String,
+    filters: Option<shopee::SearchFilters>,
+) -> Result<Vec<product_search::ProductCandidate>, CommandError> {
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return Err("Keyword is required".into());
     }
-    
-    let login_response: QRCodeLoginResponse = serde_json::from_str(&text)
-        .map_err(|e| format!("Failed to parse response: {} - Response: {}", e, text))?;
-    
-    if login_response.error != 0 {
-        return Ok(LoginResult {
-            success: false,
-            cookies: None,
-            error_msg: login_response.error_msg,
-        });
+    let cookie = match account_id {
+        Some(id) => find_shopee_account(&email, &password, id).await?.cookie.filter(|c| !c.is_empty()),
+        None => None,
+    };
+    let client = shopee_api_client(account_proxy(&app, account_id).as_deref())?;
+    let items = client
+        .search_items(keyword, &filters.unwrap_or_default(), cookie.as_deref())
+        .await
+        .map_err(String::from)?;
+    Ok(items.into_iter().map(product_search::ProductCandidate::from).collect())
+}
+
+#[tauri::command]
+async fn add_search_results_to_set(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    products: Vec<product_search::SelectedProduct>,
+) -> Result<AddItemsSummary, CommandError> {
+    let items: Vec<ProductSetItemInput> = products.iter().map(ProductSetItemInput::from).collect();
+    let mut summary = AddItemsSummary::default();
+    for chunk in items.chunks(product_items::UPLOAD_CHUNK_SIZE) {
+        let result = add_product_set_items(app.clone(), email.clone(), password.clone(), product_set_id, chunk.to_vec()).await?;
+        summary.added += result.added;
+        summary.skipped.extend(result.skipped);
     }
-    
-    let cookies = set_cookie_headers.join("; ");
-    
-    Ok(LoginResult {
-        success: true,
-        cookies: Some(cookies),
-        error_msg: None,
-    })
+    Ok(summary)
 }
 
 #[tauri::command]
-async fn get_account_info(cookies: String) -> Result<ShopeeAccountInfo, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
-        .build()
-        .map_err(|e| format!("Failed to create client: {}", e))?;
-    
-    let response = client
-        .get("https://shopee.co.id/api/v4/account/basic/get_account_info")
-        .header("Cookie", cookies)
-        .header("Accept", "application/json")
-        .header("Origin", "https://shopee.co.id")
-        .header("Referer", "https://shopee.co.id/")
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-    
-    let status = response.status();
-    let text = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
-    
-    if !status.is_success() {
-        return Err(format!("HTTP {}: {}", status, text));
+async fn parse_shopee_url(url: String) -> Result<shopee_url::ParsedShopeeUrl, String> {
+    shopee_url::parse(&url).await
+}
+
+// Copy a read-only (admin-curated) set into a personal set the member can edit
+#[tauri::command]
+async fn fork_read_only_set(app: tauri::AppHandle, email: String, password: String, set_id: i32) -> Result<ProductSet, CommandError> {
+    let source = find_product_set(&email, &password, set_id).await?;
+    let name = format!("{} (copy)", source.name);
+    copy_product_set(&app, &email, &password, source, name).await
+}
+
+#[tauri::command]
+async fn clone_product_set(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, new_name: String) -> Result<ProductSet, CommandError> {
+    let new_name = new_name.trim().to_string();
+    if new_name.is_empty() {
+        return Err("Name is required".into());
     }
-    
-    let info_response: ShopeeAccountInfoResponse = serde_json::from_str(&text)
-        .map_err(|e| format!("Failed to parse response: {} - Response: {}", e, text))?;
-    
-    if info_response.error != 0 {
-        return Err(format!("Shopee API error: {} - {}",
-            info_response.error,
-            info_response.error_msg.unwrap_or("Unknown error".to_string())));
+    let source = find_product_set(&email, &password, product_set_id).await?;
+    copy_product_set(&app, &email, &password, source, new_name).await
+}
+
+// Items without resolved IDs fall back to their URL as the identity
+fn item_identity(item: &ProductSetItem) -> String {
+    match (item.shop_id, item.item_id) {
+        (Some(shop_id), Some(item_id)) => format!("{}.{}", shop_id, item_id),
+        _ => item.url.trim().to_string(),
     }
-    
-    info_response.data.ok_or_else(|| "No account info in response".to_string())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProductSetDiff {
+    pub only_in_a: Vec<ProductSetItem>,
+    pub only_in_b: Vec<ProductSetItem>,
+    pub shared: Vec<ProductSetItem>,
+}
+
+// Shared items are reported with set A's copy
+#[tauri::command]
+async fn diff_product_sets(email: String, password: String, a_id: i32, b_id: i32) -> Result<ProductSetDiff, CommandError> {
+    let mut sets = get_product_sets(email, password).await?.product_sets;
+    let mut take = |id: i32| {
+        sets.iter()
+            .position(|s| s.id == id)
+            .map(|position| sets.swap_remove(position).items)
+            .ok_or_else(|| CommandError::from(format!("Product set {} not found", id)))
+    };
+    if a_id == b_id {
+        return Err("Pick two different sets to compare".into());
+    }
+    let a = take(a_id)?;
+    let b = take(b_id)?;
+
+    let b_keys: std::collections::HashSet<String> = b.iter().map(item_identity).collect();
+    let a_keys: std::collections::HashSet<String> = a.iter().map(item_identity).collect();
+    let (shared, only_in_a) = a.into_iter().partition(|item| b_keys.contains(&item_identity(item)));
+    let only_in_b = b.into_iter().filter(|item| !a_keys.contains(&item_identity(item))).collect();
+    Ok(ProductSetDiff { only_in_a, only_in_b, shared })
+}
+
+#[tauri::command]
+async fn get_product_set_templates(email: String, password: String) -> Result<ProductSetTemplatesResponse, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password
+    });
+    
+    let response: ApiResponse<ProductSetTemplatesResponse> = make_api_request("GET", "/api/members/product-set-templates", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to get product set templates".to_string()).into());
+    }
+    
+    response.data.ok_or_else(|| "No data in response".into())
+}
+
+// Copies a vendor template into a new set owned by the member, named after the template unless overridden
+#[tauri::command]
+async fn import_product_set_template(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    template_id: i32,
+    name: Option<String>,
+    niche_id: Option<i32>,
+) -> Result<ProductSet, CommandError> {
+    let template = get_product_set_templates(email.clone(), password.clone())
+        .await?
+        .templates
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| CommandError::from(format!("Template {} not found", template_id)))?;
+    
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or_else(|| template.name.clone());
+    let source = ProductSet {
+        id: template.id,
+        name: template.name,
+        description: template.description,
+        niche_id,
+        items: template.items,
+        is_read_only: false,
+    };
+    copy_product_set(&app, &email, &password, source, name).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub duplicates: usize,
+}
+
+// Appends the union of the source sets' items to the target, skipping any product the
+// target (or an earlier source) already has
+#[tauri::command]
+async fn merge_product_sets(app: tauri::AppHandle, email: String, password: String, mut source_ids: Vec<i32>, target_id: i32) -> Result<MergeSummary, CommandError> {
+    let mut listed = std::collections::HashSet::new();
+    source_ids.retain(|id| listed.insert(*id));
+    if source_ids.is_empty() {
+        return Err("Select at least one source set".into());
+    }
+    if source_ids.contains(&target_id) {
+        return Err("The target set can't also be a source".into());
+    }
+    
+    let mut sets = get_product_sets(email.clone(), password.clone()).await?.product_sets;
+    let target = sets
+        .iter()
+        .find(|s| s.id == target_id)
+        .ok_or_else(|| CommandError::from(format!("Product set {} not found", target_id)))?;
+    if target.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id: target_id });
+    }
+    
+    let mut seen: std::collections::HashSet<String> = target.items.iter().map(item_identity).collect();
+    
+    let mut items = Vec::new();
+    let mut duplicates = 0;
+    for source_id in &source_ids {
+        let position = sets
+            .iter()
+            .position(|s| s.id == *source_id)
+            .ok_or_else(|| CommandError::from(format!("Product set {} not found", source_id)))?;
+        for item in sets.swap_remove(position).items {
+            if seen.insert(item_identity(&item)) {
+                items.push(ProductSetItemInput::from(&item));
+            } else {
+                duplicates += 1;
+            }
+        }
+    }
+    
+    // add_product_set_items also skips products the set already has by shop/item ID, which the
+    // URL check above can miss
+    let mut added = 0;
+    let mut failed = AggregateError::new(items.len());
+    for (n, chunk) in items.chunks(product_items::UPLOAD_CHUNK_SIZE).enumerate() {
+        match add_product_set_items(app.clone(), email.clone(), password.clone(), target_id, chunk.to_vec()).await {
+            Ok(summary) => {
+                added += summary.added;
+                duplicates += summary.skipped.len();
+                failed.succeeded += chunk.len();
+            }
+            Err(e) => {
+                let urls: Vec<String> = chunk.iter().map(|item| item.url.clone()).collect();
+                failed.push_batch(n * product_items::UPLOAD_CHUNK_SIZE, &urls, e);
+            }
+        }
+    }
+    
+    println!("[MERGE] Added {} items to set {}, skipped {} duplicates", added, target_id, duplicates);
+    failed.into_result()?;
+    Ok(MergeSummary { added, duplicates })
+}
+
+// Creates a set with the source's description and niche, then copies its items over in chunks
+async fn copy_product_set(app: &tauri::AppHandle, email: &str, password: &str, source: ProductSet, name: String) -> Result<ProductSet, CommandError> {
+    let mut copy = create_product_set(
+        app.clone(),
+        email.to_string(),
+        password.to_string(),
+        name,
+        source.description.clone(),
+        source.niche_id,
+    )
+    .await?;
+    
+    let items: Vec<ProductSetItemInput> = source.items.iter().map(ProductSetItemInput::from).collect();
+    let mut failed = AggregateError::new(items.len());
+    for (n, chunk) in items.chunks(product_items::UPLOAD_CHUNK_SIZE).enumerate() {
+        match add_product_set_items(app.clone(), email.to_string(), password.to_string(), copy.id, chunk.to_vec()).await {
+            Ok(_) => failed.succeeded += chunk.len(),
+            Err(e) => {
+                let urls: Vec<String> = chunk.iter().map(|item| item.url.clone()).collect();
+                failed.push_batch(n * product_items::UPLOAD_CHUNK_SIZE, &urls, e);
+            }
+        }
+    }
+    failed.into_result()?;
+    
+    copy.items = find_product_set(email, password, copy.id).await?.items;
+    Ok(copy)
+}
+
+#[tauri::command]
+async fn delete_product_set_item(app: tauri::AppHandle, email: String, password: String, product_set_id: i32, item_id: i32) -> Result<(), CommandError> {
+    ensure_set_writable(&email, &password, product_set_id).await?;
+    delete_item_request(&email, &password, product_set_id, item_id).await?;
+    live_sync::notify_set_changed(&app, product_set_id);
+    audit::record(&app, "delete_product_set_item", Some(format!("product_set:{}", product_set_id)), serde_json::json!({ "item_id": item_id }));
+    Ok(())
+}
+
+async fn delete_item_request(email: &str, password: &str, product_set_id: i32, item_id: i32) -> Result<(), CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password
+    });
+    
+    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}/items/{}", product_set_id, item_id), Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to delete item".to_string()).into());
+    }
+    
+    Ok(())
+}
+
+// Adds the items to the target first, then deletes them from the source. If any delete
+// fails, the items already deleted are re-added to the source and the copies are removed
+// from the target, so a failed move leaves both sets as they were.
+#[tauri::command]
+async fn move_product_set_items(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    from_set: i32,
+    to_set: i32,
+    item_ids: Vec<i32>,
+) -> Result<usize, CommandError> {
+    if from_set == to_set {
+        return Err("Source and target set must be different".into());
+    }
+    let source = find_product_set(&email, &password, from_set).await?;
+    if source.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id: from_set });
+    }
+    let target = find_product_set(&email, &password, to_set).await?;
+    if target.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id: to_set });
+    }
+    
+    let mut moving = Vec::with_capacity(item_ids.len());
+    let mut missing = AggregateError::new(item_ids.len());
+    for (index, id) in item_ids.iter().enumerate() {
+        let Some(item) = source.items.iter().find(|item| item.id == *id) else {
+            missing.push(index, id.to_string(), "Item is not in this product set");
+            continue;
+        };
+        missing.succeeded += 1;
+        if !moving.iter().any(|m: &&ProductSetItem| m.id == item.id) {
+            moving.push(item);
+        }
+    }
+    missing.into_result()?;
+    if moving.is_empty() {
+        return Ok(0);
+    }
+    
+    // IDs of the target's items that this move created, so a rollback leaves items added by
+    // anything else alone
+    let mut known: std::collections::HashSet<i32> = target.items.iter().map(|item| item.id).collect();
+    let mut created = Vec::new();
+    for chunk in moving.chunks(product_items::UPLOAD_CHUNK_SIZE) {
+        let inputs: Vec<ProductSetItemInput> = chunk.iter().map(|item| ProductSetItemInput::from(*item)).collect();
+        let result = add_product_set_items(app.clone(), email.clone(), password.clone(), to_set, inputs).await;
+        // A failed request may still have added some rows, so those are looked up as well
+        let sent: Vec<&ProductSetItem> = match &result {
+            Ok(summary) => chunk
+                .iter()
+                .copied()
+                .filter(|item| !summary.skipped.iter().any(|s| s.url == item.url))
+                .collect(),
+            Err(_) => chunk.to_vec(),
+        };
+        match find_product_set(&email, &password, to_set).await {
+            Ok(set) => {
+                for item in set.items {
+                    let ours = sent.iter().any(|s| s.url == item.url || item_identity(s) == item_identity(&item));
+                    if ours && known.insert(item.id) {
+                        created.push(item.id);
+                    }
+                }
+            }
+            Err(e) => eprintln!("[MOVE] Failed to load set {} after adding items: {}", to_set, e),
+        }
+        if let Err(e) = result {
+            rollback_added(&email, &password, to_set, &created).await;
+            return Err(e);
+        }
+    }
+    
+    let mut deleted: Vec<&ProductSetItem> = Vec::new();
+    for item in &moving {
+        if let Err(e) = delete_item_request(&email, &password, from_set, item.id).await {
+            eprintln!("[MOVE] Delete of item {} failed, rolling back: {}", item.id, e);
+            let restore: Vec<ProductSetItemInput> = deleted.iter().map(|item| ProductSetItemInput::from(*item)).collect();
+            for chunk in restore.chunks(product_items::UPLOAD_CHUNK_SIZE) {
+                if let Err(e) = add_product_set_items(app.clone(), email.clone(), password.clone(), from_set, chunk.to_vec()).await {
+                    eprintln!("[MOVE] Failed to restore items to set {}: {}", from_set, e);
+                }
+            }
+            rollback_added(&email, &password, to_set, &created).await;
+            live_sync::notify_set_changed(&app, from_set);
+            live_sync::notify_set_changed(&app, to_set);
+            return Err(e);
+        }
+        deleted.push(item);
+    }
+    
+    live_sync::notify_set_changed(&app, from_set);
+    live_sync::notify_set_changed(&app, to_set);
+    println!("[MOVE] Moved {} items from set {} to set {}", moving.len(), from_set, to_set);
+    audit::record(
+        &app,
+        "move_product_set_items",
+        Some(format!("product_set:{}", from_set)),
+        serde_json::json!({ "to_set": to_set, "item_ids": moving.iter().map(|item| item.id).collect::<Vec<_>>() }),
+    );
+    Ok(moving.len())
+}
+
+// Removes the items a failed move created in the target
+async fn rollback_added(email: &str, password: &str, product_set_id: i32, created: &[i32]) {
+    for item_id in created {
+        if let Err(e) = delete_item_request(email, password, product_set_id, *item_id).await {
+            eprintln!("[MOVE] Failed to roll back item {} in set {}: {}", item_id, product_set_id, e);
+        }
+    }
+}
+
+#[tauri::command]
+async fn clear_product_set_items(
+    app: tauri::AppHandle,
+    confirmations: tauri::State<'_, ConfirmationState>,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    confirmation_token: Option<String>,
+) -> Result<DestructiveOutcome, CommandError> {
+    let action = format!("clear_product_set_items:{}", product_set_id);
+    let Some(token) = confirmation_token else {
+        let product_set = find_product_set(&email, &password, product_set_id).await?;
+        if product_set.is_read_only {
+            return Err(CommandError::ReadOnlySet { product_set_id });
+        }
+        return Ok(confirmations.issue(&action, Impact {
+            summary: format!("All {} item(s) in \"{}\" will be removed", product_set.items.len(), product_set.name),
+            product_sets: 1,
+            items: product_set.items.len(),
+        }));
+    };
+    confirmations.consume(&token, &action)?;
+    let product_set = find_product_set(&email, &password, product_set_id).await?;
+    if product_set.is_read_only {
+        return Err(CommandError::ReadOnlySet { product_set_id });
+    }
+    if !product_set.items.is_empty() {
+        clear_undo::snapshot_set(&app, product_set_id, product_set.items.iter().map(ProductSetItemInput::from).collect())?;
+    }
+    
+    let body = serde_json::json!({
+        "email": email,
+        "password": password
+    });
+    
+    let response: ApiResponse<serde_json::Value> = make_api_request("DELETE", &format!("/api/members/product-sets/{}/items", product_set_id), Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to clear items".to_string()).into());
+    }
+    
+    live_sync::notify_set_changed(&app, product_set_id);
+    audit::record(
+        &app,
+        "clear_product_set_items",
+        Some(format!("product_set:{}", product_set_id)),
+        serde_json::json!({ "items": product_set.items.len() }),
+    );
+    
+    Ok(DestructiveOutcome::Done)
+}
+
+// Watch folder commands
+#[tauri::command]
+async fn start_watch_folder(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, WatchFolderState>,
+    email: String,
+    password: String,
+    path: String,
+) -> Result<(), String> {
+    watch_folder::start(&app, &state, email, password, path)
+}
+
+#[tauri::command]
+async fn stop_watch_folder(state: tauri::State<'_, WatchFolderState>) -> Result<(), String> {
+    state.stop();
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_watch_folder(app: tauri::AppHandle, state: tauri::State<'_, WatchFolderState>) -> Result<(WatchFolderConfig, bool), String> {
+    Ok((watch_folder::load_config(&app)?, state.is_running()))
+}
+
+// Opt-in clipboard monitor emitting `product-url-copied` for copied Shopee product links
+#[tauri::command]
+async fn start_clipboard_watch(app: tauri::AppHandle, state: tauri::State<'_, ClipboardWatchState>) -> Result<(), String> {
+    clipboard_watch::start(&app, &state);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_clipboard_watch(state: tauri::State<'_, ClipboardWatchState>) -> Result<(), String> {
+    state.stop();
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_clipboard_watch_status(state: tauri::State<'_, ClipboardWatchState>) -> Result<bool, String> {
+    Ok(state.is_running())
+}
+
+// The account's current live session, if it has one
+pub(crate) async fn active_session_id(email: &str, password: &str, shopee_account_id: i32) -> Result<Option<String>, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id
+    });
+    
+    // Use new active-session endpoint which returns only one active session or null
+    let response: ActiveSessionApiResponse = make_api_request("POST", "/api/shopee-live/active-session", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to get active session".to_string()).into());
+    }
+    
+    Ok(response.session_id)
+}
+
+#[tauri::command]
+async fn get_session_ids(email: String, password: String, shopee_account_id: i32) -> Result<SessionIdsResponse, CommandError> {
+    // Convert Option<String> to Vec<String> for compatibility with frontend
+    let session_ids = match active_session_id(&email, &password, shopee_account_id).await? {
+        Some(sid) => vec![sid],
+        None => vec![],
+    };
+    
+    Ok(SessionIdsResponse {
+        session_ids,
+    })
+}
+
+// Starts a new live stream on the account through the backend, which holds the account's
+// cookie and uploads the cover to Shopee. The returned session ID is ready for replace_products.
+#[tauri::command]
+async fn create_live_session(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    title: String,
+    cover_path: Option<String>,
+) -> Result<String, CommandError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Title is required".into());
+    }
+    let mut body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "title": title
+    });
+    if let Some(path) = cover_path.filter(|p| !p.trim().is_empty()) {
+        let cover = live_session::read_cover(std::path::Path::new(&path))?;
+        body["cover_image"] = serde_json::Value::String(cover.base64);
+        body["cover_mime"] = serde_json::Value::String(cover.mime.to_string());
+    }
+    
+    let response: ActiveSessionApiResponse = make_api_request("POST", "/api/shopee-live/create-session", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to create live session".to_string()).into());
+    }
+    
+    let session_id: String = response.session_id.ok_or_else(|| CommandError::from("No session ID in response"))?;
+    audit::record(
+        &app,
+        "create_live_session",
+        Some(format!("session:{}", session_id)),
+        serde_json::json!({ "shopee_account_id": shopee_account_id, "title": title }),
+    );
+    Ok(session_id)
+}
+
+// Ends the stream so automation (schedules, license expiry) doesn't leave it running.
+// Background tasks tied to the session (rotations, polling, heartbeat, watchdog) stop with it.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn end_live_session(
+    app: tauri::AppHandle,
+    rotations: tauri::State<'_, ShowcaseRotationState>,
+    viewer_polling: tauri::State<'_, ViewerPollingState>,
+    pin_rotations: tauri::State<'_, PinRotationState>,
+    watchdogs: tauri::State<'_, SessionWatchdogState>,
+    heartbeats: tauri::State<'_, HeartbeatState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<(), CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    
+    // Stopped first so the watchdog doesn't see the session end and restart it
+    let watched = watchdogs.stop_session(&session_id);
+    let result: Result<ApiResponse<serde_json::Value>, CommandError> = make_api_request("POST", "/api/shopee-live/end-session", Some(&body), None).await;
+    let error = match result {
+        Ok(response) if response.success => None,
+        Ok(response) => Some(CommandError::from(response.message.unwrap_or_else(|| "Failed to end live session".to_string()))),
+        Err(e) => Some(e),
+    };
+    if let Some(error) = error {
+        diagnostics::record_failure(&app, &session_id, "end_live_session", &error);
+        // The session is still live, so it keeps its watchdog
+        if let Some(info) = watched {
+            let binding = LiveSyncBinding {
+                product_set_id: info.product_set_id,
+                shopee_account_id: info.shopee_account_id,
+                session_id: info.session_id,
+                email,
+                password,
+            };
+            session_watchdog::start(&app, &watchdogs, binding, None);
+        }
+        return Err(error);
+    }
+    
+    rotations.stop(&session_id);
+    viewer_polling.stop(&session_id);
+    pin_rotations.stop(&session_id);
+    heartbeats.stop(&session_id);
+    app.state::<AutomationState>().stop(&session_id);
+    app.state::<PromoCommentState>().stop(&session_id);
+    app.state::<AutoReplyState>().stop(&session_id);
+    app.state::<ModerationState>().stop(&session_id);
+    session_reports::close(&app, &session_id);
+    audit::record(
+        &app,
+        "end_live_session",
+        Some(format!("session:{}", session_id)),
+        serde_json::json!({ "shopee_account_id": shopee_account_id }),
+    );
+    Ok(())
+}
+
+// Shopee's own counters for a session, read with the account's cookie
+async fn fetch_live_stats(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Result<shopee::LiveStats, CommandError> {
+    let cookie = find_shopee_account(email, password, shopee_account_id)
+        .await?
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("Account has no cookie"))?;
+    let proxy = account_proxies::proxy_for(app, shopee_account_id);
+    let client = shopee_api_client(proxy.as_deref())?;
+    let stats = client.live_stats(session_id, &cookie).await.map_err(String::from)?;
+    session_reports::record_stats(app, session_id, &stats);
+    Ok(stats)
+}
+
+// Whether Shopee still has the account streaming into this session, i.e. the push is up
+async fn fetch_push_status(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Result<bool, CommandError> {
+    let cookie = find_shopee_account(email, password, shopee_account_id)
+        .await?
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("Account has no cookie"))?;
+    let proxy = account_proxies::proxy_for(app, shopee_account_id);
+    let client = shopee_api_client(proxy.as_deref())?;
+    let ongoing = client.ongoing_session(&cookie).await.map_err(String::from)?;
+    Ok(ongoing.as_deref() == Some(session_id))
+}
+
+// One product's live Shopee data, looked up with the account's cookie and proxy
+async fn fetch_shopee_item(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, shop_id: i64, item_id: i64) -> Result<Option<ShopeeItem>, CommandError> {
+    let cookie = find_shopee_account(email, password, shopee_account_id).await?.cookie.filter(|c| !c.is_empty());
+    let proxy = account_proxies::proxy_for(app, shopee_account_id);
+    let client = shopee_api_client(proxy.as_deref())?;
+    Ok(client.item(shop_id, item_id, cookie.as_deref()).await.map_err(String::from)?)
+}
+
+#[tauri::command]
+async fn get_live_stats(app: tauri::AppHandle, email: String, password: String, shopee_account_id: i32, session_id: String) -> Result<shopee::LiveStats, CommandError> {
+    fetch_live_stats(&app, &email, &password, shopee_account_id, &session_id).await
+}
+
+// Summary of what automation did during a monitored session; in progress until the session ends
+#[tauri::command]
+async fn get_session_report(app: tauri::AppHandle, session_id: String) -> Result<Option<session_reports::SessionReport>, String> {
+    session_reports::get(&app, &session_id)
+}
+
+// Samples stored while viewer polling ran for the session, oldest first, for charting
+#[tauri::command]
+async fn get_session_timeseries(app: tauri::AppHandle, session_id: String) -> Result<Vec<live_timeseries::LiveSample>, String> {
+    live_timeseries::get(&app, &session_id)
+}
+
+#[derive(Debug, Deserialize)]
+struct ShareLiveResponse {
+    share_url: String,
+}
+
+// Shares the live to the chosen channel and returns the link to spread, tagged with UTM params
+#[tauri::command]
+async fn share_live_session(
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    channel: Option<live_session::ShareChannel>,
+) -> Result<String, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "channel": channel.unwrap_or_default().as_api()
+    });
+    
+    let response: ApiResponse<ShareLiveResponse> = make_api_request("POST", "/api/shopee-live/share", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to share live session".to_string()).into());
+    }
+    
+    let data = response.data.ok_or_else(|| "No data in response".to_string())?;
+    Ok(live_session::with_utm(&data.share_url, &session_id)?)
+}
+
+#[derive(Debug, Deserialize)]
+struct PushUrlResponse {
+    push_url: String,
+}
+
+#[tauri::command]
+async fn get_push_url(email: String, password: String, shopee_account_id: i32, session_id: String) -> Result<LivePushUrl, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    
+    let response: ApiResponse<PushUrlResponse> = make_api_request("POST", "/api/shopee-live/push-url", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to get push URL".to_string()).into());
+    }
+    
+    let data = response.data.ok_or_else(|| "No data in response".to_string())?;
+    LivePushUrl::from_push_url(&data.push_url).ok_or_else(|| "Unexpected push URL format".to_string().into())
+}
+
+pub(crate) async fn send_session_heartbeat(email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Result<(), CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    
+    let response: ApiResponse<serde_json::Value> = make_api_request("POST", "/api/shopee-live/heartbeat", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Heartbeat failed".to_string()).into());
+    }
+    
+    Ok(())
+}
+
+#[tauri::command]
+async fn start_session_heartbeat(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, HeartbeatState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    live_session::start_heartbeat(&app, &state, email, password, shopee_account_id, session_id, interval_secs);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_session_heartbeat(state: tauri::State<'_, HeartbeatState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_heartbeat_sessions(state: tauri::State<'_, HeartbeatState>) -> Result<Vec<String>, String> {
+    Ok(state.sessions())
+}
+
+// Detects a live started outside the app (e.g. "Go Live" on the phone) as soon as Shopee
+// reports it, rather than waiting for the backend to learn about it
+#[tauri::command]
+async fn watch_for_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionWatchState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+) -> Result<(), CommandError> {
+    let cookie = find_shopee_account(&email, &password, shopee_account_id)
+        .await?
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("Account has no cookie"))?;
+    let proxy = account_proxies::proxy_for(&app, shopee_account_id);
+    Ok(live_session::watch_for_session(&app, &state, shopee_account_id, cookie, proxy)?)
+}
+
+#[tauri::command]
+async fn stop_watching_for_session(state: tauri::State<'_, SessionWatchState>, shopee_account_id: i32) -> Result<bool, String> {
+    Ok(state.stop(shopee_account_id))
+}
+
+#[tauri::command]
+async fn get_watched_accounts(state: tauri::State<'_, SessionWatchState>) -> Result<Vec<i32>, String> {
+    Ok(state.accounts())
+}
+
+// Restarts the account's live (new session + product set) if it ends without end_live_session
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn start_session_watchdog(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionWatchdogState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    product_set_id: i32,
+    title: Option<String>,
+) -> Result<session_watchdog::WatchdogInfo, String> {
+    let binding = LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        session_id,
+        email,
+        password,
+    };
+    Ok(session_watchdog::start(&app, &state, binding, title))
+}
+
+#[tauri::command]
+async fn stop_session_watchdog(state: tauri::State<'_, SessionWatchdogState>, shopee_account_id: i32) -> Result<bool, String> {
+    Ok(state.stop(shopee_account_id))
+}
+
+#[tauri::command]
+async fn get_session_watchdogs(state: tauri::State<'_, SessionWatchdogState>) -> Result<Vec<session_watchdog::WatchdogInfo>, String> {
+    Ok(state.list())
+}
+
+#[tauri::command]
+async fn start_viewer_polling(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ViewerPollingState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    let interval_secs = interval_secs.unwrap_or_else(|| settings::current().automation.viewer_poll_secs);
+    live_session::start_polling(&app, &state, email, password, shopee_account_id, session_id, interval_secs);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_viewer_polling(state: tauri::State<'_, ViewerPollingState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_viewer_polling_sessions(state: tauri::State<'_, ViewerPollingState>) -> Result<Vec<String>, String> {
+    Ok(state.sessions())
+}
+
+// Features an item from the session's showcase; `item_id` is Shopee's item ID
+// Every action on a live session goes through here, so paced actions are spaced out and
+// counted against the account's daily cap whichever command sends them. Only actions the
+// backend accepts are counted.
+async fn live_action_request(app: &tauri::AppHandle, shopee_account_id: i32, endpoint: &str, body: &serde_json::Value) -> Result<ApiResponse<serde_json::Value>, CommandError> {
+    let action = PacedAction::for_endpoint(endpoint);
+    if let Some(action) = action {
+        pacing::acquire(app, shopee_account_id, action).await?;
+    }
+    let response: ApiResponse<serde_json::Value> = make_api_request("POST", endpoint, Some(body), None).await?;
+    if let Some(action) = action.filter(|_| response.success) {
+        pacing::record(app, shopee_account_id, action);
+    }
+    Ok(response)
+}
+
+pub(crate) async fn pin_product_request(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str, item_id: i64) -> Result<(), CommandError> {
+    let response = send_pin_request(app, email, password, shopee_account_id, session_id, item_id).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to pin product".to_string()).into());
+    }
+    
+    session_reports::record_pin(app, session_id);
+    Ok(())
+}
+
+async fn send_pin_request(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str, item_id: i64) -> Result<ApiResponse<serde_json::Value>, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "item_id": item_id
+    });
+    
+    live_action_request(app, shopee_account_id, "/api/shopee-live/pin-product", &body).await
+}
+
+#[tauri::command]
+async fn pin_product(app: tauri::AppHandle, email: String, password: String, shopee_account_id: i32, session_id: String, item_id: i64) -> Result<(), CommandError> {
+    let result = send_pin_request(&app, &email, &password, shopee_account_id, &session_id, item_id).await;
+    let response = result.inspect_err(|e| diagnostics::record_failure(&app, &session_id, "pin_product", e))?;
+    
+    if !response.success {
+        let error: CommandError = response.message.unwrap_or_else(|| "Failed to pin product".to_string()).into();
+        diagnostics::record_failure(&app, &session_id, "pin_product", &error);
+        return Err(error);
+    }
+    
+    session_reports::record_pin(&app, &session_id);
+    audit::record(&app, "pin_product", Some(format!("session:{}", session_id)), serde_json::json!({ "item_id": item_id }));
+    Ok(())
+}
+
+#[tauri::command]
+async fn unpin_product(app: tauri::AppHandle, email: String, password: String, shopee_account_id: i32, session_id: String, item_id: i64) -> Result<(), CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "item_id": item_id
+    });
+    
+    let result: Result<ApiResponse<serde_json::Value>, CommandError> = live_action_request(&app, shopee_account_id, "/api/shopee-live/unpin-product", &body).await;
+    let response = result.inspect_err(|e| diagnostics::record_failure(&app, &session_id, "unpin_product", e))?;
+    
+    if !response.success {
+        let error: CommandError = response.message.unwrap_or_else(|| "Failed to unpin product".to_string()).into();
+        diagnostics::record_failure(&app, &session_id, "unpin_product", &error);
+        return Err(error);
+    }
+    
+    audit::record(&app, "unpin_product", Some(format!("session:{}", session_id)), serde_json::json!({ "item_id": item_id }));
+    Ok(())
+}
+
+// Pins each product of a set in turn for `pin_seconds`, in showcase order or best sellers first
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn start_pin_rotation(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PinRotationState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    product_set_id: i32,
+    pin_seconds: Option<u64>,
+    order: Option<PinOrder>,
+    dry_run: Option<bool>,
+) -> Result<LiveOutcome<pin_rotation::PinRotationInfo>, CommandError> {
+    let pin_seconds = pin_seconds.unwrap_or_else(|| settings::current().automation.pin_interval_secs);
+    let order = order.unwrap_or_default();
+    let set = find_product_set(&email, &password, product_set_id).await?;
+    let ids = showcase_item_ids(&app, &set)?;
+    let mut targets: Vec<pin_rotation::PinTarget> = ids
+        .iter()
+        .filter_map(|id| set.items.iter().find(|item| item.id == *id))
+        .filter_map(|item| {
+            Some(pin_rotation::PinTarget {
+                product_set_item_id: item.id,
+                shop_id: item.shop_id?,
+                item_id: item.item_id?,
+            })
+        })
+        .collect();
+    
+    if order == PinOrder::SalesRank {
+        let cookie = find_shopee_account(&email, &password, shopee_account_id).await?.cookie.filter(|c| !c.is_empty());
+        let proxy = account_proxies::proxy_for(&app, shopee_account_id);
+        let client = shopee_api_client(proxy.as_deref())?;
+        let mut sold = std::collections::HashMap::new();
+        for target in &targets {
+            // Items that can't be looked up sort last rather than failing the rotation
+            let count = match client.item(target.shop_id, target.item_id, cookie.as_deref()).await {
+                Ok(Some(item)) => item.historical_sold,
+                _ => -1,
+            };
+            sold.insert(target.item_id, count);
+        }
+        targets.sort_by_key(|target| std::cmp::Reverse(sold.get(&target.item_id).copied().unwrap_or(-1)));
+    }
+    
+    if dry_run.unwrap_or(false) {
+        let mut actions = plan_live_target(&email, &password, shopee_account_id, &session_id).await?;
+        actions.extend(targets.iter().map(|target| {
+            PlannedAction::new("pin", format!("Item {} for {}s", target.item_id, pin_seconds.max(pin_rotation::MIN_PIN_SECS)))
+        }));
+        return Ok(LiveOutcome::Planned { actions });
+    }
+    
+    let binding = LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        session_id,
+        email,
+        password,
+    };
+    let result = pin_rotation::start(&app, &state, binding, targets, pin_seconds, order)?;
+    Ok(LiveOutcome::Done { result })
+}
+
+#[tauri::command]
+async fn pause_pin_rotation(state: tauri::State<'_, PinRotationState>, session_id: String) -> Result<pin_rotation::PinRotationInfo, String> {
+    state.set_paused(&session_id, true)
+}
+
+#[tauri::command]
+async fn resume_pin_rotation(state: tauri::State<'_, PinRotationState>, session_id: String) -> Result<pin_rotation::PinRotationInfo, String> {
+    state.set_paused(&session_id, false)
+}
+
+#[tauri::command]
+async fn stop_pin_rotation(state: tauri::State<'_, PinRotationState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_pin_rotations(state: tauri::State<'_, PinRotationState>) -> Result<Vec<pin_rotation::PinRotationInfo>, String> {
+    Ok(state.list())
+}
+
+pub(crate) async fn fetch_live_comments(
+    email: &str,
+    password: &str,
+    shopee_account_id: i32,
+    session_id: &str,
+    cursor: Option<&str>,
+) -> Result<LiveCommentsResponse, CommandError> {
+    let mut body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    if let Some(cursor) = cursor {
+        body["cursor"] = serde_json::Value::String(cursor.to_string());
+    }
+    
+    let response: ApiResponse<LiveCommentsResponse> = make_api_request("POST", "/api/shopee-live/comments", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to get comments".to_string()).into());
+    }
+    
+    response.data.ok_or_else(|| "No data in response".into())
+}
+
+// Posts a comment into the session as the host account
+pub(crate) async fn post_live_comment(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str, content: &str) -> Result<(), CommandError> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Err("Comment is empty".into());
+    }
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "content": content
+    });
+    
+    let response: ApiResponse<serde_json::Value> = live_action_request(app, shopee_account_id, "/api/shopee-live/send-comment", &body).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to send comment".to_string()).into());
+    }
+    
+    session_reports::record_comment(app, session_id);
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_live_comments(
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    cursor: Option<String>,
+) -> Result<LiveCommentsResponse, CommandError> {
+    fetch_live_comments(&email, &password, shopee_account_id, &session_id, cursor.as_deref()).await
+}
+
+#[tauri::command]
+async fn send_live_comment(app: tauri::AppHandle, email: String, password: String, shopee_account_id: i32, session_id: String, content: String) -> Result<(), CommandError> {
+    post_live_comment(&app, &email, &password, shopee_account_id, &session_id, &content).await
+}
+
+#[tauri::command]
+async fn get_settings() -> Result<settings::Settings, String> {
+    Ok(settings::current())
+}
+
+// Replaces all settings; nothing is saved if any value is invalid
+#[tauri::command]
+async fn update_settings(app: tauri::AppHandle, settings: settings::Settings) -> Result<settings::Settings, String> {
+    let settings = settings::save(&app, settings)?;
+    audit::record(&app, "update_settings", None, serde_json::Value::Null);
+    Ok(settings)
+}
+
+// Points the app at production, staging or a mirror; takes effect from the next request
+#[tauri::command]
+async fn set_api_environment(app: tauri::AppHandle, environment: settings::ApiEnvironment) -> Result<settings::Settings, String> {
+    let settings = settings::update(&app, |settings| settings.environment = environment)?;
+    audit::record(&app, "set_api_environment", None, serde_json::json!({ "environment": settings.environment }));
+    Ok(settings)
+}
+
+#[tauri::command]
+async fn get_pacing_status(app: tauri::AppHandle) -> Result<pacing::PacingStatus, String> {
+    pacing::status(&app)
+}
+
+#[tauri::command]
+async fn set_pacing_profile(app: tauri::AppHandle, profile: pacing::PacingProfile) -> Result<pacing::PacingStatus, String> {
+    pacing::set_profile(&app, profile)?;
+    pacing::status(&app)
+}
+
+// Hides or reports a comment through Shopee's moderation tools; `action` is "hide" or "report"
+pub(crate) async fn moderate_live_comment(
+    app: &tauri::AppHandle,
+    email: &str,
+    password: &str,
+    shopee_account_id: i32,
+    session_id: &str,
+    comment_id: &str,
+    action: &str,
+) -> Result<(), CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "comment_id": comment_id,
+        "action": action
+    });
+    
+    let response: ApiResponse<serde_json::Value> = live_action_request(app, shopee_account_id, "/api/shopee-live/moderate-comment", &body).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| format!("Failed to {} comment", action)).into());
+    }
+    
+    Ok(())
+}
+
+// Comment moderation commands
+#[tauri::command]
+async fn get_moderation_config(app: tauri::AppHandle) -> Result<moderation::ModerationConfig, String> {
+    moderation::load(&app)
+}
+
+#[tauri::command]
+async fn set_moderation_config(app: tauri::AppHandle, config: moderation::ModerationConfig) -> Result<moderation::ModerationConfig, String> {
+    moderation::save(&app, config)
+}
+
+#[tauri::command]
+async fn start_comment_moderation(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ModerationState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<(), String> {
+    moderation::start(&app, &state, email, password, shopee_account_id, session_id);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_comment_moderation(state: tauri::State<'_, ModerationState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_moderated_sessions(state: tauri::State<'_, ModerationState>) -> Result<Vec<String>, String> {
+    Ok(state.sessions())
+}
+
+// Scheduled host announcements, posted in rotation at a fixed interval
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn start_comment_blast(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PromoCommentState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    templates: Vec<String>,
+    interval_secs: u64,
+) -> Result<promo_comments::PromoScheduleInfo, String> {
+    promo_comments::start(&app, &state, email, password, shopee_account_id, session_id, templates, interval_secs)
+}
+
+#[tauri::command]
+async fn stop_comment_blast(state: tauri::State<'_, PromoCommentState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_comment_blasts(state: tauri::State<'_, PromoCommentState>) -> Result<Vec<promo_comments::PromoScheduleInfo>, String> {
+    Ok(state.list())
+}
+
+// Automation rule commands
+#[tauri::command]
+async fn get_automation_rules(app: tauri::AppHandle) -> Result<Vec<automation::AutomationRule>, String> {
+    automation::load(&app)
+}
+
+#[tauri::command]
+async fn save_automation_rules(app: tauri::AppHandle, rules: Vec<automation::AutomationRule>) -> Result<(), String> {
+    automation::save(&app, &rules)
+}
+
+#[tauri::command]
+async fn start_automation(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AutomationState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<automation::AutomationSessionInfo, String> {
+    automation::start(&app, &state, email, password, shopee_account_id, session_id)
+}
+
+#[tauri::command]
+async fn stop_automation(state: tauri::State<'_, AutomationState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn set_session_rule_enabled(state: tauri::State<'_, AutomationState>, session_id: String, rule_id: String, enabled: bool) -> Result<(), String> {
+    state.set_rule_enabled(&session_id, &rule_id, enabled)
+}
+
+#[tauri::command]
+async fn get_automation_sessions(state: tauri::State<'_, AutomationState>) -> Result<Vec<automation::AutomationSessionInfo>, String> {
+    Ok(state.list())
+}
+
+// Keyword auto-reply commands
+#[tauri::command]
+async fn get_auto_reply_rules(app: tauri::AppHandle) -> Result<auto_reply::AutoReplyConfig, String> {
+    auto_reply::load(&app)
+}
+
+#[tauri::command]
+async fn set_auto_reply_rules(app: tauri::AppHandle, config: auto_reply::AutoReplyConfig) -> Result<(), String> {
+    auto_reply::save(&app, &config)
+}
+
+#[tauri::command]
+async fn start_auto_reply(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AutoReplyState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<(), String> {
+    auto_reply::start(&app, &state, email, password, shopee_account_id, session_id)
+}
+
+#[tauri::command]
+async fn stop_auto_reply(state: tauri::State<'_, AutoReplyState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_auto_reply_sessions(state: tauri::State<'_, AutoReplyState>) -> Result<Vec<String>, String> {
+    Ok(state.sessions())
+}
+
+// The set's item IDs in showcase order (local order if any), without disabled items
+fn showcase_item_ids(app: &tauri::AppHandle, set: &ProductSet) -> Result<Vec<i32>, CommandError> {
+    let current: Vec<i32> = set.items.iter().map(|item| item.id).collect();
+    let order = match item_order::get(app, set.id) {
+        Some(mut order) => {
+            order.retain(|id| current.contains(id));
+            item_order::normalize(&current, &order)?
+        }
+        None => current,
+    };
+    
+    // Disabled items stay in the set but are left out of the push
+    let enabled = disabled_items::enabled_in_order(&order, &disabled_items::get(app, set.id));
+    if enabled.is_empty() && !set.items.is_empty() {
+        return Err("Every item in this product set is disabled".into());
+    }
+    Ok(enabled)
+}
+
+// Dry-run checks shared by live commands: the account has a cookie and the session is the
+// account's active live
+async fn plan_live_target(email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Result<Vec<PlannedAction>, CommandError> {
+    let account = find_shopee_account(email, password, shopee_account_id).await?;
+    if account.cookie.as_deref().is_none_or(str::is_empty) {
+        return Err(format!("Account {} has no cookie", account.name).into());
+    }
+    let active = active_session_id(email, password, shopee_account_id).await?;
+    if active.as_deref() != Some(session_id) {
+        return Err(format!("Session {} is not the active live for {}", session_id, account.name).into());
+    }
+    Ok(vec![PlannedAction::new("check", format!("Account {} is live in session {}", account.name, session_id))])
+}
+
+async fn plan_replace(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str, product_set_id: i32) -> Result<Vec<PlannedAction>, CommandError> {
+    let mut actions = plan_live_target(email, password, shopee_account_id, session_id).await?;
+    let set = find_product_set(email, password, product_set_id).await?;
+    let unresolved = set.items.iter().filter(|item| item.shop_id.is_none() || item.item_id.is_none()).count();
+    if unresolved > 0 {
+        actions.push(PlannedAction::new("warning", format!("{} item(s) have no shop/item ID and may be rejected", unresolved)));
+    }
+    let enabled = showcase_item_ids(app, &set)?;
+    let chunk_index = app.state::<ShowcaseRotationState>().current_chunk(session_id).unwrap_or(0);
+    let pushed = showcase::chunk(&enabled, chunk_index);
+    if enabled.len() > pushed.len() {
+        actions.push(PlannedAction::new(
+            "warning",
+            format!("Only {} of {} enabled items fit in the showcase", pushed.len(), enabled.len()),
+        ));
+    }
+    for (i, batch) in pushed.chunks(showcase::PUSH_BATCH_SIZE).enumerate() {
+        let action = if i == 0 { "replace" } else { "add" };
+        actions.push(PlannedAction::new(action, format!("{} item(s) from \"{}\": {:?}", batch.len(), set.name, batch)));
+    }
+    if pushed.is_empty() {
+        actions.push(PlannedAction::new("replace", format!("\"{}\" is empty; the showcase will be emptied", set.name)));
+    }
+    Ok(actions)
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ReplaceProgressEvent {
+    session_id: String,
+    product_set_id: i32,
+    done: usize,
+    total: usize,
+}
+
+fn emit_replace_progress(app: &tauri::AppHandle, session_id: &str, product_set_id: i32, done: usize, total: usize) {
+    let event = ReplaceProgressEvent {
+        session_id: session_id.to_string(),
+        product_set_id,
+        done,
+        total,
+    };
+    if let Err(e) = app.emit("replace-progress", event) {
+        eprintln!("Failed to emit replace-progress: {}", e);
+    }
+}
+
+// Items the session's showcase currently holds
+pub(crate) async fn fetch_showcase_items(email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Result<Vec<LiveShowcaseItem>, CommandError> {
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    
+    let response: ApiResponse<LiveShowcaseResponse> = make_api_request("POST", "/api/shopee-live/showcase-products", Some(&body), None).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to get showcase products".to_string()).into());
+    }
+    
+    response.data.map(|data| data.items).ok_or_else(|| "No data in response".into())
+}
+
+// Every replace shows up in the task manager as its own job
+async fn replace_products_request(app: &tauri::AppHandle, email: &str, password: &str, shopee_account_id: i32, session_id: &str, product_set_id: i32) -> Result<showcase::ReplaceSummary, CommandError> {
+    let label = format!("Push set {} to session {}", product_set_id, session_id);
+    let job_id = jobs::begin(app, "replace_products", label, Some(shopee_account_id));
+    let result = replace_products_in_job(app, &job_id, email, password, shopee_account_id, session_id, product_set_id).await;
+    jobs::finish(app, &job_id, &result);
+    result
+}
+
+// Replaces the session's showcase with the set, reporting progress on a job the caller has
+// already registered. Small sets go in one call; larger ones are replaced with the first batch
+// and the rest appended batch by batch, emitting `replace-progress` after each. A failed append
+// is reported per item in the summary and leaves the job Partial rather than failing the whole
+// push, since the showcase already holds the earlier batches.
+pub(crate) async fn replace_products_in_job(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    email: &str,
+    password: &str,
+    shopee_account_id: i32,
+    session_id: &str,
+    product_set_id: i32,
+) -> Result<showcase::ReplaceSummary, CommandError> {
+    let mut body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "product_set_id": product_set_id
+    });
+    let set = find_product_set(email, password, product_set_id).await?;
+    // Showcase order kept locally when the backend couldn't store it
+    let local_order = item_order::get(app, product_set_id);
+    if let Some(order) = &local_order {
+        body["item_order"] = serde_json::json!(order);
+    }
+    let enabled = showcase_item_ids(app, &set)?;
+    
+    // Keep what the showcase held before, so the push can be rolled back mid-stream
+    match fetch_showcase_items(email, password, shopee_account_id, session_id).await {
+        Ok(items) => {
+            if let Err(e) = showcase_snapshots::save(app, session_id, shopee_account_id, items) {
+                eprintln!("[SHOWCASE] Failed to save snapshot for session {}: {}", session_id, e);
+            }
+        }
+        Err(e) => eprintln!("[SHOWCASE] Failed to snapshot session {} before replace: {}", session_id, e),
+    }
+    
+    // Oversized sets are truncated to the showcase limit, or pushed one chunk at a time
+    // while a rotation is running for the session
+    let rotation = app.state::<ShowcaseRotationState>().current_chunk(session_id);
+    let chunk_index = rotation.unwrap_or(0);
+    let pushed = showcase::chunk(&enabled, chunk_index);
+    let first = pushed.chunks(showcase::PUSH_BATCH_SIZE).next().unwrap_or_default();
+    // With a local order the IDs are always listed, since the backend would otherwise push
+    // the set in its own order
+    if local_order.is_some() || first.len() != set.items.len() {
+        body["item_ids"] = serde_json::json!(first);
+    }
+    
+    // Checkpointed before each batch so a push cut off by a crash can be resumed
+    let mut checkpoint = job_recovery::ReplaceCheckpoint {
+        shopee_account_id,
+        session_id: session_id.to_string(),
+        product_set_id,
+        item_ids: pushed.to_vec(),
+        next_batch: 0,
+        failures: Vec::new(),
+    };
+    job_recovery::save(app, job_id, &job_recovery::Checkpoint::Replace(checkpoint.clone()));
+    
+    let response: ApiResponse<serde_json::Value> = live_action_request(app, shopee_account_id, "/api/shopee-live/replace-products", &body).await?;
+    
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to replace products".to_string()).into());
+    }
+    clear_undo::record_push(app, session_id, shopee_account_id, product_set_id);
+    
+    checkpoint.next_batch = 1;
+    let failures = push_remaining_batches(app, job_id, email, password, checkpoint).await;
+    
+    let showcase = (enabled.len() > showcase::SESSION_LIMIT).then(|| {
+        if rotation.is_none() {
+            println!(
+                "[SHOWCASE] Set {} has {} items, only the first {} were pushed to session {}",
+                product_set_id,
+                enabled.len(),
+                pushed.len(),
+                session_id
+            );
+        }
+        let chunks = showcase::chunk_count(enabled.len());
+        let warning = format!("Shopee shows at most {} products; {} of {} were pushed", showcase::SESSION_LIMIT, pushed.len(), enabled.len());
+        jobs::log(app, job_id, warning.clone());
+        showcase::ShowcaseNotice {
+            total: enabled.len(),
+            pushed: pushed.len(),
+            chunk: chunk_index % chunks,
+            chunks,
+            rotating: rotation.is_some(),
+            warning,
+        }
+    });
+    
+    Ok(showcase::ReplaceSummary {
+        total: pushed.len(),
+        succeeded: pushed.len() - failures.len(),
+        failures,
+        showcase,
+    })
+}
+
+// Appends the batches from `checkpoint.next_batch` on, saving the checkpoint after each.
+// Returns every failure, including those recorded before the checkpoint was resumed, and
+// marks the job partial if there are any.
+async fn push_remaining_batches(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    email: &str,
+    password: &str,
+    mut checkpoint: job_recovery::ReplaceCheckpoint,
+) -> Vec<showcase::ItemFailure> {
+    let total = checkpoint.item_ids.len();
+    let (session_id, product_set_id) = (checkpoint.session_id.clone(), checkpoint.product_set_id);
+    let batches: Vec<Vec<i32>> = checkpoint.item_ids.chunks(showcase::PUSH_BATCH_SIZE).map(<[i32]>::to_vec).collect();
+    let mut done = total.min(checkpoint.next_batch * showcase::PUSH_BATCH_SIZE);
+    emit_replace_progress(app, &session_id, product_set_id, done, total);
+    jobs::progress(app, job_id, done, Some(total));
+    for batch in batches.iter().skip(checkpoint.next_batch) {
+        job_recovery::save(app, job_id, &job_recovery::Checkpoint::Replace(checkpoint.clone()));
+        let body = serde_json::json!({
+            "email": email,
+            "password": password,
+            "shopee_account_id": checkpoint.shopee_account_id,
+            "session_id": session_id,
+            "product_set_id": product_set_id,
+            "item_ids": batch
+        });
+        let result: Result<ApiResponse<serde_json::Value>, CommandError> = live_action_request(app, checkpoint.shopee_account_id, "/api/shopee-live/add-products", &body).await;
+        let error = match result {
+            Ok(response) if response.success => None,
+            Ok(response) => Some(response.message.unwrap_or_else(|| "Failed to add products".to_string())),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(error) = error {
+            jobs::log(app, job_id, format!("{} item(s) failed: {}", batch.len(), error));
+            let offset = checkpoint.next_batch * showcase::PUSH_BATCH_SIZE;
+            checkpoint.failures.extend(batch.iter().enumerate().map(|(i, id)| showcase::ItemFailure {
+                index: offset + i,
+                product_set_item_id: *id,
+                error: error.clone(),
+            }));
+        }
+        checkpoint.next_batch += 1;
+        done += batch.len();
+        emit_replace_progress(app, &session_id, product_set_id, done, total);
+        jobs::progress(app, job_id, done, Some(total));
+    }
+    if !checkpoint.failures.is_empty() {
+        jobs::partial(app, job_id, format!("{} of {} item(s) failed to push", checkpoint.failures.len(), total));
+    }
+    checkpoint.failures
+}
+
+// Carries on with a push that was cut off. If it stopped before the replace call went
+// through, the whole push is done again.
+pub(crate) async fn resume_replace(
+    app: &tauri::AppHandle,
+    job_id: &str,
+    email: &str,
+    password: &str,
+    checkpoint: job_recovery::ReplaceCheckpoint,
+) -> Result<showcase::ReplaceSummary, CommandError> {
+    let (shopee_account_id, session_id) = (checkpoint.shopee_account_id, checkpoint.session_id.clone());
+    if active_session_id(email, password, shopee_account_id).await?.as_deref() != Some(session_id.as_str()) {
+        return Err(format!("Session {} is no longer live", session_id).into());
+    }
+    if checkpoint.next_batch == 0 {
+        return replace_products_in_job(app, job_id, email, password, shopee_account_id, &session_id, checkpoint.product_set_id).await;
+    }
+    
+    jobs::log(app, job_id, format!("Resuming with {} item(s) left to push", checkpoint.remaining_items()));
+    let total = checkpoint.item_ids.len();
+    let failures = push_remaining_batches(app, job_id, email, password, checkpoint).await;
+    Ok(showcase::ReplaceSummary {
+        total,
+        succeeded: total - failures.len(),
+        failures,
+        showcase: None,
+    })
+}
+
+#[tauri::command]
+async fn replace_products(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    product_set_id: i32,
+    dry_run: Option<bool>,
+) -> Result<LiveOutcome<showcase::ReplaceSummary>, CommandError> {
+    if dry_run.unwrap_or(false) {
+        let actions = plan_replace(&app, &email, &password, shopee_account_id, &session_id, product_set_id).await?;
+        return Ok(LiveOutcome::Planned { actions });
+    }
+    let result = replace_products_request(&app, &email, &password, shopee_account_id, &session_id, product_set_id)
+        .await
+        .inspect_err(|e| diagnostics::record_failure(&app, &session_id, "replace_products", e))?;
+    audit::record(
+        &app,
+        "replace_products",
+        Some(format!("session:{}", session_id)),
+        serde_json::json!({ "shopee_account_id": shopee_account_id, "product_set_id": product_set_id, "summary": result }),
+    );
+    // The showcase keeps the items that went in; the ones that didn't are reported per item
+    result.check_failures()?;
+    Ok(LiveOutcome::Done { result })
+}
+
+// Rotates an oversized set through the session's showcase in limit-sized chunks
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn start_showcase_rotation(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ShowcaseRotationState>,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    product_set_id: i32,
+    interval_minutes: Option<u64>,
+    dry_run: Option<bool>,
+) -> Result<LiveOutcome<showcase::RotationInfo>, CommandError> {
+    let interval_minutes = interval_minutes.unwrap_or_else(|| settings::current().automation.showcase_interval_minutes);
+    if dry_run.unwrap_or(false) {
+        let mut actions = plan_live_target(&email, &password, shopee_account_id, &session_id).await?;
+        let set = find_product_set(&email, &password, product_set_id).await?;
+        let enabled = showcase_item_ids(&app, &set)?;
+        actions.push(PlannedAction::new(
+            "rotate",
+            format!(
+                "Push \"{}\" in {} chunk(s) of up to {} items, switching every {} minutes",
+                set.name,
+                showcase::chunk_count(enabled.len()),
+                showcase::SESSION_LIMIT,
+                interval_minutes
+            ),
+        ));
+        return Ok(LiveOutcome::Planned { actions });
+    }
+    let result = showcase::start(
+        &app,
+        &state,
+        LiveSyncBinding {
+            product_set_id,
+            shopee_account_id,
+            session_id,
+            email,
+            password,
+        },
+        interval_minutes,
+    );
+    Ok(LiveOutcome::Done { result })
+}
+
+#[tauri::command]
+async fn stop_showcase_rotation(state: tauri::State<'_, ShowcaseRotationState>, session_id: String) -> Result<bool, String> {
+    Ok(state.stop(&session_id))
+}
+
+#[tauri::command]
+async fn get_showcase_rotations(state: tauri::State<'_, ShowcaseRotationState>) -> Result<Vec<showcase::RotationInfo>, String> {
+    Ok(state.list())
+}
+
+// Scheduler commands
+#[tauri::command]
+async fn create_schedule(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SchedulerState>,
+    email: String,
+    password: String,
+    spec: scheduler::ScheduleSpec,
+) -> Result<scheduler::ScheduleInfo, CommandError> {
+    // Catch a wrong set or account up front rather than on the first run
+    find_product_set(&email, &password, spec.product_set_id).await?;
+    find_shopee_account(&email, &password, spec.shopee_account_id).await?;
+    let schedule = scheduler::start(&app, &state, email, password, spec)?;
+    audit::record(&app, "create_schedule", Some(format!("schedule:{}", schedule.id)), serde_json::json!({ "schedule": schedule }));
+    Ok(schedule)
+}
+
+#[tauri::command]
+async fn delete_schedule(app: tauri::AppHandle, state: tauri::State<'_, SchedulerState>, schedule_id: String) -> Result<bool, String> {
+    let deleted = scheduler::delete(&app, &state, &schedule_id)?;
+    if deleted {
+        audit::record(&app, "delete_schedule", Some(format!("schedule:{}", schedule_id)), serde_json::Value::Null);
+    }
+    Ok(deleted)
+}
+
+#[tauri::command]
+async fn list_schedules(state: tauri::State<'_, SchedulerState>) -> Result<Vec<scheduler::ScheduleInfo>, String> {
+    Ok(state.list())
+}
+
+// Job runner commands
+#[tauri::command]
+async fn submit_jobs(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, JobRunnerState>,
+    email: String,
+    password: String,
+    jobs: Vec<job_runner::JobRequest>,
+) -> Result<Vec<jobs::Job>, String> {
+    if jobs.is_empty() {
+        return Err("No jobs to run".to_string());
+    }
+    Ok(job_runner::submit(&app, &state, email, password, jobs))
+}
+
+// Jobs commands
+#[tauri::command]
+async fn get_jobs(state: tauri::State<'_, JobsState>, shopee_account_id: Option<i32>) -> Result<Vec<jobs::Job>, String> {
+    Ok(state.list(shopee_account_id))
+}
+
+#[tauri::command]
+async fn get_job(state: tauri::State<'_, JobsState>, job_id: String) -> Result<jobs::Job, String> {
+    state.get(&job_id).ok_or_else(|| format!("Job {} not found", job_id))
+}
+
+// Finished jobs kept across restarts
+#[tauri::command]
+async fn get_job_history(app: tauri::AppHandle, shopee_account_id: Option<i32>, limit: Option<usize>) -> Result<Vec<jobs::Job>, String> {
+    jobs::history(&app, shopee_account_id, limit.unwrap_or(100).clamp(1, 1000))
+}
+
+// Pausing stops a job's timer and holds it before its next action; cancelling stops its task.
+// One-shot jobs such as imports can't be paused or cancelled.
+#[tauri::command]
+async fn pause_job(app: tauri::AppHandle, job_id: String) -> Result<jobs::Job, String> {
+    jobs::pause(&app, &job_id)
+}
+
+#[tauri::command]
+async fn resume_job(app: tauri::AppHandle, job_id: String) -> Result<jobs::Job, String> {
+    jobs::resume(&app, &job_id)
+}
+
+// Pauses every schedule, rotation, automation engine and queued action at once; also in the tray menu
+#[tauri::command]
+async fn pause_all_automation(app: tauri::AppHandle) -> Result<jobs::AutomationStatus, String> {
+    Ok(jobs::pause_all(&app))
+}
+
+#[tauri::command]
+async fn resume_all_automation(app: tauri::AppHandle) -> Result<jobs::AutomationStatus, String> {
+    Ok(jobs::resume_all(&app))
+}
+
+#[tauri::command]
+async fn get_automation_status(jobs_state: tauri::State<'_, JobsState>) -> Result<jobs::AutomationStatus, String> {
+    Ok(jobs_state.status())
+}
+
+#[tauri::command]
+async fn cancel_job(app: tauri::AppHandle, job_id: String) -> Result<jobs::Job, String> {
+    jobs::cancel(&app, &job_id)
+}
+
+// Jobs cut off when the app last crashed or was force-quit, offered for resuming on startup
+#[tauri::command]
+async fn get_interrupted_jobs(app: tauri::AppHandle) -> Result<Vec<job_recovery::InterruptedJob>, String> {
+    Ok(job_recovery::list(&app))
+}
+
+// Resumes the given interrupted jobs, or all of them, as new jobs
+#[tauri::command]
+async fn resume_interrupted_jobs(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, JobRunnerState>,
+    email: String,
+    password: String,
+    job_ids: Option<Vec<String>>,
+) -> Result<Vec<jobs::Job>, String> {
+    job_recovery::resume(&app, &state, email, password, job_ids.as_deref())
+}
+
+#[tauri::command]
+async fn discard_interrupted_jobs(app: tauri::AppHandle, job_ids: Option<Vec<String>>) -> Result<usize, String> {
+    job_recovery::discard(&app, job_ids.as_deref())
+}
+
+// Live sync commands
+#[tauri::command]
+async fn enable_live_sync(
+    state: tauri::State<'_, LiveSyncState>,
+    email: String,
+    password: String,
+    product_set_id: i32,
+    shopee_account_id: i32,
+    session_id: String,
+) -> Result<(), CommandError> {
+    state.enable(LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        session_id,
+        email,
+        password,
+    });
+    Ok(())
+}
+
+#[tauri::command]
+async fn disable_live_sync(state: tauri::State<'_, LiveSyncState>, product_set_id: i32) -> Result<bool, CommandError> {
+    Ok(state.disable(product_set_id))
+}
+
+#[tauri::command]
+async fn get_live_syncs(state: tauri::State<'_, LiveSyncState>) -> Result<Vec<LiveSyncBinding>, CommandError> {
+    Ok(state.list())
+}
+
+#[tauri::command]
+async fn clear_products(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    dry_run: Option<bool>,
+) -> Result<LiveOutcome<()>, CommandError> {
+    if dry_run.unwrap_or(false) {
+        let mut actions = plan_live_target(&email, &password, shopee_account_id, &session_id).await?;
+        actions.push(PlannedAction::new("clear", format!("Remove every product from session {}'s showcase", session_id)));
+        return Ok(LiveOutcome::Planned { actions });
+    }
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id
+    });
+    // What the showcase holds now, so the clear can be undone
+    let before = fetch_showcase_items(&email, &password, shopee_account_id, &session_id)
+        .await
+        .inspect_err(|e| eprintln!("[UNDO] Failed to snapshot session {} before clear: {}", session_id, e))
+        .ok();
+    
+    let result: Result<ApiResponse<serde_json::Value>, CommandError> = live_action_request(&app, shopee_account_id, "/api/shopee-live/clear-products", &body).await;
+    let response = result.inspect_err(|e| diagnostics::record_failure(&app, &session_id, "clear_products", e))?;
+    
+    if !response.success {
+        let error: CommandError = response.message.unwrap_or_else(|| "Failed to clear products".to_string()).into();
+        diagnostics::record_failure(&app, &session_id, "clear_products", &error);
+        return Err(error);
+    }
+    
+    if let Some(items) = before.filter(|items| !items.is_empty()) {
+        if let Err(e) = clear_undo::snapshot_session(&app, &session_id, shopee_account_id, items) {
+            eprintln!("[UNDO] Failed to snapshot session {}: {}", session_id, e);
+        }
+    }
+    audit::record(
+        &app,
+        "clear_products",
+        Some(format!("session:{}", session_id)),
+        serde_json::json!({ "shopee_account_id": shopee_account_id }),
+    );
+    
+    Ok(LiveOutcome::Done { result: () })
+}
+
+#[derive(Debug, Serialize)]
+pub struct UndoOutcome {
+    pub product_set_id: Option<i32>,
+    pub restored_items: usize,
+}
+
+// Reverses the most recent clear of a product set (re-adding its items) or of a live
+// session's showcase (putting back the items it held). Pass exactly one scope.
+#[tauri::command]
+async fn undo_last_clear(
+    app: tauri::AppHandle,
+    email: String,
+    password: String,
+    product_set_id: Option<i32>,
+    session_id: Option<String>,
+) -> Result<UndoOutcome, CommandError> {
+    match (product_set_id, session_id) {
+        (Some(product_set_id), None) => {
+            let snapshot = clear_undo::take_set(&app, product_set_id)?
+                .ok_or_else(|| CommandError::from("Nothing to undo for this product set"))?;
+            let mut failed = AggregateError::new(snapshot.items.len());
+            let mut remaining = Vec::new();
+            for (n, chunk) in snapshot.items.chunks(product_items::UPLOAD_CHUNK_SIZE).enumerate() {
+                match add_product_set_items(app.clone(), email.clone(), password.clone(), product_set_id, chunk.to_vec()).await {
+                    Ok(_) => failed.succeeded += chunk.len(),
+                    Err(e) => {
+                        let urls: Vec<String> = chunk.iter().map(|item| item.url.clone()).collect();
+                        failed.push_batch(n * product_items::UPLOAD_CHUNK_SIZE, &urls, e);
+                        remaining.extend_from_slice(chunk);
+                    }
+                }
+            }
+            let restored_items = failed.succeeded;
+            if !remaining.is_empty() {
+                // Keep only the items that didn't go back, so a retry doesn't add the rest twice
+                clear_undo::snapshot_set(&app, product_set_id, remaining)?;
+            }
+            if restored_items > 0 {
+                audit::record(
+                    &app,
+                    "undo_last_clear",
+                    Some(format!("product_set:{}", product_set_id)),
+                    serde_json::json!({ "restored_items": restored_items }),
+                );
+            }
+            failed.into_result()?;
+            Ok(UndoOutcome { product_set_id: Some(product_set_id), restored_items })
+        }
+        (None, Some(session_id)) => {
+            let cleared = clear_undo::take_session(&app, &session_id)?
+                .filter(|cleared| !cleared.items.is_empty())
+                .ok_or_else(|| CommandError::from("Nothing to undo for this session"))?;
+            let body = serde_json::json!({
+                "email": email,
+                "password": password,
+                "shopee_account_id": cleared.shopee_account_id,
+                "session_id": session_id,
+                "items": cleared.items
+            });
+            let result: Result<ApiResponse<serde_json::Value>, CommandError> = live_action_request(&app, cleared.shopee_account_id, "/api/shopee-live/replace-products", &body).await;
+            let error = match result {
+                Ok(response) if response.success => None,
+                Ok(response) => Some(CommandError::from(response.message.unwrap_or_else(|| "Failed to restore showcase".to_string()))),
+                Err(e) => Some(e),
+            };
+            if let Some(error) = error {
+                diagnostics::record_failure(&app, &session_id, "undo_last_clear", &error);
+                // Keep the snapshot so the undo can be retried
+                clear_undo::restore_session(&app, &session_id, cleared)?;
+                return Err(error);
+            }
+            
+            let restored_items = cleared.items.len();
+            audit::record(
+                &app,
+                "undo_last_clear",
+                Some(format!("session:{}", session_id)),
+                serde_json::json!({ "product_set_id": cleared.product_set_id, "restored_items": restored_items }),
+            );
+            Ok(UndoOutcome { product_set_id: cleared.product_set_id, restored_items })
+        }
+        _ => Err("Pass either a product set or a session to undo".into()),
+    }
+}
+
+// Puts back the showcase as it was before the session's last replace. The snapshot is
+// consumed, so a second rollback needs another replace first.
+#[tauri::command]
+async fn rollback_showcase(app: tauri::AppHandle, email: String, password: String, shopee_account_id: i32, session_id: String) -> Result<usize, CommandError> {
+    let snapshot = showcase_snapshots::take(&app, &session_id)?
+        .ok_or_else(|| CommandError::from("No showcase snapshot for this session"))?;
+    if snapshot.shopee_account_id != shopee_account_id {
+        showcase_snapshots::restore(&app, &session_id, snapshot)?;
+        return Err("The snapshot for this session belongs to another account".into());
+    }
+    
+    let body = serde_json::json!({
+        "email": email,
+        "password": password,
+        "shopee_account_id": shopee_account_id,
+        "session_id": session_id,
+        "items": snapshot.items
+    });
+    let result: Result<ApiResponse<serde_json::Value>, CommandError> = live_action_request(&app, shopee_account_id, "/api/shopee-live/replace-products", &body).await;
+    let error = match result {
+        Ok(response) if response.success => None,
+        Ok(response) => Some(CommandError::from(response.message.unwrap_or_else(|| "Failed to roll back showcase".to_string()))),
+        Err(e) => Some(e),
+    };
+    if let Some(error) = error {
+        diagnostics::record_failure(&app, &session_id, "rollback_showcase", &error);
+        // Keep the snapshot so the rollback can be retried
+        showcase_snapshots::restore(&app, &session_id, snapshot)?;
+        return Err(error);
+    }
+    
+    audit::record(
+        &app,
+        "rollback_showcase",
+        Some(format!("session:{}", session_id)),
+        serde_json::json!({ "shopee_account_id": shopee_account_id, "items": snapshot.items.len() }),
+    );
+    Ok(snapshot.items.len())
+}
+
+#[tauri::command]
+async fn get_blackout_windows(app: tauri::AppHandle) -> Result<Vec<BlackoutWindow>, String> {
+    blackout::load(&app)
+}
+
+#[tauri::command]
+async fn set_blackout_windows(app: tauri::AppHandle, windows: Vec<BlackoutWindow>) -> Result<(), String> {
+    blackout::save(&app, &windows)
+}
+
+#[tauri::command]
+async fn explain_last_failure(app: tauri::AppHandle, session_id: String) -> Result<FailureExplanation, String> {
+    Ok(diagnostics::explain(&app, &session_id))
+}
+
+// QR Code commands
+async fn request_shopee_qr(proxy: Option<&str>) -> Result<ShopeeQRData, String> {
+    let client = shopee_api_client(proxy)?;
+    Ok(client.generate_qr().await?)
+}
+
+#[tauri::command]
+async fn generate_shopee_qr(app: tauri::AppHandle, account_id: Option<i32>) -> Result<ShopeeQRData, String> {
+    let qr = request_shopee_qr(account_proxy(&app, account_id).as_deref()).await?;
+    image_cache::put_qr(&app, &qr);
+    // A new login attempt starts here, so earlier replacements are no longer polled
+    app.state::<QrState>().regenerated.lock().unwrap().clear();
+    Ok(qr)
+}
+
+#[tauri::command]
+async fn check_qr_status(app: tauri::AppHandle, qrcode_id: String, account_id: Option<i32>) -> Result<AppQRStatus, String> {
+    let proxy = account_proxy(&app, account_id);
+    let client = shopee_api_client(proxy.as_deref())?;
+    let data = client.qr_status(&qrcode_id).await?;
+    
+    // Expired QR codes are replaced transparently so the user doesn't have to click again
+    let regenerated = if data.status == "EXPIRED" {
+        let existing = app.state::<QrState>().regenerated.lock().unwrap().get(&qrcode_id).cloned();
+        match existing {
+            Some(qr) => Some(qr),
+            None => {
+                let qr = request_shopee_qr(proxy.as_deref()).await?;
+                image_cache::put_qr(&app, &qr);
+                app.state::<QrState>().regenerated.lock().unwrap().insert(qrcode_id.clone(), qr.clone());
+                if let Err(e) = app.emit("qr-regenerated", qr.clone()) {
+                    eprintln!("Failed to emit qr-regenerated: {}", e);
+                }
+                Some(qr)
+            }
+        }
+    } else {
+        None
+    };
+    if data.status == "CONFIRMED" {
+        notifications::notify(&app, notifications::NotificationEvent::QrConfirmed, &[]);
+    }
+    
+    Ok(AppQRStatus {
+        status: data.status,
+        qrcode_token: if data.qrcode_token.is_empty() { None } else { Some(data.qrcode_token) },
+        regenerated,
+    })
+}
+
+#[tauri::command]
+async fn qr_login(app: tauri::AppHandle, qrcode_token: String, account_id: Option<i32>) -> Result<LoginResult, String> {
+    let client = shopee_login_client(account_proxy(&app, account_id).as_deref())?;
+    let login_response = match client.qr_login(&qrcode_token).await {
+        Ok(login_response) => login_response,
+        Err(e) if e.http_status.is_some() => return Ok(LoginResult::failed(Some(e.message), None)),
+        Err(e) => return Err(e.message),
+    };
+    
+    if login_response.error != 0 {
+        return Ok(LoginResult::failed(login_response.error_msg, None));
+    }
+    
+    Ok(LoginResult::signed_in(&login_response.set_cookie_headers))
+}
+
+// Shopee error codes that mean the login needs a captcha or extra verification
+const LOGIN_CHALLENGE_ERRORS: &[i32] = &[98, 99, 77];
+
+fn detect_login_challenge(error: i32, data: Option<&serde_json::Value>) -> Option<LoginChallenge> {
+    let flow_no = data
+        .and_then(|d| d["ivs_flow_no"].as_str())
+        .map(|s| s.to_string());
+    let captcha_url = data
+        .and_then(|d| d["captcha_url"].as_str().or_else(|| d["url"].as_str()))
+        .map(|s| s.to_string());
+
+    let kind = if flow_no.is_some() {
+        "verification"
+    } else if captcha_url.is_some() || LOGIN_CHALLENGE_ERRORS.contains(&error) {
+        "captcha"
+    } else {
+        return None;
+    };
+
+    Some(LoginChallenge {
+        kind: kind.to_string(),
+        verify_url: captcha_url.unwrap_or_else(|| "https://shopee.co.id/buyer/login?next=https%3A%2F%2Fshopee.co.id%2F".to_string()),
+        flow_no,
+        raw: data.cloned(),
+    })
+}
+
+// Turns Shopee's answer to a password or OTP login into the result shown to the user
+fn login_result(login_response: shopee::LoginResponse) -> LoginResult {
+    if let Some(challenge) = detect_login_challenge(login_response.error, login_response.data.as_ref()) {
+        return LoginResult::failed(login_response.error_msg, Some(challenge));
+    }
+
+    if login_response.error != 0 {
+        let error_msg = login_response.error_msg.unwrap_or_else(|| format!("Shopee error {}", login_response.error));
+        return LoginResult::failed(Some(error_msg), None);
+    }
+
+    LoginResult::signed_in(&login_response.set_cookie_headers)
+}
+
+#[tauri::command]
+async fn shopee_password_login(app: tauri::AppHandle, account_id: Option<i32>, login: String, password: String) -> Result<LoginResult, String> {
+    let client = shopee_login_client(account_proxy(&app, account_id).as_deref())?;
+    match client.password_login(login.trim(), &password).await {
+        Ok(login_response) => Ok(login_result(login_response)),
+        Err(e) if e.http_status.is_some() => Ok(LoginResult::failed(Some(e.message), None)),
+        Err(e) => Err(e.message),
+    }
+}
+
+// Indonesian numbers are sent to Shopee as 62xxxxxxxxxx
+fn normalize_phone(phone: &str) -> String {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    match digits.strip_prefix('0') {
+        Some(rest) => format!("62{}", rest),
+        None => digits,
+    }
+}
+
+#[tauri::command]
+async fn request_shopee_otp(app: tauri::AppHandle, state: tauri::State<'_, OtpState>, account_id: Option<i32>, phone: String) -> Result<OtpRequestResult, String> {
+    let phone = normalize_phone(&phone);
+    if phone.len() < 10 {
+        return Err("Invalid phone number".to_string());
+    }
+
+    let client = shopee_login_client(account_proxy(&app, account_id).as_deref())?;
+    let otp_response = client.send_otp(&phone).await?;
+
+    if let Some(challenge) = detect_login_challenge(otp_response.error, otp_response.data.as_ref()) {
+        return Ok(OtpRequestResult {
+            sent: false,
+            error_msg: otp_response.error_msg,
+            challenge: Some(challenge),
+        });
+    }
+
+    if otp_response.error != 0 {
+        return Ok(OtpRequestResult {
+            sent: false,
+            error_msg: otp_response.error_msg.or_else(|| Some(format!("Shopee error {}", otp_response.error))),
+            challenge: None,
+        });
+    }
+
+    let otp_seed = otp_response
+        .data
+        .as_ref()
+        .and_then(|d| d["otp_seed"].as_str())
+        .map(|s| s.to_string());
+    *state.pending.lock().unwrap() = Some(PendingOtp { phone, otp_seed });
+
+    Ok(OtpRequestResult {
+        sent: true,
+        error_msg: None,
+        challenge: None,
+    })
+}
+
+#[tauri::command]
+async fn verify_shopee_otp(app: tauri::AppHandle, state: tauri::State<'_, OtpState>, account_id: Option<i32>, code: String) -> Result<LoginResult, String> {
+    let pending = state
+        .pending
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "No OTP has been requested".to_string())?;
+
+    let client = shopee_login_client(account_proxy(&app, account_id).as_deref())?;
+    let result = match client.otp_login(&pending.phone, code.trim(), pending.otp_seed.as_deref()).await {
+        Ok(login_response) => login_result(login_response),
+        Err(e) if e.http_status.is_some() => LoginResult::failed(Some(e.message), None),
+        Err(e) => return Err(e.message),
+    };
+
+    // The code is single use, so drop the pending request once it has been accepted
+    if result.success {
+        *state.pending.lock().unwrap() = None;
+    }
+    Ok(result)
+}
+
+// Returns the account info together with any Set-Cookie headers Shopee sent back
+async fn request_account_info(cookies: &str, proxy: Option<&str>) -> Result<(ShopeeAccountInfo, Vec<String>), ShopeeApiFailure> {
+    let client = shopee_api_client(proxy).map_err(ShopeeApiFailure::message)?;
+    client.account_info(cookies).await
+}
+
+async fn fetch_account_info(cookies: &str, proxy: Option<&str>) -> Result<ShopeeAccountInfo, ShopeeApiFailure> {
+    request_account_info(cookies, proxy).await.map(|(info, _)| info)
+}
+
+#[tauri::command]
+async fn get_account_info(app: tauri::AppHandle, cookies: String, account_id: Option<i32>) -> Result<ShopeeAccountInfo, String> {
+    Ok(fetch_account_info(&cookies, account_proxy(&app, account_id).as_deref()).await?)
+}
+
+async fn find_shopee_account(email: &str, password: &str, account_id: i32) -> Result<ShopeeAccount, CommandError> {
+    let accounts = fetch_shopee_accounts(email, password).await?;
+    accounts
+        .data
+        .into_iter()
+        .find(|a| a.id == account_id)
+        .ok_or_else(|| format!("Shopee account {} not found", account_id).into())
+}
+
+#[tauri::command]
+async fn check_cookie_health(app: tauri::AppHandle, email: String, password: String, account_id: i32) -> Result<CookieHealth, CommandError> {
+    let account = find_shopee_account(&email, &password, account_id).await?;
+    let cookie = account
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("No cookie stored for this account"))?;
+    
+    let result = fetch_account_info(&cookie, account_proxies::proxy_for(&app, account_id).as_deref()).await;
+    let health = cookie_health::evaluate(account_id, &result);
+    cookie_health::record(&app, &health);
+    Ok(health)
+}
+
+// Shop identity for the account picker, looked up with the account's stored cookie
+#[tauri::command]
+async fn enrich_account(app: tauri::AppHandle, email: String, password: String, account_id: i32) -> Result<ShopProfile, CommandError> {
+    let account = find_shopee_account(&email, &password, account_id).await?;
+    let cookie = account
+        .cookie
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| CommandError::from("No cookie stored for this account"))?;
+    let proxy = account_proxies::proxy_for(&app, account_id);
+
+    let info = fetch_account_info(&cookie, proxy.as_deref()).await.map_err(String::from)?;
+
+    let client = shopee_api_client(proxy.as_deref())?;
+    let shop = client.shop_detail(&info.username, Some(&cookie)).await.map_err(String::from)?;
+
+    Ok(ShopProfile {
+        account_id,
+        shop_id: shop.shopid,
+        shop_name: shop.name,
+        avatar_url: shop
+            .account
+            .and_then(|a| a.portrait)
+            .filter(|p| !p.is_empty())
+            .map(|p| format!("https://down-id.img.susercontent.com/file/{}", p)),
+        follower_count: shop.follower_count,
+    })
+}
+
+#[tauri::command]
+async fn check_all_accounts(app: tauri::AppHandle, email: String, password: String, tag: Option<String>) -> Result<Vec<CookieHealth>, CommandError> {
+    let accounts = fetch_shopee_accounts(&email, &password).await?;
+    let accounts = account_tags::filter_by_tag(&app, accounts.data, tag.as_deref(), |a| a.id)?;
+    let names: std::collections::HashMap<i32, String> = accounts.iter().map(|a| (a.id, a.name.clone())).collect();
+    let results = cookie_health::check_all(&app, accounts).await;
+
+    // Every result is still recorded and emitted; accounts whose cookie couldn't be checked at
+    // all fail the batch, while verdicts like Expired are what the check is for
+    let mut unchecked = AggregateError::new(results.len());
+    for (index, health) in results.iter().enumerate() {
+        if health.status == CookieStatus::Unknown {
+            let name = names.get(&health.account_id).cloned().unwrap_or_else(|| format!("Account {}", health.account_id));
+            unchecked.push(index, name, health.detail.clone().unwrap_or_else(|| "Cookie could not be checked".to_string()));
+        } else {
+            unchecked.succeeded += 1;
+        }
+    }
+    unchecked.into_result()?;
+    Ok(results)
+}
+
+#[tauri::command]
+async fn start_cookie_keepalive(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, CookieKeepAliveState>,
+    email: String,
+    password: String,
+    interval_minutes: u64,
+) -> Result<(), String> {
+    cookie_keepalive::start(&app, &state, email, password, interval_minutes);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_cookie_keepalive(state: tauri::State<'_, CookieKeepAliveState>) -> Result<(), String> {
+    state.stop();
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_cookie_keepalive_status(state: tauri::State<'_, CookieKeepAliveState>) -> Result<bool, String> {
+    Ok(state.is_running())
+}
+
+#[cfg(feature = "load-test")]
+#[tauri::command]
+async fn run_load_test(config: load_test::LoadTestConfig) -> Result<load_test::LoadTestReport, String> {
+    load_test::run(config).await
+}
+
+#[cfg(not(feature = "load-test"))]
+#[tauri::command]
+async fn run_load_test() -> Result<(), String> {
+    Err("Load testing is only available in builds with the load-test feature".to_string())
+}
+
+// Moves this install to another PC; restore after update_machine_id on the new one
+#[tauri::command]
+async fn export_backup(
+    app: tauri::AppHandle,
+    path: String,
+    passphrase: Option<String>,
+    include_cookies: Option<bool>,
+) -> Result<backup::BackupSummary, String> {
+    backup::export(&app, std::path::Path::new(&path), passphrase.as_deref(), include_cookies.unwrap_or(false))
+}
+
+#[tauri::command]
+async fn import_backup(app: tauri::AppHandle, path: String, passphrase: Option<String>) -> Result<backup::BackupSummary, String> {
+    let summary = backup::import(&app, std::path::Path::new(&path), passphrase.as_deref())?;
+    audit::record(&app, "import_backup", None, serde_json::json!({ "path": path, "summary": summary }));
+    Ok(summary)
+}
+
+// What operators did on this install, newest first
+#[tauri::command]
+async fn query_audit_log(app: tauri::AppHandle, filters: Option<audit::AuditFilter>) -> Result<Vec<audit::AuditEntry>, String> {
+    audit::query(&app, &filters.unwrap_or_default())
+}
+
+// Discord webhook for job results and error alerts
+#[tauri::command]
+async fn get_discord_config(app: tauri::AppHandle) -> Result<notifications::DiscordConfig, String> {
+    notifications::discord_config(&app)
+}
+
+#[tauri::command]
+async fn set_discord_config(app: tauri::AppHandle, config: notifications::DiscordConfig) -> Result<notifications::DiscordConfig, String> {
+    notifications::set_discord_config(&app, config)
+}
+
+#[tauri::command]
+async fn test_discord_webhook(app: tauri::AppHandle) -> Result<(), String> {
+    notifications::test_discord(&app).await
+}
+
+// SMTP email alerts for critical events such as an expired license
+#[tauri::command]
+async fn get_smtp_config(app: tauri::AppHandle) -> Result<email_alerts::SmtpConfig, String> {
+    email_alerts::config(&app)
+}
+
+#[tauri::command]
+async fn set_smtp_config(app: tauri::AppHandle, config: email_alerts::SmtpConfig) -> Result<email_alerts::SmtpConfig, String> {
+    email_alerts::set_config(&app, config)
+}
+
+#[tauri::command]
+async fn test_smtp(app: tauri::AppHandle) -> Result<(), String> {
+    email_alerts::test(&app).await
+}
+
+// Outbound webhooks for automation events, signed with each webhook's secret
+#[tauri::command]
+async fn list_webhooks(app: tauri::AppHandle) -> Result<Vec<webhooks::Webhook>, String> {
+    webhooks::list(&app)
+}
+
+#[tauri::command]
+async fn add_webhook(app: tauri::AppHandle, webhook: webhooks::WebhookInput) -> Result<webhooks::Webhook, String> {
+    webhooks::add(&app, webhook)
+}
+
+#[tauri::command]
+async fn update_webhook(app: tauri::AppHandle, webhook_id: String, webhook: webhooks::WebhookInput) -> Result<webhooks::Webhook, String> {
+    webhooks::update(&app, &webhook_id, webhook)
+}
+
+#[tauri::command]
+async fn rotate_webhook_secret(app: tauri::AppHandle, webhook_id: String) -> Result<webhooks::Webhook, String> {
+    webhooks::rotate_secret(&app, &webhook_id)
+}
+
+#[tauri::command]
+async fn delete_webhook(app: tauri::AppHandle, webhook_id: String) -> Result<bool, String> {
+    webhooks::remove(&app, &webhook_id)
+}
+
+#[tauri::command]
+async fn test_webhook(app: tauri::AppHandle, webhook_id: String) -> Result<(), String> {
+    webhooks::test(&app, &webhook_id).await
+}
+
+// Disk space used by caches and history, and pruning by retention period
+#[tauri::command]
+async fn get_storage_usage(app: tauri::AppHandle) -> Result<storage_cleanup::StorageUsage, String> {
+    storage_cleanup::usage(&app)
+}
+
+#[tauri::command]
+async fn cleanup_storage(app: tauri::AppHandle, options: Option<storage_cleanup::CleanupOptions>) -> Result<storage_cleanup::CleanupSummary, String> {
+    storage_cleanup::cleanup(&app, options.unwrap_or_default())
+}
+
+// Which local data migrations have run, for support
+#[tauri::command]
+async fn get_storage_version(app: tauri::AppHandle) -> Result<migrations::StorageVersion, String> {
+    migrations::status(&app)
+}
+
+#[tauri::command]
+async fn get_autostart(app: tauri::AppHandle) -> Result<bool, String> {
+    background::autostart_enabled(&app)
+}
+
+// Launches the app minimized to the tray on OS login, with saved schedules resumed
+#[tauri::command]
+async fn set_autostart(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    background::set_autostart(&app, enabled)
 }
 
 #[tauri::command]
@@ -1006,37 +3850,262 @@ async fn close_window(window: tauri::Window) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must come first; a second launch (e.g. from a livekenceng:// link) is handed to this one
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| background::show_main_window(app)))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            Some(vec![background::BACKGROUND_ARG]),
+        ))
+        .manage(LiveSyncState::default())
+        .manage(AccountsCacheState::default())
+        .manage(ShowcaseRotationState::default())
+        .manage(ViewerPollingState::default())
+        .manage(HeartbeatState::default())
+        .manage(SessionWatchState::default())
+        .manage(AutoReplyState::default())
+        .manage(AutomationState::default())
+        .manage(ModerationState::default())
+        .manage(PinRotationState::default())
+        .manage(PromoCommentState::default())
+        .manage(SessionWatchdogState::default())
+        .manage(SessionReportState::default())
+        .manage(SchedulerState::default())
+        .manage(JobRunnerState::default())
+        .manage(JobsState::default())
+        .manage(JobRecoveryState::default())
+        .manage(DbState::default())
+        .manage(PacingState::default())
+        .manage(DiagnosticsState::default())
+        .manage(OtpState::default())
+        .manage(QrState::default())
+        .manage(MachineBindingState::default())
+        .manage(CookieKeepAliveState::default())
+        .manage(WatchFolderState::default())
+        .manage(OperatorState::default())
+        .manage(ConfirmationState::default())
+        .manage(RemoteAssistState::default())
+        .manage(NotificationState::default())
+        .manage(EmailAlertState::default())
+        .manage(DeepLinkState::default())
+        .manage(UpdateState::default())
+        .manage(ClipboardWatchState::default())
+        .setup(|app| {
+            settings::init(app.handle());
+            db::open(app.handle())?;
+            migrations::run(app.handle());
+            job_recovery::restore(app.handle());
+            encrypted_store::rekey_files(app.handle());
+            scheduler::restore(app.handle());
+            background::setup(app.handle())?;
+            deep_link::setup(app.handle());
+            updates::start(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             get_machine_id,
             get_user_machine_id,
+            start_machine_binding_watch,
+            stop_machine_binding_watch,
             close_window,
+            export_backup,
+            import_backup,
+            get_storage_version,
+            get_storage_usage,
+            get_discord_config,
+            set_discord_config,
+            test_discord_webhook,
+            get_smtp_config,
+            set_smtp_config,
+            test_smtp,
+            list_webhooks,
+            add_webhook,
+            update_webhook,
+            rotate_webhook_secret,
+            delete_webhook,
+            test_webhook,
+            cleanup_storage,
+            query_audit_log,
+            get_autostart,
+            set_autostart,
             login,
+            start_remote_assist,
+            stop_remote_assist,
+            get_remote_assist,
+            list_operators,
+            add_operator,
+            remove_operator,
+            switch_operator,
+            get_active_operator,
             redeem_license,
+            take_pending_deep_link,
+            check_for_update,
+            get_announcements,
+            mark_announcements_read,
             update_machine_id,
             change_password,
             get_shopee_accounts,
             add_shopee_account,
             update_shopee_account,
             delete_shopee_account,
+            get_cookie_history,
+            rollback_account_cookie,
+            list_archived_accounts,
+            restore_account,
+            import_shopee_accounts,
+            export_shopee_accounts,
+            set_account_tags,
+            get_account_tags,
+            get_accounts_by_tag,
+            format_prices,
+            set_account_proxy,
+            get_account_proxies,
+            get_account_notes,
+            set_account_notes,
+            get_annotation,
+            list_annotations,
+            set_annotation,
+            delete_annotation,
             get_niches,
             create_niche,
             update_niche,
             delete_niche,
             get_product_sets,
+            get_ipc_encodings,
+            get_niches_bulk,
+            get_product_sets_bulk,
             create_product_set,
             update_product_set,
             delete_product_set,
             add_product_set_items,
+            parse_shopee_url,
+            import_shop_products,
+            search_shopee_products,
+            add_search_results_to_set,
+            get_cached_image,
+            get_cached_qr_image,
+            clear_image_cache,
+            reorder_product_set_items,
+            set_product_set_item_enabled,
+            get_disabled_product_set_items,
+            get_product_set_item_order,
+            validate_product_set,
+            get_product_set_metadata,
+            import_product_set_items,
             delete_product_set_item,
+            move_product_set_items,
             clear_product_set_items,
+            fork_read_only_set,
+            clone_product_set,
+            merge_product_sets,
+            diff_product_sets,
+            get_product_set_templates,
+            import_product_set_template,
+            start_watch_folder,
+            stop_watch_folder,
+            get_watch_folder,
+            start_clipboard_watch,
+            stop_clipboard_watch,
+            get_clipboard_watch_status,
             get_session_ids,
+            watch_for_session,
+            stop_watching_for_session,
+            get_watched_accounts,
+            create_live_session,
+            end_live_session,
+            get_live_stats,
+            share_live_session,
+            get_push_url,
+            start_session_watchdog,
+            stop_session_watchdog,
+            get_session_watchdogs,
+            start_session_heartbeat,
+            stop_session_heartbeat,
+            get_heartbeat_sessions,
+            start_viewer_polling,
+            stop_viewer_polling,
+            get_viewer_polling_sessions,
+            get_session_report,
+            get_session_timeseries,
+            pin_product,
+            unpin_product,
+            start_pin_rotation,
+            pause_pin_rotation,
+            resume_pin_rotation,
+            stop_pin_rotation,
+            get_pin_rotations,
+            get_live_comments,
+            send_live_comment,
+            get_settings,
+            update_settings,
+            set_api_environment,
+            get_pacing_status,
+            set_pacing_profile,
+            start_comment_blast,
+            stop_comment_blast,
+            get_comment_blasts,
+            get_automation_rules,
+            save_automation_rules,
+            start_automation,
+            stop_automation,
+            set_session_rule_enabled,
+            get_automation_sessions,
+            get_auto_reply_rules,
+            set_auto_reply_rules,
+            start_auto_reply,
+            stop_auto_reply,
+            get_auto_reply_sessions,
+            get_moderation_config,
+            set_moderation_config,
+            start_comment_moderation,
+            stop_comment_moderation,
+            get_moderated_sessions,
             replace_products,
+            start_showcase_rotation,
+            stop_showcase_rotation,
+            get_showcase_rotations,
+            create_schedule,
+            delete_schedule,
+            list_schedules,
+            submit_jobs,
+            get_jobs,
+            get_job,
+            get_job_history,
+            get_interrupted_jobs,
+            resume_interrupted_jobs,
+            discard_interrupted_jobs,
+            pause_job,
+            resume_job,
+            pause_all_automation,
+            resume_all_automation,
+            get_automation_status,
+            cancel_job,
             clear_products,
+            undo_last_clear,
+            rollback_showcase,
+            explain_last_failure,
+            get_blackout_windows,
+            set_blackout_windows,
+            enable_live_sync,
+            disable_live_sync,
+            get_live_syncs,
             generate_shopee_qr,
             check_qr_status,
             qr_login,
+            shopee_password_login,
+            request_shopee_otp,
+            verify_shopee_otp,
             get_account_info,
+            check_cookie_health,
+            enrich_account,
+            check_all_accounts,
+            start_cookie_keepalive,
+            stop_cookie_keepalive,
+            get_cookie_keepalive_status,
+            run_load_test,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,312 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use botgacor_core::shopee::LiveStats;
+
+use crate::live_timeseries::CommentRate;
+
+// Shopee rejects live covers larger than this
+const MAX_COVER_BYTES: u64 = 2 * 1024 * 1024;
+const MIN_POLL_SECS: u64 = 5;
+// Stable counts stretch the interval up to this multiple of the requested one
+const MAX_BACKOFF_FACTOR: u32 = 8;
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+const MIN_HEARTBEAT_SECS: u64 = 15;
+const SESSION_WATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct CoverImage {
+    pub mime: &'static str,
+    pub base64: String,
+}
+
+pub fn read_cover(path: &Path) -> Result<CoverImage, String> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    let mime = match extension.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        _ => return Err("Cover image must be a JPG or PNG file".to_string()),
+    };
+    let size = std::fs::metadata(path).map_err(|e| format!("Failed to read cover image: {}", e))?.len();
+    if size > MAX_COVER_BYTES {
+        return Err(format!("Cover image must be at most {} MB", MAX_COVER_BYTES / 1024 / 1024));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read cover image: {}", e))?;
+    Ok(CoverImage {
+        mime,
+        base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareChannel {
+    // Posts the live to the shop's Shopee Feed
+    Feed,
+    // Only returns a link to paste elsewhere
+    #[default]
+    Link,
+}
+
+impl ShareChannel {
+    pub fn as_api(self) -> &'static str {
+        match self {
+            ShareChannel::Feed => "feed",
+            ShareChannel::Link => "link",
+        }
+    }
+}
+
+// Tags a share link so traffic from outside Shopee can be told apart in analytics
+pub fn with_utm(share_url: &str, session_id: &str) -> Result<String, String> {
+    let mut url = url::Url::parse(share_url).map_err(|e| format!("Invalid share URL: {}", e))?;
+    url.query_pairs_mut()
+        .append_pair("utm_source", "botgacor")
+        .append_pair("utm_medium", "share")
+        .append_pair("utm_campaign", &format!("live_{}", session_id));
+    Ok(url.into())
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ViewerUpdateEvent {
+    shopee_account_id: i32,
+    session_id: String,
+    stats: LiveStats,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LoopStoppedEvent {
+    session_id: String,
+    reason: String,
+}
+
+#[derive(Default)]
+pub struct ViewerPollingState {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl ViewerPollingState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.tasks.lock().unwrap().remove(session_id) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn sessions(&self) -> Vec<String> {
+        self.tasks.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.tasks.lock().unwrap().remove(session_id);
+    }
+}
+
+fn emit_stopped(app: &AppHandle, event_name: &str, session_id: &str, reason: &str) {
+    let event = LoopStoppedEvent {
+        session_id: session_id.to_string(),
+        reason: reason.to_string(),
+    };
+    if let Err(e) = app.emit(event_name, event) {
+        eprintln!("Failed to emit {}: {}", event_name, e);
+    }
+}
+
+// Emits `viewer-update` with fresh counters every interval and stores each sample, along with
+// the comment rate from the comment feed, for the session's time series. While the counts
+// don't change the interval doubles (up to MAX_BACKOFF_FACTOR times), and any change resets
+// it. Polling stops on its own once Shopee reports the session is no longer live.
+pub fn start_polling(
+    app: &AppHandle,
+    state: &ViewerPollingState,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    interval_secs: u64,
+) {
+    state.stop(&session_id);
+    crate::session_reports::track(app, shopee_account_id, &session_id);
+
+    let base = Duration::from_secs(interval_secs.max(MIN_POLL_SECS));
+    let app = app.clone();
+    let key = session_id.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut factor = 1;
+        let mut failures = 0;
+        let mut last: Option<(i64, i64, i64, i64)> = None;
+        let mut comment_rate = CommentRate::default();
+        let reason = loop {
+            match crate::fetch_live_stats(&app, &email, &password, shopee_account_id, &session_id).await {
+                Ok(stats) => {
+                    failures = 0;
+                    let comments_per_min = comment_rate.sample(&email, &password, shopee_account_id, &session_id).await;
+                    crate::live_timeseries::record(&app, shopee_account_id, &session_id, &stats, comments_per_min);
+                    let counters = (stats.viewers, stats.likes, stats.shares, stats.items_sold);
+                    factor = if last == Some(counters) { (factor * 2).min(MAX_BACKOFF_FACTOR) } else { 1 };
+                    last = Some(counters);
+
+                    let is_live = stats.is_live();
+                    let event = ViewerUpdateEvent {
+                        shopee_account_id,
+                        session_id: session_id.clone(),
+                        stats,
+                    };
+                    if let Err(e) = app.emit("viewer-update", event) {
+                        eprintln!("Failed to emit viewer-update: {}", e);
+                    }
+                    if !is_live {
+                        break "Session ended".to_string();
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("[VIEWER POLLING] Session {} poll failed ({}): {}", session_id, failures, e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        break format!("Stopped after {} failed polls: {}", failures, e);
+                    }
+                }
+            }
+            tokio::time::sleep(base * factor).await;
+        };
+
+        app.state::<ViewerPollingState>().finish(&session_id);
+        emit_stopped(&app, "viewer-polling-stopped", &session_id, &reason);
+    });
+
+    state.tasks.lock().unwrap().insert(key, task);
+}
+
+#[derive(Default)]
+pub struct HeartbeatState {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl HeartbeatState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.tasks.lock().unwrap().remove(session_id) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn sessions(&self) -> Vec<String> {
+        self.tasks.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.tasks.lock().unwrap().remove(session_id);
+    }
+}
+
+// Sends the session heartbeat Shopee's streaming client sends, so long unattended streams
+// aren't ended as idle. Stops after repeated failures, which usually means the session is over.
+pub fn start_heartbeat(
+    app: &AppHandle,
+    state: &HeartbeatState,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    interval_secs: Option<u64>,
+) {
+    state.stop(&session_id);
+
+    let interval = Duration::from_secs(interval_secs.unwrap_or_else(|| crate::settings::current().automation.heartbeat_secs).max(MIN_HEARTBEAT_SECS));
+    let app = app.clone();
+    let key = session_id.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut failures = 0;
+        loop {
+            match crate::send_session_heartbeat(&email, &password, shopee_account_id, &session_id).await {
+                Ok(()) => failures = 0,
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("[HEARTBEAT] Session {} heartbeat failed ({}): {}", session_id, failures, e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        crate::diagnostics::record_failure(&app, &session_id, "heartbeat", &e);
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+        app.state::<HeartbeatState>().finish(&session_id);
+        emit_stopped(&app, "heartbeat-stopped", &session_id, "Heartbeat failed repeatedly");
+    });
+
+    state.tasks.lock().unwrap().insert(key, task);
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SessionDetectedEvent {
+    shopee_account_id: i32,
+    session_id: String,
+}
+
+// Accounts being watched for a new live, keyed by account ID
+#[derive(Default)]
+pub struct SessionWatchState {
+    tasks: Mutex<HashMap<i32, JoinHandle<()>>>,
+}
+
+impl SessionWatchState {
+    pub fn stop(&self, shopee_account_id: i32) -> bool {
+        match self.tasks.lock().unwrap().remove(&shopee_account_id) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn accounts(&self) -> Vec<i32> {
+        self.tasks.lock().unwrap().keys().copied().collect()
+    }
+}
+
+// Asks Shopee directly which live the account is streaming and emits `session-detected`
+// for each session not seen before, including one already running when the watch starts.
+// Keeps watching afterwards so a restarted stream is picked up too.
+pub fn watch_for_session(app: &AppHandle, state: &SessionWatchState, shopee_account_id: i32, cookie: String, proxy: Option<String>) -> Result<(), String> {
+    let client = crate::shopee_api_client(proxy.as_deref())?;
+    state.stop(shopee_account_id);
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut last_seen: Option<String> = None;
+        loop {
+            match client.ongoing_session(&cookie).await {
+                Ok(Some(session_id)) if last_seen.as_deref() != Some(session_id.as_str()) => {
+                    println!("[SESSION WATCH] Account {} went live: session {}", shopee_account_id, session_id);
+                    last_seen = Some(session_id.clone());
+                    let event = SessionDetectedEvent {
+                        shopee_account_id,
+                        session_id,
+                    };
+                    if let Err(e) = app.emit("session-detected", event) {
+                        eprintln!("Failed to emit session-detected: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[SESSION WATCH] Check for account {} failed: {}", shopee_account_id, e),
+            }
+            tokio::time::sleep(SESSION_WATCH_INTERVAL).await;
+        }
+    });
+
+    state.tasks.lock().unwrap().insert(shopee_account_id, task);
+    Ok(())
+}
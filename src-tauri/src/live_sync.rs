@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+// Changes made within this window are coalesced into a single live update
+const DEBOUNCE: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveSyncBinding {
+    pub product_set_id: i32,
+    pub shopee_account_id: i32,
+    pub session_id: String,
+    #[serde(skip_serializing)]
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LiveSyncEvent {
+    product_set_id: i32,
+    session_id: String,
+    success: bool,
+    error: Option<String>,
+    operator: Option<String>,
+}
+
+#[derive(Default)]
+pub struct LiveSyncState {
+    bindings: Mutex<HashMap<i32, LiveSyncBinding>>,
+    // Bumped on every change so only the last change in a burst triggers an update
+    generations: Mutex<HashMap<i32, u64>>,
+}
+
+impl LiveSyncState {
+    pub fn enable(&self, binding: LiveSyncBinding) {
+        self.bindings.lock().unwrap().insert(binding.product_set_id, binding);
+    }
+
+    pub fn disable(&self, product_set_id: i32) -> bool {
+        self.generations.lock().unwrap().remove(&product_set_id);
+        self.bindings.lock().unwrap().remove(&product_set_id).is_some()
+    }
+
+    pub fn list(&self) -> Vec<LiveSyncBinding> {
+        self.bindings.lock().unwrap().values().cloned().collect()
+    }
+
+    fn binding(&self, product_set_id: i32) -> Option<LiveSyncBinding> {
+        self.bindings.lock().unwrap().get(&product_set_id).cloned()
+    }
+
+    fn bump(&self, product_set_id: i32) -> u64 {
+        let mut generations = self.generations.lock().unwrap();
+        let generation = generations.entry(product_set_id).or_insert(0);
+        *generation += 1;
+        *generation
+    }
+
+    fn is_current(&self, product_set_id: i32, generation: u64) -> bool {
+        self.generations.lock().unwrap().get(&product_set_id) == Some(&generation)
+    }
+}
+
+// Called after any item mutation on a product set. If the set is mirrored to a live
+// session, the session's showcase is refreshed once the debounce window passes quietly.
+// Shopee only exposes a full replace for the showcase, so append/remove/reorder are all
+// applied by re-pushing the whole set.
+pub fn notify_set_changed(app: &AppHandle, product_set_id: i32) {
+    let state = app.state::<LiveSyncState>();
+    if state.binding(product_set_id).is_none() {
+        return;
+    }
+
+    let generation = state.bump(product_set_id);
+    let app = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DEBOUNCE).await;
+
+        let state = app.state::<LiveSyncState>();
+        if !state.is_current(product_set_id, generation) {
+            return;
+        }
+        // A sync requested during a blackout window goes out once the window ends
+        crate::blackout::wait_until_clear(&app).await;
+        if !state.is_current(product_set_id, generation) {
+            return;
+        }
+        let Some(binding) = state.binding(product_set_id) else {
+            return;
+        };
+
+        println!("[LIVE SYNC] Pushing product set {} to session {}", product_set_id, binding.session_id);
+
+        let result = crate::replace_products_request(
+            &app,
+            &binding.email,
+            &binding.password,
+            binding.shopee_account_id,
+            &binding.session_id,
+            product_set_id,
+        )
+        .await;
+
+        if let Err(e) = &result {
+            crate::diagnostics::record_failure(&app, &binding.session_id, "live_sync", e);
+        }
+
+        let event = LiveSyncEvent {
+            product_set_id,
+            session_id: binding.session_id.clone(),
+            success: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+            operator: crate::operators::current(&app),
+        };
+        if let Err(e) = app.emit("live-sync-applied", event) {
+            eprintln!("Failed to emit live-sync-applied: {}", e);
+        }
+    });
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tauri::AppHandle;
+
+use botgacor_core::shopee::LiveStats;
+
+use crate::db;
+
+// One viewer-polling result, for charting a session over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveSample {
+    pub at: u64,
+    pub viewers: i64,
+    pub views: i64,
+    pub likes: i64,
+    pub shares: i64,
+    pub items_sold: i64,
+    // Audience comments per minute since the previous sample; None for the first sample
+    // and when the comment feed couldn't be read
+    pub comments_per_min: Option<f64>,
+}
+
+// Follows the session's comment feed between samples. The first page is history and only
+// sets the starting point.
+#[derive(Default)]
+pub struct CommentRate {
+    cursor: Option<String>,
+    since: Option<Instant>,
+}
+
+impl CommentRate {
+    pub async fn sample(&mut self, email: &str, password: &str, shopee_account_id: i32, session_id: &str) -> Option<f64> {
+        let page = match crate::fetch_live_comments(email, password, shopee_account_id, session_id, self.cursor.as_deref()).await {
+            Ok(page) => page,
+            Err(e) => {
+                eprintln!("[LIVE ANALYTICS] Session {} comment feed failed: {}", session_id, e);
+                return None;
+            }
+        };
+        if page.cursor.is_some() {
+            self.cursor = page.cursor;
+        }
+        let now = Instant::now();
+        let since = self.since.replace(now)?;
+        let minutes = now.duration_since(since).as_secs_f64() / 60.0;
+        let comments = page.comments.iter().filter(|c| !c.is_host).count();
+        (minutes > 0.0).then(|| comments as f64 / minutes)
+    }
+}
+
+pub fn record(app: &AppHandle, shopee_account_id: i32, session_id: &str, stats: &LiveStats, comments_per_min: Option<f64>) {
+    let sample = LiveSample {
+        at: crate::unix_now(),
+        viewers: stats.viewers,
+        views: stats.views,
+        likes: stats.likes,
+        shares: stats.shares,
+        items_sold: stats.items_sold,
+        comments_per_min,
+    };
+    let result = db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO live_samples (session_id, shopee_account_id, at, data) VALUES (?1, ?2, ?3, ?4)",
+            (session_id, shopee_account_id, sample.at as i64, db::to_json(&sample)?),
+        )
+    });
+    if let Err(e) = result {
+        eprintln!("[LIVE ANALYTICS] Failed to record sample for session {}: {}", session_id, e);
+    }
+}
+
+// Oldest first
+pub fn get(app: &AppHandle, session_id: &str) -> Result<Vec<LiveSample>, String> {
+    db::with(app, |conn| {
+        let mut statement = conn.prepare("SELECT data FROM live_samples WHERE session_id = ?1 ORDER BY at")?;
+        let mut samples = Vec::new();
+        for data in statement.query_map([session_id], |row| row.get::<_, String>(0))? {
+            samples.push(db::from_json(&data?)?);
+        }
+        Ok(samples)
+    })
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::error::CommandError;
+use crate::{make_api_request_at, ActiveSessionApiResponse, ApiResponse, ShopeeAccountsResponse};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoadTestConfig {
+    #[serde(default = "default_accounts")]
+    pub accounts: u32,
+    #[serde(default = "default_iterations")]
+    pub iterations: u32,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    // Artificial latency and failure rate of the mock backend
+    #[serde(default)]
+    pub mock_latency_ms: u64,
+    #[serde(default)]
+    pub mock_error_rate: f64,
+}
+
+fn default_accounts() -> u32 {
+    50
+}
+
+fn default_iterations() -> u32 {
+    10
+}
+
+fn default_concurrency() -> usize {
+    16
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadStats {
+    pub requests: usize,
+    pub errors: usize,
+    pub error_rate: f64,
+    pub p50_ms: u128,
+    pub p95_ms: u128,
+    pub max_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LoadTestReport {
+    pub total_requests: usize,
+    pub total_errors: usize,
+    pub duration_ms: u128,
+    pub throughput_per_sec: f64,
+    pub workloads: HashMap<String, WorkloadStats>,
+}
+
+#[derive(Default)]
+struct Samples {
+    latencies: HashMap<&'static str, Vec<Duration>>,
+    errors: HashMap<&'static str, usize>,
+}
+
+// ==================== Mock Backend ====================
+
+async fn read_request(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&buf[..pos]).to_string();
+            let content_length = head
+                .lines()
+                .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                .unwrap_or(0);
+            while buf.len() < pos + 4 + content_length {
+                let n = stream.read(&mut chunk).await.ok()?;
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            return head.lines().next().map(|l| l.to_string());
+        }
+    }
+}
+
+fn mock_response(request_line: &str) -> serde_json::Value {
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let path = path.split('?').next().unwrap_or(path);
+    match path {
+        "/api/members/shopee-accounts" => serde_json::json!({
+            "success": true,
+            "data": [{ "id": 1, "name": "Mock Shop", "is_active": true, "created_at": null }]
+        }),
+        "/api/shopee-live/active-session" => serde_json::json!({
+            "success": true,
+            "session_id": 123456,
+            "message": null
+        }),
+        _ => serde_json::json!({ "success": true, "replaced": 10 }),
+    }
+}
+
+async fn serve(listener: TcpListener, config: LoadTestConfig) {
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let config = config.clone();
+        tokio::spawn(async move {
+            let Some(request_line) = read_request(&mut stream).await else {
+                return;
+            };
+            if config.mock_latency_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(config.mock_latency_ms)).await;
+            }
+            let (status, body) = if rand::random::<f64>() < config.mock_error_rate {
+                ("500 Internal Server Error", serde_json::json!({ "success": false, "message": "Mock failure" }))
+            } else {
+                ("200 OK", mock_response(&request_line))
+            };
+            let body = body.to_string();
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+// ==================== Workload ====================
+
+async fn timed<F, T>(samples: &Mutex<Samples>, name: &'static str, request: F)
+where
+    F: std::future::Future<Output = Result<T, CommandError>>,
+{
+    let started = Instant::now();
+    let result = request.await;
+    let elapsed = started.elapsed();
+
+    let mut samples = samples.lock().await;
+    samples.latencies.entry(name).or_default().push(elapsed);
+    if result.is_err() {
+        *samples.errors.entry(name).or_default() += 1;
+    }
+}
+
+async fn run_account(base_url: String, account_id: u32, iterations: u32, samples: Arc<Mutex<Samples>>) {
+    let body = serde_json::json!({
+        "email": format!("loadtest+{}@example.com", account_id),
+        "password": "loadtest",
+        "shopee_account_id": account_id,
+        "session_id": "123456",
+        "product_set_id": 1
+    });
+
+    for _ in 0..iterations {
+        timed(&samples, "rotation", make_api_request_at::<ApiResponse<serde_json::Value>>(&base_url, "POST", "/api/shopee-live/replace-products", Some(&body), None)).await;
+        timed(&samples, "validation", make_api_request_at::<ApiResponse<ShopeeAccountsResponse>>(&base_url, "GET", "/api/members/shopee-accounts", None, Some("email=loadtest&password=loadtest"))).await;
+        timed(&samples, "metrics_polling", make_api_request_at::<ActiveSessionApiResponse>(&base_url, "POST", "/api/shopee-live/active-session", Some(&body), None)).await;
+    }
+}
+
+fn percentile(sorted: &[Duration], pct: f64) -> u128 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let index = ((sorted.len() as f64 - 1.0) * pct).round() as usize;
+    sorted[index].as_millis()
+}
+
+// Spins up a local mock backend and drives the app's own member API pipeline against it
+pub async fn run(config: LoadTestConfig) -> Result<LoadTestReport, String> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to start mock server: {}", e))?;
+    let base_url = format!("http://{}", listener.local_addr().map_err(|e| e.to_string())?);
+    let server = tokio::spawn(serve(listener, config.clone()));
+
+    let samples = Arc::new(Mutex::new(Samples::default()));
+    let semaphore = Arc::new(Semaphore::new(config.concurrency.max(1)));
+    let started = Instant::now();
+
+    let mut tasks = Vec::new();
+    for account_id in 1..=config.accounts {
+        let permit = semaphore.clone().acquire_owned().await.map_err(|e| e.to_string())?;
+        let base_url = base_url.clone();
+        let samples = samples.clone();
+        let iterations = config.iterations;
+        tasks.push(tokio::spawn(async move {
+            run_account(base_url, account_id, iterations, samples).await;
+            drop(permit);
+        }));
+    }
+    for task in tasks {
+        let _ = task.await;
+    }
+
+    let duration = started.elapsed();
+    server.abort();
+
+    let samples = samples.lock().await;
+    let mut workloads = HashMap::new();
+    let mut total_requests = 0;
+    let mut total_errors = 0;
+    for (name, latencies) in &samples.latencies {
+        let mut sorted = latencies.clone();
+        sorted.sort();
+        let errors = samples.errors.get(name).copied().unwrap_or(0);
+        total_requests += sorted.len();
+        total_errors += errors;
+        workloads.insert(
+            name.to_string(),
+            WorkloadStats {
+                requests: sorted.len(),
+                errors,
+                error_rate: errors as f64 / sorted.len().max(1) as f64,
+                p50_ms: percentile(&sorted, 0.5),
+                p95_ms: percentile(&sorted, 0.95),
+                max_ms: sorted.last().map(|d| d.as_millis()).unwrap_or(0),
+            },
+        );
+    }
+
+    Ok(LoadTestReport {
+        total_requests,
+        total_errors,
+        duration_ms: duration.as_millis(),
+        throughput_per_sec: total_requests as f64 / duration.as_secs_f64().max(0.001),
+        workloads,
+    })
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+struct ReboundEvent {
+    email: String,
+    local_machine_id: String,
+    bound_machine_id: String,
+    detected_at: u64,
+    suggestion: String,
+}
+
+#[derive(Default)]
+pub struct MachineBindingState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl MachineBindingState {
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
+// Periodically asks the backend which machine the license is bound to. When another
+// machine has taken it over, the UI is told once so it can offer re-binding (via
+// update_machine_id with the password) or contacting support.
+pub fn start(app: &AppHandle, state: &MachineBindingState, email: String) {
+    state.stop();
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let local_machine_id = crate::get_or_generate_machine_id();
+        let legacy_machine_id = crate::machine_id::legacy_machine_id();
+        let mut reported_machine_id: Option<String> = None;
+
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+
+            let bound = match crate::get_user_machine_id(email.clone()).await {
+                Ok(response) => response.machine_id,
+                Err(e) => {
+                    eprintln!("[MACHINE BINDING] Check failed: {}", e);
+                    continue;
+                }
+            };
+
+            // Still bound to our pre-migration ID is not a takeover
+            if bound == local_machine_id || bound == legacy_machine_id || bound.is_empty() {
+                reported_machine_id = None;
+                continue;
+            }
+            if reported_machine_id.as_deref() == Some(bound.as_str()) {
+                continue;
+            }
+
+            println!("[MACHINE BINDING] License for {} is now bound to another machine", email);
+            let event = ReboundEvent {
+                email: email.clone(),
+                local_machine_id: local_machine_id.clone(),
+                bound_machine_id: bound.clone(),
+                detected_at: crate::unix_now(),
+                suggestion: "Log in again with your password to bind the license back to this computer, or contact support if you didn't log in elsewhere".to_string(),
+            };
+            if let Err(e) = app.emit("license-rebound-elsewhere", event) {
+                eprintln!("Failed to emit license-rebound-elsewhere: {}", e);
+            }
+            reported_machine_id = Some(bound);
+        }
+    });
+
+    *state.task.lock().unwrap() = Some(task);
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+use crate::storage;
+
+const MIGRATION_FILE: &str = "machine_id_migration.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MachineIdMigration {
+    pub email: String,
+    pub from: String,
+    pub to: String,
+    pub migrated_at: u64,
+}
+
+fn short_hash(input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    hex::encode(hasher.finalize())[..16].to_string()
+}
+
+// Original ID scheme: hash of hostname and OS user. Licenses bound before the switch
+// to hardware IDs still carry this value.
+pub fn legacy_machine_id() -> String {
+    use std::env;
+    let hostname = env::var("COMPUTERNAME").or_else(|_| env::var("HOSTNAME")).unwrap_or_else(|_| "unknown".to_string());
+    let user = env::var("USER").or_else(|_| env::var("USERNAME")).unwrap_or_else(|_| "unknown".to_string());
+
+    let combined = format!("{}-{}", hostname, user);
+    short_hash(&combined)
+}
+
+#[cfg(target_os = "linux")]
+fn read_hardware_uuid() -> Option<String> {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|p| std::fs::read_to_string(p).ok())
+        .map(|s| s.trim().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn read_hardware_uuid() -> Option<String> {
+    let output = std::process::Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|l| l.contains("MachineGuid"))
+        .and_then(|l| l.split_whitespace().last())
+        .map(|s| s.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn read_hardware_uuid() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|l| l.contains("IOPlatformUUID"))
+        .and_then(|l| l.split('"').nth(3))
+        .map(|s| s.to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn read_hardware_uuid() -> Option<String> {
+    None
+}
+
+// Hardware-based ID; stays the same when the user renames the computer or account
+pub fn hardware_machine_id() -> Option<String> {
+    read_hardware_uuid()
+        .filter(|uuid| !uuid.is_empty())
+        .map(|uuid| short_hash(&format!("botgacor-hw:{}", uuid)))
+}
+
+pub fn current() -> String {
+    static CURRENT: OnceLock<String> = OnceLock::new();
+    CURRENT
+        .get_or_init(|| hardware_machine_id().unwrap_or_else(legacy_machine_id))
+        .clone()
+}
+
+// Licenses still bound to the legacy ID are moved to the hardware ID once, so existing
+// users aren't locked out by the scheme change. The migration record is kept locally.
+pub async fn migrate_if_needed(app: &AppHandle, email: &str, password: &str) -> Result<Option<MachineIdMigration>, String> {
+    let legacy = legacy_machine_id();
+    let current = current();
+    if legacy == current {
+        return Ok(None);
+    }
+
+    let done: Option<MachineIdMigration> = storage::load_json(app, MIGRATION_FILE)?;
+    if done.is_some_and(|m| m.email == email) {
+        return Ok(None);
+    }
+
+    let bound = crate::get_user_machine_id(email.to_string())
+        .await
+        .map_err(|e| e.to_string())?
+        .machine_id;
+    if bound != legacy {
+        return Ok(None);
+    }
+
+    println!("[MACHINE ID] Migrating license for {} from legacy ID to hardware ID", email);
+    crate::update_machine_id(email.to_string(), current.clone(), Some(password.to_string()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let migration = MachineIdMigration {
+        email: email.to_string(),
+        from: legacy,
+        to: current,
+        migrated_at: crate::unix_now(),
+    };
+    storage::save_json(app, MIGRATION_FILE, &Some(migration.clone()))?;
+    Ok(Some(migration))
+}
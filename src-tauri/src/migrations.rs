@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::AppHandle;
+
+use crate::pacing::PacingProfile;
+use crate::{settings, storage};
+
+// Layout version of the files in the app data dir. The database schema has its own version
+// (see db::MIGRATIONS) and is brought up to date before these run.
+const VERSION_FILE: &str = "storage_version.json";
+// Copies of the data files taken before upgrading, one directory per starting version
+pub const BACKUP_DIR: &str = "migration_backups";
+
+struct Migration {
+    version: u32,
+    description: &'static str,
+    run: fn(&AppHandle) -> Result<(), String>,
+}
+
+// Applied in order. Each step must be safe to run again if the app stops halfway through,
+// and must never delete data it hasn't copied somewhere else first.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Move cached metadata, session reports, showcase snapshots and schedules into the database",
+        run: files_to_database,
+    },
+    Migration {
+        version: 2,
+        description: "Move the pacing profile into settings",
+        run: pacing_profile_to_settings,
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub description: String,
+    pub applied_at: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageVersion {
+    pub version: u32,
+    pub applied: Vec<AppliedMigration>,
+}
+
+fn files_to_database(app: &AppHandle) -> Result<(), String> {
+    crate::product_metadata::import_legacy(app);
+    crate::session_reports::import_legacy(app);
+    crate::showcase_snapshots::import_legacy(app);
+    crate::scheduler::import_legacy(app);
+    Ok(())
+}
+
+fn pacing_profile_to_settings(app: &AppHandle) -> Result<(), String> {
+    let mut pacing: serde_json::Value = storage::load_json(app, "pacing.json")?;
+    let Some(profile) = pacing.as_object_mut().and_then(|p| p.remove("profile")) else {
+        return Ok(());
+    };
+    let profile: PacingProfile = serde_json::from_value(profile).map_err(|e| format!("Invalid pacing profile: {}", e))?;
+    settings::update(app, |settings| settings.pacing_profile = profile)?;
+    storage::save_json(app, "pacing.json", &pacing)
+}
+
+// Copies every data file so a failed upgrade can be recovered by hand
+fn backup_files(app: &AppHandle, from_version: u32) -> Result<(), String> {
+    let dir = storage::data_dir(app)?;
+    let target = dir.join(BACKUP_DIR).join(format!("v{}", from_version));
+    fs::create_dir_all(&target).map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read data dir: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_data = matches!(path.extension().and_then(|e| e.to_str()), Some("json" | "enc"));
+        if path.is_file() && is_data {
+            fs::copy(&path, target.join(entry.file_name()))
+                .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+// Runs the migrations newer than the recorded version, once at startup. Stops at the first
+// failure, leaving the rest for the next launch, so the app still starts with older data.
+pub fn run(app: &AppHandle) {
+    let mut state: StorageVersion = match storage::load_json(app, VERSION_FILE) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("[MIGRATIONS] Failed to read storage version, not migrating: {}", e);
+            return;
+        }
+    };
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    if state.version > latest {
+        eprintln!("[MIGRATIONS] Data is from a newer version of the app (storage version {})", state.version);
+        return;
+    }
+    if state.version == latest {
+        return;
+    }
+
+    if let Err(e) = backup_files(app, state.version) {
+        eprintln!("[MIGRATIONS] Not migrating, backup failed: {}", e);
+        return;
+    }
+    let from = state.version;
+    for migration in MIGRATIONS.iter().filter(|m| m.version > from) {
+        println!("[MIGRATIONS] {} -> {}: {}", state.version, migration.version, migration.description);
+        if let Err(e) = (migration.run)(app) {
+            eprintln!("[MIGRATIONS] Migration {} failed: {}", migration.version, e);
+            return;
+        }
+        state.version = migration.version;
+        state.applied.push(AppliedMigration {
+            version: migration.version,
+            description: migration.description.to_string(),
+            applied_at: crate::unix_now(),
+        });
+        if let Err(e) = storage::save_json(app, VERSION_FILE, &state) {
+            eprintln!("[MIGRATIONS] Failed to record storage version {}: {}", migration.version, e);
+            return;
+        }
+    }
+}
+
+pub fn status(app: &AppHandle) -> Result<StorageVersion, String> {
+    storage::load_json(app, VERSION_FILE)
+}
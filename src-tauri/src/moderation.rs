@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::storage;
+use crate::LiveComment;
+
+const MODERATION_FILE: &str = "moderation.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+// What happens to a comment containing a banned word, besides the `comment-flagged` event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    #[default]
+    FlagOnly,
+    Hide,
+    Report,
+}
+
+impl ModerationAction {
+    fn as_api(self) -> Option<&'static str> {
+        match self {
+            ModerationAction::FlagOnly => None,
+            ModerationAction::Hide => Some("hide"),
+            ModerationAction::Report => Some("report"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModerationConfig {
+    #[serde(default)]
+    pub banned_words: Vec<String>,
+    #[serde(default)]
+    pub action: ModerationAction,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CommentFlaggedEvent {
+    session_id: String,
+    comment: LiveComment,
+    matched: Vec<String>,
+    action: ModerationAction,
+    action_error: Option<String>,
+}
+
+fn normalize(word: &str) -> String {
+    word.trim().to_lowercase()
+}
+
+pub fn load(app: &AppHandle) -> Result<ModerationConfig, String> {
+    storage::load_json(app, MODERATION_FILE)
+}
+
+pub fn save(app: &AppHandle, mut config: ModerationConfig) -> Result<ModerationConfig, String> {
+    config.banned_words = config.banned_words.iter().map(|w| normalize(w)).filter(|w| !w.is_empty()).collect();
+    config.banned_words.sort();
+    config.banned_words.dedup();
+    storage::save_json(app, MODERATION_FILE, &config)?;
+    Ok(config)
+}
+
+// Banned words found in a comment. Matching is a case-insensitive substring check, so
+// a banned word also catches longer words that contain it.
+pub fn matched_words(config: &ModerationConfig, content: &str) -> Vec<String> {
+    let content = content.to_lowercase();
+    config.banned_words.iter().filter(|w| content.contains(w.as_str())).cloned().collect()
+}
+
+#[derive(Default)]
+pub struct ModerationState {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl ModerationState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.tasks.lock().unwrap().remove(session_id) {
+            Some(task) => {
+                task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn sessions(&self) -> Vec<String> {
+        self.tasks.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.tasks.lock().unwrap().remove(session_id);
+    }
+}
+
+// Checks every new comment in the session against the banned word list. The list and
+// action are re-read on every poll so edits apply immediately.
+pub fn start(app: &AppHandle, state: &ModerationState, email: String, password: String, shopee_account_id: i32, session_id: String) {
+    state.stop(&session_id);
+
+    let app = app.clone();
+    let key = session_id.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut cursor: Option<String> = None;
+        let mut primed = false;
+        let mut failures = 0;
+
+        loop {
+            match crate::fetch_live_comments(&email, &password, shopee_account_id, &session_id, cursor.as_deref()).await {
+                Ok(page) => {
+                    failures = 0;
+                    if page.cursor.is_some() {
+                        cursor = page.cursor;
+                    }
+                    // The first page is what was posted before moderation started; it only sets the cursor
+                    if !primed {
+                        primed = true;
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                        continue;
+                    }
+                    let config = load(&app).unwrap_or_default();
+                    for comment in page.comments.into_iter().filter(|c| !c.is_host) {
+                        let matched = matched_words(&config, &comment.content);
+                        if matched.is_empty() {
+                            continue;
+                        }
+
+                        let action_error = match config.action.as_api() {
+                            Some(action) => {
+                                crate::blackout::wait_until_clear(&app).await;
+                                crate::moderate_live_comment(&app, &email, &password, shopee_account_id, &session_id, &comment.id, action)
+                                    .await
+                                    .err()
+                                    .map(|e| e.to_string())
+                            }
+                            None => None,
+                        };
+                        let event = CommentFlaggedEvent {
+                            session_id: session_id.clone(),
+                            comment,
+                            matched,
+                            action: config.action,
+                            action_error,
+                        };
+                        if let Err(e) = app.emit("comment-flagged", event) {
+                            eprintln!("Failed to emit comment-flagged: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("[MODERATION] Session {} comment poll failed ({}): {}", session_id, failures, e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        app.state::<ModerationState>().finish(&session_id);
+    });
+
+    state.tasks.lock().unwrap().insert(key, task);
+}
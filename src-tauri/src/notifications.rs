@@ -0,0 +1,271 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::encrypted_store;
+use crate::jobs::{Job, JobState};
+
+// Encrypted because anyone holding the webhook URL can post to the channel
+const DISCORD_FILE: &str = "discord_webhook.enc";
+const DISCORD_HOSTS: &[&str] = &["discord.com", "discordapp.com", "ptb.discord.com", "canary.discord.com"];
+// Discord rejects messages longer than this
+const MAX_MESSAGE_CHARS: usize = 2000;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+// Discord's own limit is about 30 messages a minute per webhook
+const MAX_PER_MINUTE: u32 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    JobDone,
+    JobFailed,
+    // A live operation failed, as recorded by diagnostics
+    Error,
+    QrConfirmed,
+    // The watchdog replaced a live that ended unexpectedly
+    SessionRestarted,
+    // The member license has run out
+    LicenseExpired,
+    // No Shopee account has a working cookie left
+    AllCookiesDead,
+}
+
+impl NotificationEvent {
+    // Placeholders are `{name}`; see `job_finished` and `error` for what each event fills in
+    fn default_template(self) -> &'static str {
+        match self {
+            NotificationEvent::JobDone => "✅ **{label}** finished (account {account_id})",
+            NotificationEvent::JobFailed => "❌ **{label}** failed (account {account_id}): {error}",
+            NotificationEvent::Error => "⚠️ `{operation}` failed in session {session_id}: {error}",
+            NotificationEvent::QrConfirmed => "📱 QR login confirmed",
+            NotificationEvent::SessionRestarted => {
+                "🔁 Live on account {account_id} ended unexpectedly and was restarted as session {session_id} (restart {restarts})"
+            }
+            NotificationEvent::LicenseExpired => "⛔ License for {email} expired on {expiry_date}",
+            NotificationEvent::AllCookiesDead => "🍪 All {accounts} Shopee account cookie(s) are expired or banned",
+        }
+    }
+
+    // Title and body template for a desktop notification, for events that have one
+    fn desktop_text(self) -> Option<(&'static str, &'static str)> {
+        let toggles = crate::settings::current().desktop_notifications;
+        match self {
+            NotificationEvent::QrConfirmed if toggles.qr_confirmed => {
+                Some(("QR login confirmed", "The Shopee account was logged in from your phone"))
+            }
+            NotificationEvent::SessionRestarted if toggles.session_restarted => Some((
+                "Live restarted",
+                "The live on account {account_id} ended unexpectedly and was restarted",
+            )),
+            NotificationEvent::JobFailed if toggles.job_failed => Some(("Job failed", "{label}: {error}")),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscordConfig {
+    pub enabled: bool,
+    pub webhook_url: Option<String>,
+    // Events posted to the channel
+    pub events: BTreeSet<NotificationEvent>,
+    // Overrides of the default message per event
+    pub templates: BTreeMap<NotificationEvent, String>,
+    // Messages over this in a minute are dropped and counted in the next one sent
+    pub max_per_minute: u32,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        DiscordConfig {
+            enabled: false,
+            webhook_url: None,
+            events: [NotificationEvent::JobDone, NotificationEvent::JobFailed, NotificationEvent::Error].into(),
+            templates: BTreeMap::new(),
+            max_per_minute: 20,
+        }
+    }
+}
+
+impl DiscordConfig {
+    fn validate(&mut self) -> Result<(), String> {
+        self.webhook_url = self.webhook_url.take().map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+        if let Some(url) = &self.webhook_url {
+            let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+            let is_discord = parsed.scheme() == "https"
+                && parsed.host_str().is_some_and(|host| DISCORD_HOSTS.contains(&host))
+                && parsed.path().starts_with("/api/webhooks/");
+            if !is_discord {
+                return Err("Webhook URL must be a Discord webhook (https://discord.com/api/webhooks/...)".to_string());
+            }
+        }
+        if self.enabled && self.webhook_url.is_none() {
+            return Err("Enter a webhook URL to enable Discord notifications".to_string());
+        }
+        if !(1..=MAX_PER_MINUTE).contains(&self.max_per_minute) {
+            return Err(format!("Messages per minute must be between 1 and {}", MAX_PER_MINUTE));
+        }
+        self.templates.retain(|_, template| !template.trim().is_empty());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct NotificationState {
+    // When recent Discord messages were sent, oldest first
+    discord_sent: Mutex<VecDeque<Instant>>,
+    discord_suppressed: Mutex<u32>,
+}
+
+pub fn discord_config(app: &AppHandle) -> Result<DiscordConfig, String> {
+    encrypted_store::load(app, DISCORD_FILE)
+}
+
+pub fn set_discord_config(app: &AppHandle, mut config: DiscordConfig) -> Result<DiscordConfig, String> {
+    config.validate()?;
+    let _guard = crate::storage::lock();
+    encrypted_store::save(app, DISCORD_FILE, &config)?;
+    Ok(config)
+}
+
+// Fills `{name}` placeholders; unknown ones are left as they are
+pub(crate) fn render(template: &str, fields: &[(&str, String)]) -> String {
+    let mut message = template.to_string();
+    for (name, value) in fields {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    if message.chars().count() > MAX_MESSAGE_CHARS {
+        message = message.chars().take(MAX_MESSAGE_CHARS - 1).collect::<String>() + "…";
+    }
+    message
+}
+
+// Takes a slot in the rate window, or counts the message as suppressed
+fn take_discord_slot(app: &AppHandle, max_per_minute: u32) -> Option<u32> {
+    let state = app.state::<NotificationState>();
+    let mut sent = state.discord_sent.lock().unwrap();
+    let now = Instant::now();
+    while sent.front().is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW) {
+        sent.pop_front();
+    }
+    let mut suppressed = state.discord_suppressed.lock().unwrap();
+    if sent.len() >= max_per_minute as usize {
+        *suppressed += 1;
+        return None;
+    }
+    sent.push_back(now);
+    Some(std::mem::take(&mut *suppressed))
+}
+
+async fn post_discord(url: &str, content: &str) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(crate::settings::current().request_timeout())
+        .build()
+        .map_err(|e| format!("Failed to create client: {}", e))?;
+    let response = client
+        .post(url)
+        .json(&serde_json::json!({ "content": content }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Discord: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Discord returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+fn send_discord(app: &AppHandle, event: NotificationEvent, fields: &[(&str, String)]) {
+    let config = match discord_config(app) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("[NOTIFICATIONS] Failed to load Discord config: {}", e);
+            return;
+        }
+    };
+    let Some(url) = config.webhook_url.filter(|_| config.enabled && config.events.contains(&event)) else {
+        return;
+    };
+    let Some(suppressed) = take_discord_slot(app, config.max_per_minute) else {
+        return;
+    };
+    let template = config.templates.get(&event).map_or(event.default_template(), String::as_str);
+    let mut content = render(template, fields);
+    if suppressed > 0 {
+        content = render(&format!("{}\n_({} earlier message(s) skipped by the rate limit)_", content, suppressed), &[]);
+    }
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = post_discord(&url, &content).await {
+            eprintln!("[NOTIFICATIONS] Discord webhook failed: {}", e);
+        }
+    });
+}
+
+// Sends a sample message to the saved webhook, enabled or not
+pub async fn test_discord(app: &AppHandle) -> Result<(), String> {
+    let config = discord_config(app)?;
+    let url = config.webhook_url.ok_or("No webhook URL saved")?;
+    if take_discord_slot(app, config.max_per_minute).is_none() {
+        return Err("Too many messages this minute, try again shortly".to_string());
+    }
+    post_discord(&url, "🔔 Test notification from Bot Gacor").await
+}
+
+// Shown by the OS, so it is seen while the window is minimized or hidden in the tray
+fn send_desktop(app: &AppHandle, event: NotificationEvent, fields: &[(&str, String)]) {
+    let Some((title, body)) = event.desktop_text() else {
+        return;
+    };
+    let result = app.notification().builder().title(title).body(render(body, fields)).show();
+    if let Err(e) = result {
+        eprintln!("[NOTIFICATIONS] Failed to show desktop notification: {}", e);
+    }
+}
+
+// Fans an event out to every configured channel
+pub fn notify(app: &AppHandle, event: NotificationEvent, fields: &[(&str, String)]) {
+    send_desktop(app, event, fields);
+    send_discord(app, event, fields);
+    crate::email_alerts::send(app, event, fields);
+    crate::webhooks::dispatch(app, event, fields);
+}
+
+pub fn job_finished(app: &AppHandle, job: &Job) {
+    let event = match job.state {
+        JobState::Done => NotificationEvent::JobDone,
+        JobState::Failed | JobState::Partial => NotificationEvent::JobFailed,
+        _ => return,
+    };
+    let fields = [
+        ("label", job.label.clone()),
+        ("kind", job.kind.clone()),
+        ("job_id", job.id.clone()),
+        ("account_id", job.shopee_account_id.map_or_else(|| "-".to_string(), |id| id.to_string())),
+        ("error", job.error.clone().unwrap_or_default()),
+        ("operator", crate::operators::current(app).unwrap_or_default()),
+    ];
+    notify(app, event, &fields);
+}
+
+pub fn error(app: &AppHandle, session_id: &str, operation: &str, error: &str) {
+    let fields = [
+        ("session_id", session_id.to_string()),
+        ("operation", operation.to_string()),
+        ("error", error.to_string()),
+        ("operator", crate::operators::current(app).unwrap_or_default()),
+    ];
+    notify(app, NotificationEvent::Error, &fields);
+}
+
+// `expiry_date` is None when the backend only said the license expired, without the date
+pub fn license_expired(app: &AppHandle, email: &str, expiry_date: Option<&str>) {
+    let fields = [("email", email.to_string()), ("expiry_date", expiry_date.unwrap_or("an unknown date").to_string())];
+    notify(app, NotificationEvent::LicenseExpired, &fields);
+}
+
+pub fn all_cookies_dead(app: &AppHandle, accounts: usize) {
+    notify(app, NotificationEvent::AllCookiesDead, &[("accounts", accounts.to_string())]);
+}
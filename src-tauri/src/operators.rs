@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::storage;
+
+const OPERATORS_FILE: &str = "operators.json";
+
+// A staff member sharing this install. The PIN only guards against switching to
+// someone else by accident; it is not an access control boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorProfile {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pin_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OperatorSummary {
+    pub name: String,
+    pub has_pin: bool,
+}
+
+#[derive(Default)]
+pub struct OperatorState {
+    active: Mutex<Option<String>>,
+}
+
+fn hash_pin(name: &str, pin: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("botgacor-operator:{}:{}", name, pin).as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<OperatorSummary>, String> {
+    let profiles: Vec<OperatorProfile> = storage::load_json(app, OPERATORS_FILE)?;
+    Ok(profiles
+        .into_iter()
+        .map(|p| OperatorSummary {
+            has_pin: p.pin_hash.is_some(),
+            name: p.name,
+        })
+        .collect())
+}
+
+pub fn add(app: &AppHandle, name: &str, pin: Option<&str>) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Operator name is required".to_string());
+    }
+    let pin_hash = pin.filter(|p| !p.is_empty()).map(|p| hash_pin(&name, p));
+    storage::update_json(app, OPERATORS_FILE, |profiles: &mut Vec<OperatorProfile>| {
+        profiles.retain(|p| p.name != name);
+        profiles.push(OperatorProfile { name, pin_hash });
+    })
+}
+
+pub fn remove(app: &AppHandle, name: &str) -> Result<(), String> {
+    storage::update_json(app, OPERATORS_FILE, |profiles: &mut Vec<OperatorProfile>| {
+        profiles.retain(|p| p.name != name);
+    })?;
+    let state = app.state::<OperatorState>();
+    let mut active = state.active.lock().unwrap();
+    if active.as_deref() == Some(name) {
+        *active = None;
+    }
+    Ok(())
+}
+
+pub fn switch(app: &AppHandle, name: &str, pin: Option<&str>) -> Result<(), String> {
+    let profiles: Vec<OperatorProfile> = storage::load_json(app, OPERATORS_FILE)?;
+    let profile = profiles
+        .iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("Unknown operator: {}", name))?;
+    if let Some(expected) = &profile.pin_hash {
+        if pin.map(|p| hash_pin(name, p)).as_ref() != Some(expected) {
+            return Err("Wrong PIN".to_string());
+        }
+    }
+    *app.state::<OperatorState>().active.lock().unwrap() = Some(profile.name.clone());
+    Ok(())
+}
+
+// Name recorded against actions taken right now
+pub fn current(app: &AppHandle) -> Option<String> {
+    app.state::<OperatorState>().active.lock().unwrap().clone()
+}
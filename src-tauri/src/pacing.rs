@@ -0,0 +1,205 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::{settings, storage};
+
+const PACING_FILE: &str = "pacing.json";
+
+// Live actions that are paced per account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacedAction {
+    Comment,
+    Pin,
+    Moderate,
+}
+
+impl PacedAction {
+    // The paced action a live endpoint performs, if any. Unpinning changes the pinned
+    // product just like pinning does, so it shares the pin budget.
+    pub fn for_endpoint(endpoint: &str) -> Option<Self> {
+        match endpoint {
+            "/api/shopee-live/send-comment" => Some(PacedAction::Comment),
+            "/api/shopee-live/pin-product" | "/api/shopee-live/unpin-product" => Some(PacedAction::Pin),
+            "/api/shopee-live/moderate-comment" => Some(PacedAction::Moderate),
+            _ => None,
+        }
+    }
+
+    fn noun(self) -> &'static str {
+        match self {
+            PacedAction::Comment => "comments",
+            PacedAction::Pin => "pins",
+            PacedAction::Moderate => "moderation actions",
+        }
+    }
+}
+
+// How hard automation may push an account: a fixed minimum gap between actions of the same
+// kind and a cap per local calendar day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacingProfile {
+    Aggressive,
+    #[default]
+    Normal,
+    Safe,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PacingLimits {
+    pub comment_gap_secs: u64,
+    pub pin_gap_secs: u64,
+    pub moderate_gap_secs: u64,
+    pub daily_comments: u32,
+    pub daily_pins: u32,
+    pub daily_moderations: u32,
+}
+
+impl PacingProfile {
+    pub fn limits(self) -> PacingLimits {
+        match self {
+            PacingProfile::Aggressive => PacingLimits {
+                comment_gap_secs: 5,
+                pin_gap_secs: 5,
+                moderate_gap_secs: 1,
+                daily_comments: 1500,
+                daily_pins: 3000,
+                daily_moderations: 3000,
+            },
+            PacingProfile::Normal => PacingLimits {
+                comment_gap_secs: 20,
+                pin_gap_secs: 10,
+                moderate_gap_secs: 3,
+                daily_comments: 500,
+                daily_pins: 1000,
+                daily_moderations: 1000,
+            },
+            PacingProfile::Safe => PacingLimits {
+                comment_gap_secs: 60,
+                pin_gap_secs: 30,
+                moderate_gap_secs: 10,
+                daily_comments: 200,
+                daily_pins: 300,
+                daily_moderations: 300,
+            },
+        }
+    }
+}
+
+impl PacingLimits {
+    fn gap(&self, action: PacedAction) -> Duration {
+        Duration::from_secs(match action {
+            PacedAction::Comment => self.comment_gap_secs,
+            PacedAction::Pin => self.pin_gap_secs,
+            PacedAction::Moderate => self.moderate_gap_secs,
+        })
+    }
+
+    fn daily_cap(&self, action: PacedAction) -> u32 {
+        match action {
+            PacedAction::Comment => self.daily_comments,
+            PacedAction::Pin => self.daily_pins,
+            PacedAction::Moderate => self.daily_moderations,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PacingStore {
+    // Only today's counts are kept; a new day starts from zero
+    #[serde(default)]
+    day: String,
+    #[serde(default)]
+    counts: HashMap<i32, HashMap<PacedAction, u32>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PacingStatus {
+    pub profile: PacingProfile,
+    pub limits: PacingLimits,
+    pub day: String,
+    pub counts: HashMap<i32, HashMap<PacedAction, u32>>,
+}
+
+// When each account may next perform each action
+#[derive(Default)]
+pub struct PacingState {
+    next_allowed: Mutex<HashMap<(i32, PacedAction), Instant>>,
+}
+
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+pub fn status(app: &AppHandle) -> Result<PacingStatus, String> {
+    let store: PacingStore = storage::load_json(app, PACING_FILE)?;
+    let profile = settings::current().pacing_profile;
+    let day = today();
+    let counts = if store.day == day { store.counts } else { HashMap::new() };
+    Ok(PacingStatus {
+        profile,
+        limits: profile.limits(),
+        day,
+        counts,
+    })
+}
+
+// The profile is part of the app settings
+pub fn set_profile(app: &AppHandle, profile: PacingProfile) -> Result<(), String> {
+    settings::update(app, |settings| settings.pacing_profile = profile).map(|_| ())
+}
+
+// Today's counts, starting a new day from zero
+fn current_day(store: &mut PacingStore) {
+    let day = today();
+    if store.day != day {
+        store.day = day;
+        store.counts.clear();
+    }
+}
+
+// Fails once the account has used today's cap for the action
+fn check_daily(app: &AppHandle, shopee_account_id: i32, action: PacedAction) -> Result<PacingLimits, String> {
+    let mut store: PacingStore = storage::load_json(app, PACING_FILE)?;
+    current_day(&mut store);
+    let limits = settings::current().pacing_profile.limits();
+    let count = store.counts.get(&shopee_account_id).and_then(|counts| counts.get(&action)).copied().unwrap_or(0);
+    if count >= limits.daily_cap(action) {
+        return Err(format!("Daily limit of {} {} reached for this account", limits.daily_cap(action), action.noun()));
+    }
+    Ok(limits)
+}
+
+// Called by the live request layer before every paced action. Waits out the profile's gap
+// since the account's previous action of the same kind; concurrent callers queue up behind
+// each other rather than firing together. Nothing is counted until `record`.
+pub async fn acquire(app: &AppHandle, shopee_account_id: i32, action: PacedAction) -> Result<(), String> {
+    let limits = check_daily(app, shopee_account_id, action)?;
+    let wait = {
+        let state = app.state::<PacingState>();
+        let mut next_allowed = state.next_allowed.lock().unwrap();
+        let now = Instant::now();
+        let slot = next_allowed.get(&(shopee_account_id, action)).copied().filter(|at| *at > now).unwrap_or(now);
+        next_allowed.insert((shopee_account_id, action), slot + limits.gap(action));
+        slot - now
+    };
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+    Ok(())
+}
+
+// Counts an action the backend accepted against today's cap
+pub fn record(app: &AppHandle, shopee_account_id: i32, action: PacedAction) {
+    if let Err(e) = storage::update_json(app, PACING_FILE, |store: &mut PacingStore| {
+        current_day(store);
+        *store.counts.entry(shopee_account_id).or_default().entry(action).or_default() += 1;
+    }) {
+        eprintln!("[PACING] Failed to count {} for account {}: {}", action.noun(), shopee_account_id, e);
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+use crate::jobs;
+use crate::live_sync::LiveSyncBinding;
+
+pub const MIN_PIN_SECS: u64 = 10;
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinOrder {
+    // The set's showcase order
+    #[default]
+    Showcase,
+    // Best sellers first, by Shopee's historical sold count
+    SalesRank,
+}
+
+// A showcase product to pin: Shopee IDs plus the product set item it came from
+#[derive(Debug, Clone, Serialize)]
+pub struct PinTarget {
+    pub product_set_item_id: i32,
+    pub shop_id: i64,
+    pub item_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PinRotationInfo {
+    pub session_id: String,
+    pub job_id: String,
+    pub product_set_id: i32,
+    pub shopee_account_id: i32,
+    pub pin_seconds: u64,
+    pub order: PinOrder,
+    pub total: usize,
+    pub position: usize,
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RotationAdvancedEvent {
+    session_id: String,
+    product_set_id: i32,
+    position: usize,
+    total: usize,
+    item: PinTarget,
+}
+
+struct Rotation {
+    info: PinRotationInfo,
+    current: Option<PinTarget>,
+    paused: Arc<AtomicBool>,
+    skip: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+pub struct PinRotationState {
+    rotations: Mutex<HashMap<String, Rotation>>,
+}
+
+impl PinRotationState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.rotations.lock().unwrap().remove(session_id) {
+            Some(rotation) => {
+                rotation.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_paused(&self, session_id: &str, paused: bool) -> Result<PinRotationInfo, String> {
+        let mut rotations = self.rotations.lock().unwrap();
+        let rotation = rotations
+            .get_mut(session_id)
+            .ok_or_else(|| format!("No pin rotation running for session {}", session_id))?;
+        rotation.paused.store(paused, Ordering::SeqCst);
+        rotation.info.paused = paused;
+        Ok(rotation.info.clone())
+    }
+
+    // `paused` is read from the shared flag, which the task manager can also set
+    pub fn list(&self) -> Vec<PinRotationInfo> {
+        self.rotations
+            .lock()
+            .unwrap()
+            .values()
+            .map(|r| PinRotationInfo {
+                paused: r.paused.load(Ordering::SeqCst),
+                ..r.info.clone()
+            })
+            .collect()
+    }
+
+    // The item the rotation pinned last
+    pub fn current(&self, session_id: &str) -> Option<PinTarget> {
+        self.rotations.lock().unwrap().get(session_id)?.current.clone()
+    }
+
+    // Cuts the current pin short so the next item is pinned right away
+    pub fn advance(&self, session_id: &str) -> Result<(), String> {
+        let rotations = self.rotations.lock().unwrap();
+        let rotation = rotations
+            .get(session_id)
+            .ok_or_else(|| format!("No pin rotation running for session {}", session_id))?;
+        rotation.skip.notify_one();
+        Ok(())
+    }
+
+    fn set_position(&self, session_id: &str, position: usize, item: &PinTarget) {
+        if let Some(rotation) = self.rotations.lock().unwrap().get_mut(session_id) {
+            rotation.info.position = position;
+            rotation.current = Some(item.clone());
+        }
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.rotations.lock().unwrap().remove(session_id);
+    }
+}
+
+// Pins each target for `pin_seconds` in turn, looping back to the first. While paused the
+// current pin stays in place and the timer doesn't advance.
+pub fn start(
+    app: &AppHandle,
+    state: &PinRotationState,
+    binding: LiveSyncBinding,
+    targets: Vec<PinTarget>,
+    pin_seconds: u64,
+    order: PinOrder,
+) -> Result<PinRotationInfo, String> {
+    if targets.is_empty() {
+        return Err("The product set has no items to pin".to_string());
+    }
+    let LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        session_id,
+        email,
+        password,
+    } = binding;
+    state.stop(&session_id);
+
+    let pin_seconds = pin_seconds.max(MIN_PIN_SECS);
+    let cancel_session = session_id.clone();
+    let job = jobs::start_background(
+        app,
+        "pin_rotation",
+        format!("Pin rotation for set {} in session {}", product_set_id, session_id),
+        Some(shopee_account_id),
+        move |app| {
+            app.state::<PinRotationState>().stop(&cancel_session);
+        },
+    );
+    let info = PinRotationInfo {
+        session_id: session_id.clone(),
+        job_id: job.id().to_string(),
+        product_set_id,
+        shopee_account_id,
+        pin_seconds,
+        order,
+        total: targets.len(),
+        position: 0,
+        paused: false,
+    };
+    // Shared with the job so pausing from the task manager and from here is the same thing
+    let paused = job.paused_flag();
+    let skip = Arc::new(Notify::new());
+    let task_skip = skip.clone();
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut position = 0;
+        let mut failures = 0;
+        loop {
+            job.checkpoint().await;
+            crate::blackout::wait_until_clear(&app).await;
+
+            let item = targets[position].clone();
+            match crate::pin_product_request(&app, &email, &password, shopee_account_id, &session_id, item.item_id).await {
+                Ok(()) => {
+                    failures = 0;
+                    app.state::<PinRotationState>().set_position(&session_id, position, &item);
+                    crate::session_reports::record_rotation(&app, &session_id);
+                    let event = RotationAdvancedEvent {
+                        session_id: session_id.clone(),
+                        product_set_id,
+                        position,
+                        total: targets.len(),
+                        item,
+                    };
+                    if let Err(e) = app.emit("rotation-advanced", event) {
+                        eprintln!("Failed to emit rotation-advanced: {}", e);
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    crate::diagnostics::record_failure(&app, &session_id, "pin_rotation", &e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        eprintln!("[PIN ROTATION] Stopping session {} after {} failed pins", session_id, failures);
+                        break;
+                    }
+                }
+            }
+
+            position = (position + 1) % targets.len();
+            tokio::select! {
+                _ = job.sleep(Duration::from_secs(pin_seconds)) => {}
+                _ = task_skip.notified() => {}
+            }
+        }
+        job.finish::<(), String>(&Err("Too many failed pins".to_string()));
+        app.state::<PinRotationState>().finish(&session_id);
+    });
+
+    state.rotations.lock().unwrap().insert(
+        info.session_id.clone(),
+        Rotation {
+            info: info.clone(),
+            current: None,
+            paused,
+            skip,
+            task,
+        },
+    );
+    Ok(info)
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+// Shopee APIs return prices multiplied by 100000
+const MICRO_UNITS: i64 = 100_000;
+
+pub const REGIONS: &[&str] = &["ID", "MY", "SG", "PH", "TH", "VN", "TW", "BR"];
+
+struct RegionFormat {
+    symbol: &'static str,
+    symbol_after: bool,
+    thousands: char,
+    decimal: char,
+    decimals: u32,
+}
+
+fn region_format(region: &str) -> RegionFormat {
+    let (symbol, symbol_after, thousands, decimal, decimals) = match region.to_uppercase().as_str() {
+        "MY" => ("RM", false, ',', '.', 2),
+        "SG" => ("$", false, ',', '.', 2),
+        "PH" => ("₱", false, ',', '.', 2),
+        "TH" => ("฿", false, ',', '.', 0),
+        "VN" => ("₫", true, '.', ',', 0),
+        "TW" => ("NT$", false, ',', '.', 0),
+        "BR" => ("R$", false, '.', ',', 2),
+        // Indonesia is the primary market
+        _ => ("Rp", false, '.', ',', 0),
+    };
+    RegionFormat { symbol, symbol_after, thousands, decimal, decimals }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawPrice {
+    pub price: i64,
+    #[serde(default)]
+    pub price_before_discount: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FormattedPrice {
+    pub price: i64,
+    pub display: String,
+    pub original_display: Option<String>,
+    pub discount_percent: Option<u32>,
+}
+
+impl FormattedPrice {
+    pub fn new(raw: &RawPrice, region: &str) -> Self {
+        let original = raw.price_before_discount.filter(|&p| p > raw.price);
+        FormattedPrice {
+            price: raw.price,
+            display: format_price(raw.price, region),
+            original_display: original.map(|p| format_price(p, region)),
+            discount_percent: original.and_then(|p| discount_percent(p, raw.price)),
+        }
+    }
+}
+
+// 12900000000 in "ID" -> "Rp129.000"
+pub fn format_price(micro_price: i64, region: &str) -> String {
+    let format = region_format(region);
+    let scale = 10i64.pow(format.decimals);
+    // Round to the region's smallest displayed unit
+    let scaled = (micro_price.abs() * scale + MICRO_UNITS / 2) / MICRO_UNITS;
+    let whole = group_thousands(scaled / scale, format.thousands);
+
+    let mut amount = whole;
+    if format.decimals > 0 {
+        amount.push(format.decimal);
+        amount.push_str(&format!("{:0width$}", scaled % scale, width = format.decimals as usize));
+    }
+
+    let sign = if micro_price < 0 { "-" } else { "" };
+    if format.symbol_after {
+        format!("{}{}{}", sign, amount, format.symbol)
+    } else {
+        format!("{}{}{}", sign, format.symbol, amount)
+    }
+}
+
+// Whole-percent discount, e.g. 200000 -> 150000 is 25
+pub fn discount_percent(original: i64, price: i64) -> Option<u32> {
+    if original <= 0 || price >= original {
+        return None;
+    }
+    let percent = ((original - price) as f64 / original as f64 * 100.0).round() as u32;
+    (percent > 0).then_some(percent)
+}
+
+fn group_thousands(value: i64, separator: char) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
@@ -0,0 +1,170 @@
+use calamine::{open_workbook_auto, Reader};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::{AggregateError, CommandError};
+use crate::jobs;
+use crate::product_items::{ProductSetItemInput, UPLOAD_CHUNK_SIZE};
+
+// Parse CSV contents with a header row. A file without a recognizable header is read
+// as one URL per line.
+pub fn parse_csv(contents: &str) -> Result<Vec<ProductSetItemInput>, String> {
+    let first_line = contents.lines().next().unwrap_or_default().to_ascii_lowercase();
+    let has_header = first_line.contains("url") || first_line.contains("link");
+
+    let mut rows = Vec::new();
+    if has_header {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(contents.as_bytes());
+        for (i, record) in reader.deserialize().enumerate() {
+            let row: ProductSetItemInput = record.map_err(|e| format!("Invalid CSV row {}: {}", i + 2, e))?;
+            rows.push(row);
+        }
+    } else {
+        for line in contents.lines() {
+            let url = line.split(',').next().unwrap_or_default().trim().trim_matches('"');
+            if !url.is_empty() {
+                rows.push(ProductSetItemInput::new(url));
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+// Spreadsheet exports: the column headed url/link is used, or the first column otherwise
+fn parse_xlsx(path: &Path) -> Result<Vec<ProductSetItemInput>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| "The workbook has no sheets".to_string())?
+        .map_err(|e| format!("Failed to read sheet: {}", e))?;
+
+    let mut rows = range.rows();
+    let first: Vec<String> = rows
+        .next()
+        .map(|row| row.iter().map(|c| c.to_string().trim().to_string()).collect())
+        .unwrap_or_default();
+    let header_column = first.iter().position(|h| {
+        let h = h.to_ascii_lowercase();
+        h == "url" || h == "link"
+    });
+    let column = header_column.unwrap_or(0);
+
+    let mut items = Vec::new();
+    if header_column.is_none() {
+        if let Some(url) = first.get(column).filter(|u| !u.is_empty()) {
+            items.push(ProductSetItemInput::new(url));
+        }
+    }
+    for row in rows {
+        let url = row.get(column).map(|c| c.to_string()).unwrap_or_default();
+        if !url.trim().is_empty() {
+            items.push(ProductSetItemInput::new(url.trim()));
+        }
+    }
+    Ok(items)
+}
+
+pub fn parse_file(path: &Path) -> Result<Vec<ProductSetItemInput>, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "csv" | "txt" => {
+            let contents = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            parse_csv(&contents)
+        }
+        "xlsx" | "xls" => parse_xlsx(path),
+        _ => Err("Unsupported file type, expected .csv or .xlsx".to_string()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemImportSummary {
+    pub total: usize,
+    pub duplicates: usize,
+    pub imported: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ItemImportProgress {
+    product_set_id: i32,
+    stage: &'static str,
+    done: usize,
+    total: usize,
+}
+
+fn emit_progress(app: &AppHandle, product_set_id: i32, stage: &'static str, done: usize, total: usize) {
+    let progress = ItemImportProgress { product_set_id, stage, done, total };
+    if let Err(e) = app.emit("product-import-progress", progress) {
+        eprintln!("Failed to emit product-import-progress: {}", e);
+    }
+}
+
+// Resolves every row's IDs, drops rows already in the file or the set, then uploads the
+// rest in chunks. Failed rows are reported together as an aggregate error.
+pub async fn import(
+    app: &AppHandle,
+    job_id: &str,
+    email: &str,
+    password: &str,
+    product_set_id: i32,
+    items: Vec<ProductSetItemInput>,
+) -> Result<ItemImportSummary, CommandError> {
+    let existing = crate::find_product_set(email, password, product_set_id).await?;
+    let mut seen: HashSet<(i64, i64)> = existing
+        .items
+        .iter()
+        .filter_map(|item| Some((item.shop_id?, item.item_id?)))
+        .collect();
+
+    let total = items.len();
+    let mut duplicates = 0;
+    let mut outcome = AggregateError::new(total);
+    let mut resolved = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let url = item.url.clone();
+        match item.resolve().await {
+            Ok(item) => {
+                let ids = (item.shop_id.unwrap_or_default(), item.item_id.unwrap_or_default());
+                if seen.insert(ids) {
+                    resolved.push((index, item));
+                } else {
+                    duplicates += 1;
+                }
+            }
+            Err(e) => outcome.push(index, url, e),
+        }
+        emit_progress(app, product_set_id, "resolving", index + 1, total);
+    }
+    jobs::log(app, job_id, format!("Resolved {} of {} rows, {} duplicates", resolved.len(), total, duplicates));
+
+    let mut uploaded = 0;
+    for chunk in resolved.chunks(UPLOAD_CHUNK_SIZE) {
+        let batch = chunk.iter().map(|(_, item)| item.clone()).collect();
+        match crate::add_product_set_items(app.clone(), email.to_string(), password.to_string(), product_set_id, batch).await {
+            Ok(summary) => {
+                outcome.succeeded += summary.added;
+                duplicates += summary.skipped.len();
+            }
+            Err(e) => {
+                for (index, item) in chunk {
+                    outcome.push(*index, item.url.clone(), e.clone());
+                }
+            }
+        }
+        uploaded += chunk.len();
+        emit_progress(app, product_set_id, "uploading", uploaded, resolved.len());
+        jobs::progress(app, job_id, uploaded, Some(resolved.len()));
+    }
+
+    let imported = outcome.into_result()?;
+    Ok(ItemImportSummary { total, duplicates, imported })
+}
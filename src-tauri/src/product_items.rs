@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::AggregateError;
+use crate::{CommandError, ProductSetItem};
+
+// Items sent per add_product_set_items call when copying or importing many items
+pub const UPLOAD_CHUNK_SIZE: usize = 50;
+
+// Result of an add: items already in the set (or repeated in the request) are skipped
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddItemsSummary {
+    pub added: usize,
+    pub skipped: Vec<ProductSetItemInput>,
+}
+
+// An item to add to a product set. Only `url` is required; shop_id and item_id are always
+// resolved from the URL before upload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductSetItemInput {
+    #[serde(alias = "URL", alias = "link", alias = "Link")]
+    pub url: String,
+    #[serde(default)]
+    pub shop_id: Option<i64>,
+    #[serde(default)]
+    pub item_id: Option<i64>,
+}
+
+impl ProductSetItemInput {
+    pub fn new(url: &str) -> Self {
+        ProductSetItemInput {
+            url: url.to_string(),
+            shop_id: None,
+            item_id: None,
+        }
+    }
+
+    // Checks the URL and fills in missing IDs from it, following short links
+    pub async fn resolve(mut self) -> Result<Self, String> {
+        let parsed = crate::shopee_url::parse(&self.url).await?;
+        if self.shop_id.is_some_and(|id| id != parsed.shop_id) || self.item_id.is_some_and(|id| id != parsed.item_id) {
+            return Err(format!("shop_id/item_id don't match the IDs in '{}'", self.url));
+        }
+        self.url = parsed.resolved_url;
+        self.shop_id = Some(parsed.shop_id);
+        self.item_id = Some(parsed.item_id);
+        Ok(self)
+    }
+}
+
+impl From<&ProductSetItem> for ProductSetItemInput {
+    fn from(item: &ProductSetItem) -> Self {
+        ProductSetItemInput {
+            url: item.url.clone(),
+            shop_id: item.shop_id,
+            item_id: item.item_id,
+        }
+    }
+}
+
+// Resolves every item before anything is sent, reporting all bad rows by index
+pub async fn resolve_all(items: Vec<ProductSetItemInput>) -> Result<Vec<ProductSetItemInput>, CommandError> {
+    let mut aggregate = AggregateError::new(items.len());
+    let mut resolved = Vec::with_capacity(items.len());
+    for (index, item) in items.into_iter().enumerate() {
+        let url = item.url.clone();
+        match item.resolve().await {
+            Ok(item) => resolved.push(item),
+            Err(e) => aggregate.push(index, url, e),
+        }
+    }
+    aggregate.succeeded = resolved.len();
+    aggregate.into_result()?;
+    Ok(resolved)
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::sync::Semaphore;
+
+use crate::price_format::{FormattedPrice, RawPrice};
+use rusqlite::OptionalExtension;
+
+use crate::{db, storage, ProductSetItem};
+
+// Where cached metadata was kept before the database
+const LEGACY_METADATA_FILE: &str = "product_metadata.json";
+const MAX_CONCURRENT_FETCHES: usize = 5;
+// Cached cards are refetched after this long
+const CACHE_TTL_SECS: u64 = 6 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductMetadata {
+    pub shop_id: i64,
+    pub item_id: i64,
+    pub title: String,
+    pub price: i64,
+    pub price_before_discount: Option<i64>,
+    pub stock: i64,
+    pub rating: Option<f64>,
+    pub image_url: Option<String>,
+    pub fetched_at: u64,
+}
+
+// What the product set editor shows for one item
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductCard {
+    pub product_set_item_id: i32,
+    pub url: String,
+    pub metadata: Option<ProductMetadata>,
+    pub price: Option<FormattedPrice>,
+    pub error: Option<String>,
+}
+
+// Keyed "shop_id.item_id" in the legacy file
+type MetadataMap = HashMap<String, ProductMetadata>;
+
+fn cached(app: &AppHandle, shop_id: i64, item_id: i64) -> Result<Option<ProductMetadata>, String> {
+    db::with(app, |conn| {
+        let data: Option<String> = conn
+            .query_row(
+                "SELECT data FROM product_metadata WHERE shop_id = ?1 AND item_id = ?2",
+                (shop_id, item_id),
+                |row| row.get(0),
+            )
+            .optional()?;
+        data.map(|data| db::from_json(&data)).transpose()
+    })
+}
+
+fn store(app: &AppHandle, fetched: &[ProductMetadata]) -> Result<(), String> {
+    db::with(app, |conn| {
+        let tx = conn.transaction()?;
+        for metadata in fetched {
+            tx.execute(
+                "INSERT OR REPLACE INTO product_metadata (shop_id, item_id, data, fetched_at) VALUES (?1, ?2, ?3, ?4)",
+                (metadata.shop_id, metadata.item_id, db::to_json(metadata)?, metadata.fetched_at as i64),
+            )?;
+        }
+        tx.commit()
+    })
+}
+
+pub fn import_legacy(app: &AppHandle) {
+    let map = match storage::load_json::<MetadataMap>(app, LEGACY_METADATA_FILE) {
+        Ok(map) if !map.is_empty() => map,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[PRODUCT METADATA] Failed to read {}: {}", LEGACY_METADATA_FILE, e);
+            return;
+        }
+    };
+    let fetched: Vec<ProductMetadata> = map.into_values().collect();
+    match store(app, &fetched).and_then(|_| storage::retire_file(app, LEGACY_METADATA_FILE)) {
+        Ok(()) => println!("[PRODUCT METADATA] Imported {} cached items into the database", fetched.len()),
+        Err(e) => eprintln!("[PRODUCT METADATA] Failed to import cached items: {}", e),
+    }
+}
+
+fn card(item: &ProductSetItem, metadata: Option<ProductMetadata>, error: Option<String>) -> ProductCard {
+    let price = metadata.as_ref().map(|m| {
+        FormattedPrice::new(
+            &RawPrice {
+                price: m.price,
+                price_before_discount: m.price_before_discount,
+            },
+            &crate::settings::current().region,
+        )
+    });
+    ProductCard {
+        product_set_item_id: item.id,
+        url: item.url.clone(),
+        metadata,
+        price,
+        error,
+    }
+}
+
+// Returns a card per item, fetching from Shopee only what is missing or stale in the cache
+pub async fn enrich(
+    app: &AppHandle,
+    items: Vec<ProductSetItem>,
+    cookie: Option<String>,
+    proxy: Option<String>,
+    refresh: bool,
+) -> Result<Vec<ProductCard>, String> {
+    let now = crate::unix_now();
+    let client = crate::shopee_api_client(proxy.as_deref())?;
+    let cookie = Arc::new(cookie);
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut cards: Vec<Option<ProductCard>> = vec![None; items.len()];
+
+    for (index, item) in items.into_iter().enumerate() {
+        let (Some(shop_id), Some(item_id)) = (item.shop_id, item.item_id) else {
+            cards[index] = Some(card(&item, None, Some("Item has no shop/item ID".to_string())));
+            continue;
+        };
+        if !refresh {
+            if let Some(metadata) = cached(app, shop_id, item_id)? {
+                if now.saturating_sub(metadata.fetched_at) < CACHE_TTL_SECS {
+                    cards[index] = Some(card(&item, Some(metadata), None));
+                    continue;
+                }
+            }
+        }
+
+        let client = client.clone();
+        let cookie = cookie.clone();
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = client.item(shop_id, item_id, cookie.as_deref()).await;
+            (index, item, result)
+        });
+    }
+
+    let mut fetched = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((index, item, result)) = joined else {
+            continue;
+        };
+        cards[index] = Some(match result {
+            Ok(Some(shopee_item)) => {
+                let metadata = ProductMetadata {
+                    shop_id: shopee_item.shopid,
+                    item_id: shopee_item.itemid,
+                    title: shopee_item.name,
+                    price: shopee_item.price,
+                    price_before_discount: shopee_item.price_before_discount,
+                    stock: shopee_item.stock,
+                    rating: shopee_item.item_rating.map(|r| r.rating_star),
+                    image_url: shopee_item
+                        .image
+                        .filter(|i| !i.is_empty())
+                        .map(|i| format!("https://down-id.img.susercontent.com/file/{}", i)),
+                    fetched_at: crate::unix_now(),
+                };
+                fetched.push(metadata.clone());
+                card(&item, Some(metadata), None)
+            }
+            Ok(None) => card(&item, None, Some("Item not found on Shopee".to_string())),
+            Err(e) => card(&item, None, Some(e.message)),
+        });
+    }
+
+    if !fetched.is_empty() {
+        store(app, &fetched)?;
+    }
+
+    Ok(cards.into_iter().flatten().collect())
+}
+
+// Image of a cached item, for the thumbnail cache
+pub fn image_url(app: &AppHandle, item_id: i64) -> Option<String> {
+    let data: String = db::with(app, |conn| {
+        conn.query_row("SELECT data FROM product_metadata WHERE item_id = ?1 LIMIT 1", [item_id], |row| row.get(0))
+            .optional()
+    })
+    .ok()??;
+    let metadata: ProductMetadata = serde_json::from_str(&data).ok()?;
+    metadata.image_url
+}
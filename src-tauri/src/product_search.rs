@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use botgacor_core::shopee::SearchItem;
+
+use crate::price_format::{FormattedPrice, RawPrice};
+use crate::product_items::ProductSetItemInput;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductCandidate {
+    pub shop_id: i64,
+    pub item_id: i64,
+    pub name: String,
+    pub url: String,
+    pub price: FormattedPrice,
+    pub sold: i64,
+    pub shop_name: Option<String>,
+    pub shop_location: Option<String>,
+    pub image_url: Option<String>,
+}
+
+impl From<SearchItem> for ProductCandidate {
+    fn from(item: SearchItem) -> Self {
+        ProductCandidate {
+            shop_id: item.shopid,
+            item_id: item.itemid,
+            url: product_url(item.shopid, item.itemid),
+            price: FormattedPrice::new(
+                &RawPrice {
+                    price: item.price,
+                    price_before_discount: None,
+                },
+                &crate::settings::current().region,
+            ),
+            name: item.name,
+            sold: item.historical_sold,
+            shop_name: item.shop_name,
+            shop_location: item.shop_location,
+            image_url: item
+                .image
+                .filter(|i| !i.is_empty())
+                .map(|i| format!("https://down-id.img.susercontent.com/file/{}", i)),
+        }
+    }
+}
+
+// A search result the user picked to add to a set
+#[derive(Debug, Clone, Deserialize)]
+pub struct SelectedProduct {
+    pub shop_id: i64,
+    pub item_id: i64,
+}
+
+impl From<&SelectedProduct> for ProductSetItemInput {
+    fn from(product: &SelectedProduct) -> Self {
+        ProductSetItemInput {
+            url: product_url(product.shop_id, product.item_id),
+            shop_id: Some(product.shop_id),
+            item_id: Some(product.item_id),
+        }
+    }
+}
+
+fn product_url(shop_id: i64, item_id: i64) -> String {
+    format!("https://shopee.co.id/product/{}/{}", shop_id, item_id)
+}
@@ -0,0 +1,114 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::error::{AggregateError, CommandError};
+use crate::{ProductSetItem, ShopeeApiFailure, ShopeeItem};
+
+const MAX_CONCURRENT_CHECKS: usize = 5;
+
+// Shopee error code for an item that no longer exists
+const ITEM_NOT_FOUND_ERRORS: &[i32] = &[4];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemState {
+    Ok,
+    Deleted,
+    OutOfStock,
+    Banned,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemCheck {
+    pub item_id: i32,
+    pub url: String,
+    pub state: ItemState,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationReport {
+    pub product_set_id: i32,
+    pub checked: usize,
+    // Only items that shouldn't be pushed to a live showcase
+    pub problems: Vec<ItemCheck>,
+}
+
+pub fn classify(result: &Result<Option<ShopeeItem>, ShopeeApiFailure>) -> (ItemState, Option<String>) {
+    match result {
+        Ok(None) => (ItemState::Deleted, None),
+        Ok(Some(item)) => match item.item_status.as_deref() {
+            Some("deleted") => (ItemState::Deleted, None),
+            Some("banned") | Some("unlisted") => (ItemState::Banned, item.item_status.clone()),
+            _ if item.stock <= 0 => (ItemState::OutOfStock, None),
+            _ => (ItemState::Ok, None),
+        },
+        Err(failure) if failure.error_code.is_some_and(|c| ITEM_NOT_FOUND_ERRORS.contains(&c)) => {
+            (ItemState::Deleted, None)
+        }
+        Err(failure) => (ItemState::Unknown, Some(failure.message.clone())),
+    }
+}
+
+// Checks every item against Shopee's item API, a few at a time. Dead or unavailable items
+// are reported as problems; items that couldn't be checked at all fail the whole check.
+pub async fn validate(
+    product_set_id: i32,
+    items: Vec<ProductSetItem>,
+    cookie: Option<String>,
+    proxy: Option<String>,
+) -> Result<ValidationReport, CommandError> {
+    let client = crate::shopee_api_client(proxy.as_deref())?;
+    let cookie = Arc::new(cookie);
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_CHECKS));
+    let mut tasks = tokio::task::JoinSet::new();
+    let checked = items.len();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let client = client.clone();
+        let cookie = cookie.clone();
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let (state, detail) = match (item.shop_id, item.item_id) {
+                (Some(shop_id), Some(shopee_item_id)) => {
+                    classify(&client.item(shop_id, shopee_item_id, cookie.as_deref()).await)
+                }
+                _ => (ItemState::Unknown, Some("Item has no shop/item ID".to_string())),
+            };
+            let check = ItemCheck {
+                item_id: item.id,
+                url: item.url,
+                state,
+                detail,
+            };
+            (index, check)
+        });
+    }
+
+    let mut problems = Vec::new();
+    let mut unchecked = AggregateError::new(checked);
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((index, check)) = joined else {
+            continue;
+        };
+        match check.state {
+            ItemState::Ok => unchecked.succeeded += 1,
+            ItemState::Unknown => unchecked.push(index, check.url, check.detail.unwrap_or_else(|| "Item could not be checked".to_string())),
+            _ => {
+                unchecked.succeeded += 1;
+                problems.push(check);
+            }
+        }
+    }
+    unchecked.into_result()?;
+    problems.sort_by_key(|c| c.item_id);
+
+    Ok(ValidationReport {
+        product_set_id,
+        checked,
+        problems,
+    })
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::jobs;
+
+// Host announcements are kept at least this far apart so the chat isn't flooded
+const MIN_INTERVAL_SECS: u64 = 60;
+const MAX_TEMPLATES: usize = 20;
+const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PromoScheduleInfo {
+    pub session_id: String,
+    pub job_id: String,
+    pub shopee_account_id: i32,
+    pub interval_secs: u64,
+    pub templates: usize,
+    pub posted: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PromoCommentPostedEvent {
+    session_id: String,
+    index: usize,
+    content: String,
+}
+
+struct Schedule {
+    info: PromoScheduleInfo,
+    task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+pub struct PromoCommentState {
+    schedules: Mutex<HashMap<String, Schedule>>,
+}
+
+impl PromoCommentState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.schedules.lock().unwrap().remove(session_id) {
+            Some(schedule) => {
+                schedule.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<PromoScheduleInfo> {
+        self.schedules.lock().unwrap().values().map(|s| s.info.clone()).collect()
+    }
+
+    fn record_post(&self, session_id: &str) {
+        if let Some(schedule) = self.schedules.lock().unwrap().get_mut(session_id) {
+            schedule.info.posted += 1;
+        }
+    }
+
+    fn finish(&self, session_id: &str) {
+        self.schedules.lock().unwrap().remove(session_id);
+    }
+}
+
+// Posts the templates in turn as the host account, one every `interval_secs`
+#[allow(clippy::too_many_arguments)]
+pub fn start(
+    app: &AppHandle,
+    state: &PromoCommentState,
+    email: String,
+    password: String,
+    shopee_account_id: i32,
+    session_id: String,
+    templates: Vec<String>,
+    interval_secs: u64,
+) -> Result<PromoScheduleInfo, String> {
+    let templates: Vec<String> = templates.into_iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+    if templates.is_empty() {
+        return Err("Add at least one message".to_string());
+    }
+    if templates.len() > MAX_TEMPLATES {
+        return Err(format!("At most {} messages can be scheduled", MAX_TEMPLATES));
+    }
+    state.stop(&session_id);
+
+    let interval_secs = interval_secs.max(MIN_INTERVAL_SECS);
+    let cancel_session = session_id.clone();
+    let job = jobs::start_background(
+        app,
+        "comment_blast",
+        format!("Post {} message(s) in session {}", templates.len(), session_id),
+        Some(shopee_account_id),
+        move |app| {
+            app.state::<PromoCommentState>().stop(&cancel_session);
+        },
+    );
+    let info = PromoScheduleInfo {
+        session_id: session_id.clone(),
+        job_id: job.id().to_string(),
+        shopee_account_id,
+        interval_secs,
+        templates: templates.len(),
+        posted: 0,
+    };
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut index = 0;
+        let mut failures = 0;
+        loop {
+            job.checkpoint().await;
+            crate::blackout::wait_until_clear(&app).await;
+            let content = templates[index].clone();
+            match crate::post_live_comment(&app, &email, &password, shopee_account_id, &session_id, &content).await {
+                Ok(()) => {
+                    failures = 0;
+                    app.state::<PromoCommentState>().record_post(&session_id);
+                    let event = PromoCommentPostedEvent {
+                        session_id: session_id.clone(),
+                        index,
+                        content,
+                    };
+                    if let Err(e) = app.emit("promo-comment-posted", event) {
+                        eprintln!("Failed to emit promo-comment-posted: {}", e);
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    crate::diagnostics::record_failure(&app, &session_id, "promo_comment", &e);
+                    if failures >= MAX_CONSECUTIVE_FAILURES {
+                        eprintln!("[PROMO] Stopping session {} after {} failed posts", session_id, failures);
+                        break;
+                    }
+                }
+            }
+            index = (index + 1) % templates.len();
+            job.sleep(Duration::from_secs(interval_secs)).await;
+        }
+        job.finish::<(), String>(&Err("Too many failed posts".to_string()));
+        app.state::<PromoCommentState>().finish(&session_id);
+    });
+
+    state
+        .schedules
+        .lock()
+        .unwrap()
+        .insert(info.session_id.clone(), Schedule { info: info.clone(), task });
+    Ok(info)
+}
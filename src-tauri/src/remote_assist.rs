@@ -0,0 +1,156 @@
+use rand::Rng;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(15);
+const MAX_DURATION_MINUTES: u64 = 60;
+
+// Telemetry only: snapshots are pushed to the backend, nothing is ever received or executed
+#[derive(Debug, Clone, Serialize)]
+pub struct AssistSession {
+    pub support_code: String,
+    pub started_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AssistStatusEvent {
+    active: bool,
+    session: Option<AssistSession>,
+}
+
+#[derive(Debug, Serialize)]
+struct Snapshot {
+    taken_at: u64,
+    app_version: String,
+    operator: Option<String>,
+    cookie_health: Vec<crate::cookie_health::CookieHealth>,
+    keepalive_running: bool,
+    live_syncs: usize,
+    failures: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct RemoteAssistState {
+    session: Mutex<Option<AssistSession>>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl RemoteAssistState {
+    pub fn current(&self) -> Option<AssistSession> {
+        self.session.lock().unwrap().clone()
+    }
+
+    fn clear(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+        *self.session.lock().unwrap() = None;
+    }
+}
+
+// Starts streaming for at most an hour under a code the user reads out to support
+pub fn start(app: &AppHandle, email: String, password: String, minutes: u64) -> Result<AssistSession, String> {
+    if minutes == 0 || minutes > MAX_DURATION_MINUTES {
+        return Err(format!("Remote assist can run for 1 to {} minutes", MAX_DURATION_MINUTES));
+    }
+
+    let state = app.state::<RemoteAssistState>();
+    state.clear();
+
+    let started_at = crate::unix_now();
+    let session = AssistSession {
+        support_code: format!("{:06}", rand::thread_rng().gen_range(0..1_000_000)),
+        started_at,
+        expires_at: started_at + minutes * 60,
+    };
+    *state.session.lock().unwrap() = Some(session.clone());
+
+    let task_app = app.clone();
+    let task_session = session.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        while crate::unix_now() < task_session.expires_at {
+            let body = serde_json::json!({
+                "email": email,
+                "password": password,
+                "snapshot": snapshot(&task_app),
+            });
+            let endpoint = format!("/api/members/support-sessions/{}/snapshots", task_session.support_code);
+            if let Err(e) = crate::make_api_request::<serde_json::Value>("POST", &endpoint, Some(&body), None).await {
+                eprintln!("[REMOTE ASSIST] Failed to send snapshot: {}", e);
+            }
+            tokio::time::sleep(SNAPSHOT_INTERVAL).await;
+        }
+
+        println!("[REMOTE ASSIST] Session {} expired", task_session.support_code);
+        *task_app.state::<RemoteAssistState>().session.lock().unwrap() = None;
+        emit_status(&task_app, None);
+    });
+    *state.task.lock().unwrap() = Some(task);
+
+    println!("[REMOTE ASSIST] Session {} started for {} minutes", session.support_code, minutes);
+    emit_status(app, Some(session.clone()));
+    Ok(session)
+}
+
+pub fn stop(app: &AppHandle) {
+    let state = app.state::<RemoteAssistState>();
+    if state.current().is_some() {
+        state.clear();
+        emit_status(app, None);
+    }
+}
+
+fn emit_status(app: &AppHandle, session: Option<AssistSession>) {
+    let event = AssistStatusEvent {
+        active: session.is_some(),
+        session,
+    };
+    if let Err(e) = app.emit("remote-assist-changed", event) {
+        eprintln!("Failed to emit remote-assist-changed: {}", e);
+    }
+}
+
+fn snapshot(app: &AppHandle) -> Snapshot {
+    let mut cookie_health: Vec<_> = crate::cookie_health::load_all(app).into_values().collect();
+    for health in &mut cookie_health {
+        health.detail = health.detail.as_deref().map(redact);
+    }
+
+    Snapshot {
+        taken_at: crate::unix_now(),
+        app_version: app.package_info().version.to_string(),
+        operator: crate::operators::current(app),
+        cookie_health,
+        keepalive_running: app.state::<crate::cookie_keepalive::CookieKeepAliveState>().is_running(),
+        live_syncs: app.state::<crate::live_sync::LiveSyncState>().list().len(),
+        failures: crate::diagnostics::recent(app)
+            .into_iter()
+            .map(|(session_id, record)| {
+                redact(&format!("[{}] {} {}: {}", record.occurred_at, session_id, record.operation, record.error))
+            })
+            .collect(),
+    }
+}
+
+// Masks cookie values, tokens and email addresses before anything leaves the machine
+fn redact(text: &str) -> String {
+    text.split_inclusive(|c: char| c.is_whitespace() || c == ';' || c == ',' || c == '&')
+        .map(|part| {
+            let trimmed = part.trim_end_matches(|c: char| c.is_whitespace() || c == ';' || c == ',' || c == '&');
+            let tail = &part[trimmed.len()..];
+            if trimmed.contains('@') {
+                format!("[email]{}", tail)
+            } else if let Some((name, _)) = trimmed.split_once('=') {
+                format!("{}=[redacted]{}", name, tail)
+            } else if trimmed.len() >= 24 && trimmed.chars().all(|c| c.is_ascii_alphanumeric() || "-_.+/".contains(c)) {
+                format!("[token]{}", tail)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
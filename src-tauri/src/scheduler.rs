@@ -0,0 +1,315 @@
+use botgacor_core::scheduler::{self as runner, Timing};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{db, encrypted_store, jobs};
+
+pub use botgacor_core::scheduler::ScheduleSpec;
+
+// Where schedules were kept before the database
+const LEGACY_SCHEDULES_FILE: &str = "schedules.enc";
+
+// Saved with the member's credentials so schedules can resume after a restart, which is
+// why each row is encrypted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredSchedule {
+    id: String,
+    email: String,
+    password: String,
+    spec: ScheduleSpec,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInfo {
+    pub id: String,
+    pub job_id: String,
+    #[serde(flatten)]
+    pub spec: ScheduleSpec,
+    pub runs: u64,
+    pub last_run_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub next_run_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScheduleRanEvent {
+    schedule_id: String,
+    shopee_account_id: i32,
+    product_set_id: i32,
+    session_id: Option<String>,
+    success: bool,
+    error: Option<String>,
+}
+
+struct Schedule {
+    info: ScheduleInfo,
+    task: JoinHandle<()>,
+}
+
+// Running schedules, keyed by schedule ID. Tasks live in the backend, so they keep going
+// while the user moves between pages.
+#[derive(Default)]
+pub struct SchedulerState {
+    schedules: Mutex<HashMap<String, Schedule>>,
+}
+
+impl SchedulerState {
+    pub fn stop(&self, id: &str) -> bool {
+        match self.schedules.lock().unwrap().remove(id) {
+            Some(schedule) => {
+                schedule.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<ScheduleInfo> {
+        let mut list: Vec<ScheduleInfo> = self.schedules.lock().unwrap().values().map(|s| s.info.clone()).collect();
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        list
+    }
+
+    fn record_run(&self, id: &str, error: Option<String>) {
+        if let Some(schedule) = self.schedules.lock().unwrap().get_mut(id) {
+            schedule.info.runs += 1;
+            schedule.info.last_run_at = Some(crate::unix_now());
+            schedule.info.last_error = error;
+        }
+    }
+
+    fn finish(&self, id: &str) {
+        self.schedules.lock().unwrap().remove(id);
+    }
+
+    fn set_next_run(&self, id: &str, wait: Duration) {
+        if let Some(schedule) = self.schedules.lock().unwrap().get_mut(id) {
+            schedule.info.next_run_at = Some(crate::unix_now() + wait.as_secs());
+        }
+    }
+}
+
+fn new_id() -> String {
+    let suffix: String = (0..8).map(|_| format!("{:x}", rand::thread_rng().gen_range(0..16u8))).collect();
+    format!("sched-{}", suffix)
+}
+
+// A running schedule's ties to the app: its background job, the member it pushes as,
+// and the state, events and storage it reports to
+struct AppHost {
+    app: AppHandle,
+    id: String,
+    email: String,
+    password: String,
+    job: jobs::JobHandle,
+}
+
+impl runner::Host for AppHost {
+    fn next_run(&self, wait: Duration) {
+        self.app.state::<SchedulerState>().set_next_run(&self.id, wait);
+    }
+
+    // A paused interval schedule's countdown stands still
+    async fn countdown(&self, wait: Duration) {
+        self.job.sleep(wait).await
+    }
+
+    async fn before_run(&self) {
+        self.job.checkpoint().await;
+        crate::blackout::wait_until_clear(&self.app).await;
+    }
+
+    // Fails if the account isn't live
+    async fn push(&self, spec: &ScheduleSpec) -> (Option<String>, Result<(), String>) {
+        let session_id = match crate::active_session_id(&self.email, &self.password, spec.shopee_account_id).await {
+            Ok(Some(session_id)) => session_id,
+            Ok(None) => return (None, Err("Account is not live".to_string())),
+            Err(e) => return (None, Err(e.to_string())),
+        };
+        let result =
+            crate::replace_products_request(&self.app, &self.email, &self.password, spec.shopee_account_id, &session_id, spec.product_set_id).await;
+        if let Err(e) = &result {
+            crate::diagnostics::record_failure(&self.app, &session_id, "schedule", e);
+        }
+        (Some(session_id), result.map(|_| ()).map_err(|e| e.to_string()))
+    }
+
+    fn ran(&self, spec: &ScheduleSpec, session_id: Option<String>, error: Option<String>) {
+        if let Some(e) = &error {
+            eprintln!("[SCHEDULER] Schedule {} failed: {}", self.id, e);
+        }
+        self.app.state::<SchedulerState>().record_run(&self.id, error.clone());
+        let event = ScheduleRanEvent {
+            schedule_id: self.id.clone(),
+            shopee_account_id: spec.shopee_account_id,
+            product_set_id: spec.product_set_id,
+            session_id,
+            success: error.is_none(),
+            error,
+        };
+        if let Err(e) = self.app.emit("schedule-ran", event) {
+            eprintln!("Failed to emit schedule-ran: {}", e);
+        }
+    }
+
+    fn finished(&self) {
+        self.app.state::<SchedulerState>().finish(&self.id);
+        if let Err(e) = forget(&self.app, &self.id) {
+            eprintln!("[SCHEDULER] Failed to remove finished schedule {}: {}", self.id, e);
+        }
+    }
+}
+
+fn spawn(app: &AppHandle, state: &SchedulerState, id: String, email: String, password: String, mut spec: ScheduleSpec) -> Result<ScheduleInfo, String> {
+    let timing = Timing::from_spec(&mut spec)?;
+    let cancel_id = id.clone();
+    let job = jobs::start_background(
+        app,
+        "schedule",
+        format!("Schedule {}: push set {}", id, spec.product_set_id),
+        Some(spec.shopee_account_id),
+        move |app| {
+            if let Err(e) = delete(app, &app.state::<SchedulerState>(), &cancel_id) {
+                eprintln!("[SCHEDULER] Failed to delete cancelled schedule {}: {}", cancel_id, e);
+            }
+        },
+    );
+    let info = ScheduleInfo {
+        id: id.clone(),
+        job_id: job.id().to_string(),
+        spec: spec.clone(),
+        runs: 0,
+        last_run_at: None,
+        last_error: None,
+        next_run_at: None,
+    };
+
+    let host = AppHost {
+        app: app.clone(),
+        id: id.clone(),
+        email,
+        password,
+        job,
+    };
+    let task = tauri::async_runtime::spawn(async move {
+        runner::run(&host, &timing, &spec).await;
+        host.job.finish::<(), String>(&Ok(()));
+    });
+
+    state.schedules.lock().unwrap().insert(id, Schedule { info: info.clone(), task });
+    Ok(info)
+}
+
+pub fn start(app: &AppHandle, state: &SchedulerState, email: String, password: String, mut spec: ScheduleSpec) -> Result<ScheduleInfo, String> {
+    Timing::from_spec(&mut spec)?;
+    let stored = StoredSchedule {
+        id: new_id(),
+        email,
+        password,
+        spec,
+    };
+    insert(app, &stored)?;
+    spawn(app, state, stored.id, stored.email, stored.password, stored.spec)
+}
+
+// Stops the schedule and removes it from disk so it doesn't come back on the next launch
+pub fn delete(app: &AppHandle, state: &SchedulerState, id: &str) -> Result<bool, String> {
+    let running = state.stop(id);
+    let stored = forget(app, id)?;
+    Ok(running || stored)
+}
+
+fn insert(app: &AppHandle, stored: &StoredSchedule) -> Result<(), String> {
+    let plaintext = serde_json::to_vec(stored).map_err(|e| format!("Failed to serialize schedule: {}", e))?;
+    let data = encrypted_store::encrypt(&plaintext)?;
+    db::with(app, |conn| {
+        conn.execute("INSERT OR REPLACE INTO schedules (id, data) VALUES (?1, ?2)", (&stored.id, &data))
+            .map(|_| ())
+    })
+}
+
+fn forget(app: &AppHandle, id: &str) -> Result<bool, String> {
+    db::with(app, |conn| conn.execute("DELETE FROM schedules WHERE id = ?1", [id]).map(|n| n > 0))
+}
+
+fn load_all(app: &AppHandle) -> Result<Vec<StoredSchedule>, String> {
+    let rows = db::with(app, |conn| {
+        let mut statement = conn.prepare("SELECT data FROM schedules")?;
+        let mut rows = Vec::new();
+        for data in statement.query_map([], |row| row.get::<_, String>(0))? {
+            rows.push(data?);
+        }
+        Ok(rows)
+    })?;
+    rows.iter()
+        .map(|data| {
+            let plaintext = encrypted_store::decrypt(data)?;
+            serde_json::from_slice(&plaintext).map_err(|e| format!("Failed to parse schedule: {}", e))
+        })
+        .collect()
+}
+
+pub fn import_legacy(app: &AppHandle) {
+    let schedules = match encrypted_store::load::<Vec<StoredSchedule>>(app, LEGACY_SCHEDULES_FILE) {
+        Ok(schedules) if !schedules.is_empty() => schedules,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[SCHEDULER] Failed to read {}: {}", LEGACY_SCHEDULES_FILE, e);
+            return;
+        }
+    };
+    let imported = schedules.iter().try_for_each(|stored| insert(app, stored));
+    match imported.and_then(|_| crate::storage::retire_file(app, LEGACY_SCHEDULES_FILE)) {
+        Ok(()) => println!("[SCHEDULER] Imported {} schedules into the database", schedules.len()),
+        Err(e) => eprintln!("[SCHEDULER] Failed to import schedules: {}", e),
+    }
+}
+
+// Decrypted schedules, for a backup that is encrypted with the user's passphrase
+pub fn export(app: &AppHandle) -> Result<Vec<serde_json::Value>, String> {
+    load_all(app)?
+        .iter()
+        .map(|stored| serde_json::to_value(stored).map_err(|e| format!("Failed to serialize schedule: {}", e)))
+        .collect()
+}
+
+// Saves schedules from a backup and starts them, replacing any with the same ID
+pub fn import(app: &AppHandle, schedules: Vec<serde_json::Value>) -> Result<usize, String> {
+    let state = app.state::<SchedulerState>();
+    let count = schedules.len();
+    for value in schedules {
+        let stored: StoredSchedule = serde_json::from_value(value).map_err(|e| format!("Invalid schedule in backup: {}", e))?;
+        insert(app, &stored)?;
+        state.stop(&stored.id);
+        spawn(app, &state, stored.id, stored.email, stored.password, stored.spec)?;
+    }
+    Ok(count)
+}
+
+// Restarts every saved schedule; called once at launch
+pub fn restore(app: &AppHandle) {
+    let schedules = match load_all(app) {
+        Ok(schedules) => schedules,
+        Err(e) => {
+            eprintln!("[SCHEDULER] Failed to load schedules: {}", e);
+            return;
+        }
+    };
+    let state = app.state::<SchedulerState>();
+    for stored in schedules {
+        // Rewritten so rows saved under an older encryption key move to the current one
+        if let Err(e) = insert(app, &stored) {
+            eprintln!("[SCHEDULER] Failed to re-save schedule {}: {}", stored.id, e);
+        }
+        let id = stored.id.clone();
+        match spawn(app, &state, stored.id, stored.email, stored.password, stored.spec) {
+            Ok(_) => println!("[SCHEDULER] Restored schedule {}", id),
+            Err(e) => eprintln!("[SCHEDULER] Failed to restore schedule {}: {}", id, e),
+        }
+    }
+}
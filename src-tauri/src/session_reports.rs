@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use botgacor_core::shopee::LiveStats;
+use rusqlite::OptionalExtension;
+
+use crate::{db, storage};
+
+// Where reports were kept before the database
+const LEGACY_REPORTS_FILE: &str = "session_reports.json";
+const MAX_REPORTS: usize = 200;
+const MAX_REPORT_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportError {
+    pub operation: String,
+    pub error: String,
+    pub occurred_at: u64,
+}
+
+// What the bot did during one monitored session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub shopee_account_id: i32,
+    pub session_id: String,
+    pub title: Option<String>,
+    pub started_at: u64,
+    // None while the session is still being monitored
+    pub ended_at: Option<u64>,
+    pub duration_secs: u64,
+    pub peak_viewers: i64,
+    pub comments_posted: u32,
+    pub pins: u32,
+    pub product_rotations: u32,
+    pub error_count: u32,
+    // The most recent errors only; `error_count` has the full count
+    pub errors: Vec<ReportError>,
+    // Shopee's counters as last seen
+    pub final_stats: Option<LiveStats>,
+}
+
+impl SessionReport {
+    fn new(shopee_account_id: i32, session_id: &str) -> Self {
+        SessionReport {
+            shopee_account_id,
+            session_id: session_id.to_string(),
+            title: None,
+            started_at: crate::unix_now(),
+            ended_at: None,
+            duration_secs: 0,
+            peak_viewers: 0,
+            comments_posted: 0,
+            pins: 0,
+            product_rotations: 0,
+            error_count: 0,
+            errors: Vec::new(),
+            final_stats: None,
+        }
+    }
+}
+
+// Reports for sessions that are still being monitored, by session ID
+#[derive(Default)]
+pub struct SessionReportState {
+    open: Mutex<HashMap<String, SessionReport>>,
+}
+
+// Starts collecting a report for the session, keeping any counts already collected
+pub fn track(app: &AppHandle, shopee_account_id: i32, session_id: &str) {
+    app.state::<SessionReportState>()
+        .open
+        .lock()
+        .unwrap()
+        .entry(session_id.to_string())
+        .or_insert_with(|| SessionReport::new(shopee_account_id, session_id));
+}
+
+// Applies `f` if the session is being monitored; other sessions aren't reported on
+fn modify(app: &AppHandle, session_id: &str, f: impl FnOnce(&mut SessionReport)) {
+    if let Some(report) = app.state::<SessionReportState>().open.lock().unwrap().get_mut(session_id) {
+        f(report);
+    }
+}
+
+pub fn record_comment(app: &AppHandle, session_id: &str) {
+    modify(app, session_id, |report| report.comments_posted += 1);
+}
+
+pub fn record_pin(app: &AppHandle, session_id: &str) {
+    modify(app, session_id, |report| report.pins += 1);
+}
+
+pub fn record_rotation(app: &AppHandle, session_id: &str) {
+    modify(app, session_id, |report| report.product_rotations += 1);
+}
+
+pub fn record_error(app: &AppHandle, session_id: &str, operation: &str, error: &str) {
+    modify(app, session_id, |report| {
+        report.error_count += 1;
+        if report.errors.len() >= MAX_REPORT_ERRORS {
+            report.errors.remove(0);
+        }
+        report.errors.push(ReportError {
+            operation: operation.to_string(),
+            error: error.to_string(),
+            occurred_at: crate::unix_now(),
+        });
+    });
+}
+
+// Called with every stats fetch; once Shopee reports the session over, the report is closed
+pub fn record_stats(app: &AppHandle, session_id: &str, stats: &LiveStats) {
+    modify(app, session_id, |report| {
+        report.peak_viewers = report.peak_viewers.max(stats.viewers);
+        if stats.title.is_some() {
+            report.title = stats.title.clone();
+        }
+        report.final_stats = Some(stats.clone());
+    });
+    if !stats.is_live() {
+        close(app, session_id);
+    }
+}
+
+// Saves the session's report and emits `session-report`. Does nothing if the session
+// wasn't monitored or its report was already closed.
+pub fn close(app: &AppHandle, session_id: &str) {
+    let Some(mut report) = app.state::<SessionReportState>().open.lock().unwrap().remove(session_id) else {
+        return;
+    };
+    let ended_at = crate::unix_now();
+    report.ended_at = Some(ended_at);
+    report.duration_secs = ended_at.saturating_sub(report.started_at);
+
+    if let Err(e) = insert(app, &report) {
+        eprintln!("[SESSION REPORT] Failed to save report for session {}: {}", session_id, e);
+    }
+    if let Err(e) = app.emit("session-report", &report) {
+        eprintln!("Failed to emit session-report: {}", e);
+    }
+}
+
+// The saved report, or the report so far while the session is still monitored
+pub fn get(app: &AppHandle, session_id: &str) -> Result<Option<SessionReport>, String> {
+    if let Some(report) = app.state::<SessionReportState>().open.lock().unwrap().get(session_id) {
+        let mut report = report.clone();
+        report.duration_secs = crate::unix_now().saturating_sub(report.started_at);
+        return Ok(Some(report));
+    }
+    db::with(app, |conn| {
+        let data: Option<String> = conn
+            .query_row("SELECT data FROM session_reports WHERE session_id = ?1", [session_id], |row| row.get(0))
+            .optional()?;
+        data.map(|data| db::from_json(&data)).transpose()
+    })
+}
+
+// Saves a closed report, keeping only the newest MAX_REPORTS
+fn insert(app: &AppHandle, report: &SessionReport) -> Result<(), String> {
+    db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO session_reports (session_id, shopee_account_id, ended_at, data) VALUES (?1, ?2, ?3, ?4)",
+            (
+                &report.session_id,
+                report.shopee_account_id,
+                report.ended_at.unwrap_or_default() as i64,
+                db::to_json(report)?,
+            ),
+        )?;
+        conn.execute(
+            "DELETE FROM session_reports WHERE session_id NOT IN
+                (SELECT session_id FROM session_reports ORDER BY ended_at DESC LIMIT ?1)",
+            [MAX_REPORTS as i64],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn import_legacy(app: &AppHandle) {
+    let reports = match storage::load_json::<Vec<SessionReport>>(app, LEGACY_REPORTS_FILE) {
+        Ok(reports) if !reports.is_empty() => reports,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[SESSION REPORT] Failed to read {}: {}", LEGACY_REPORTS_FILE, e);
+            return;
+        }
+    };
+    let imported = reports.iter().try_for_each(|report| insert(app, report));
+    match imported.and_then(|_| storage::retire_file(app, LEGACY_REPORTS_FILE)) {
+        Ok(()) => println!("[SESSION REPORT] Imported {} reports into the database", reports.len()),
+        Err(e) => eprintln!("[SESSION REPORT] Failed to import reports: {}", e),
+    }
+}
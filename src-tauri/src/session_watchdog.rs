@@ -0,0 +1,205 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::live_sync::LiveSyncBinding;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// Restarts per watchdog run; a session that keeps dying needs a human
+const MAX_RESTARTS: u32 = 3;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchdogInfo {
+    pub shopee_account_id: i32,
+    pub session_id: String,
+    pub product_set_id: i32,
+    pub restarts: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SessionRestartedEvent {
+    shopee_account_id: i32,
+    old_session_id: String,
+    new_session_id: String,
+    product_set_id: i32,
+    restarts: u32,
+    products_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WatchdogStoppedEvent {
+    shopee_account_id: i32,
+    session_id: String,
+    reason: String,
+}
+
+struct Watchdog {
+    info: WatchdogInfo,
+    task: JoinHandle<()>,
+}
+
+// One watchdog per account, since a restart moves it to a new session ID
+#[derive(Default)]
+pub struct SessionWatchdogState {
+    watchdogs: Mutex<HashMap<i32, Watchdog>>,
+}
+
+impl SessionWatchdogState {
+    pub fn stop(&self, shopee_account_id: i32) -> bool {
+        match self.watchdogs.lock().unwrap().remove(&shopee_account_id) {
+            Some(watchdog) => {
+                watchdog.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Called when a session is ended on purpose so it isn't restarted. Returns what was
+    // watched, so the watchdog can be put back if ending the session fails.
+    pub fn stop_session(&self, session_id: &str) -> Option<WatchdogInfo> {
+        let info = self
+            .watchdogs
+            .lock()
+            .unwrap()
+            .values()
+            .find(|w| w.info.session_id == session_id)
+            .map(|w| w.info.clone())?;
+        self.stop(info.shopee_account_id);
+        Some(info)
+    }
+
+    pub fn list(&self) -> Vec<WatchdogInfo> {
+        self.watchdogs.lock().unwrap().values().map(|w| w.info.clone()).collect()
+    }
+
+    fn restarted(&self, shopee_account_id: i32, session_id: &str, restarts: u32) {
+        if let Some(watchdog) = self.watchdogs.lock().unwrap().get_mut(&shopee_account_id) {
+            watchdog.info.session_id = session_id.to_string();
+            watchdog.info.restarts = restarts;
+        }
+    }
+
+    fn finish(&self, shopee_account_id: i32) {
+        self.watchdogs.lock().unwrap().remove(&shopee_account_id);
+    }
+}
+
+fn emit_stopped(app: &AppHandle, shopee_account_id: i32, session_id: &str, reason: String) {
+    let event = WatchdogStoppedEvent {
+        shopee_account_id,
+        session_id: session_id.to_string(),
+        reason,
+    };
+    if let Err(e) = app.emit("session-watchdog-stopped", event) {
+        eprintln!("Failed to emit session-watchdog-stopped: {}", e);
+    }
+}
+
+// Checks the session's stats and push status on Shopee every CHECK_INTERVAL. When it has
+// ended without end_live_session being called, a new session is created with the same title,
+// the product set is pushed to it, and `session-restarted` is emitted.
+pub fn start(app: &AppHandle, state: &SessionWatchdogState, binding: LiveSyncBinding, title: Option<String>) -> WatchdogInfo {
+    let LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        mut session_id,
+        email,
+        password,
+    } = binding;
+    state.stop(shopee_account_id);
+
+    let info = WatchdogInfo {
+        shopee_account_id,
+        session_id: session_id.clone(),
+        product_set_id,
+        restarts: 0,
+    };
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let mut restarts = 0;
+        let mut title = title;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            let stats = crate::fetch_live_stats(&app, &email, &password, shopee_account_id, &session_id).await;
+            match &stats {
+                Ok(stats) if stats.is_live() => {
+                    if stats.title.is_some() {
+                        title = stats.title.clone();
+                    }
+                    continue;
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[WATCHDOG] Status check for session {} failed: {}", session_id, e),
+            }
+            // Either the stats say the session ended or they couldn't be read; the push status
+            // decides, so one lagging or failing endpoint doesn't restart a healthy live
+            match crate::fetch_push_status(&app, &email, &password, shopee_account_id, &session_id).await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("[WATCHDOG] Push status check for session {} failed: {}", session_id, e);
+                    continue;
+                }
+            }
+
+            if restarts >= MAX_RESTARTS {
+                emit_stopped(&app, shopee_account_id, &session_id, format!("Session ended again after {} restarts", restarts));
+                break;
+            }
+            crate::blackout::wait_until_clear(&app).await;
+
+            println!("[WATCHDOG] Session {} ended unexpectedly, starting a new one", session_id);
+            let new_title = title.clone().unwrap_or_else(|| "Live".to_string());
+            let new_session_id = match crate::create_live_session(app.clone(), email.clone(), password.clone(), shopee_account_id, new_title, None).await {
+                Ok(id) => id,
+                Err(e) => {
+                    crate::diagnostics::record_failure(&app, &session_id, "session_restart", &e);
+                    emit_stopped(&app, shopee_account_id, &session_id, format!("Failed to create a new session: {}", e));
+                    break;
+                }
+            };
+            restarts += 1;
+
+            let products_error =
+                crate::replace_products_request(&app, &email, &password, shopee_account_id, &new_session_id, product_set_id)
+                    .await
+                    .err()
+                    .map(|e| e.to_string());
+
+            app.state::<SessionWatchdogState>().restarted(shopee_account_id, &new_session_id, restarts);
+            crate::notifications::notify(
+                &app,
+                crate::notifications::NotificationEvent::SessionRestarted,
+                &[
+                    ("account_id", shopee_account_id.to_string()),
+                    ("session_id", new_session_id.clone()),
+                    ("restarts", restarts.to_string()),
+                ],
+            );
+            let event = SessionRestartedEvent {
+                shopee_account_id,
+                old_session_id: std::mem::replace(&mut session_id, new_session_id.clone()),
+                new_session_id,
+                product_set_id,
+                restarts,
+                products_error,
+            };
+            if let Err(e) = app.emit("session-restarted", event) {
+                eprintln!("Failed to emit session-restarted: {}", e);
+            }
+        }
+        app.state::<SessionWatchdogState>().finish(shopee_account_id);
+    });
+
+    state
+        .watchdogs
+        .lock()
+        .unwrap()
+        .insert(shopee_account_id, Watchdog { info: info.clone(), task });
+    info
+}
@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use botgacor_core::members;
+
+use crate::pacing::PacingProfile;
+use crate::storage;
+
+const SETTINGS_FILE: &str = "settings.json";
+// Bump when a field changes meaning, and migrate older files in `migrate`
+const SETTINGS_VERSION: u32 = 1;
+const MAX_RETENTION_DAYS: u64 = 3650;
+
+// Loaded at startup so request code without an AppHandle can read it
+static CURRENT: Mutex<Option<Settings>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+}
+
+// Which livekenceng backend the app talks to. A custom URL is for mirror domains when the
+// main one is blocked by DNS.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiEnvironment {
+    #[default]
+    Production,
+    Staging,
+    Custom { url: String },
+}
+
+impl ApiEnvironment {
+    pub fn base_url(&self) -> &str {
+        match self {
+            ApiEnvironment::Production => members::DEFAULT_BASE_URL,
+            ApiEnvironment::Staging => members::STAGING_BASE_URL,
+            ApiEnvironment::Custom { url } => url,
+        }
+    }
+
+    fn validate(&mut self) -> Result<(), String> {
+        if let ApiEnvironment::Custom { url } = self {
+            *url = url.trim().trim_end_matches('/').to_string();
+            let parsed = url::Url::parse(url).map_err(|e| format!("Invalid API URL: {}", e))?;
+            if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+                return Err("API URL must be an http:// or https:// address".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+// Used when a command doesn't pass its own interval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomationDefaults {
+    pub viewer_poll_secs: u64,
+    pub heartbeat_secs: u64,
+    pub pin_interval_secs: u64,
+    pub showcase_interval_minutes: u64,
+}
+
+impl Default for AutomationDefaults {
+    fn default() -> Self {
+        AutomationDefaults {
+            viewer_poll_secs: 10,
+            heartbeat_secs: 30,
+            pin_interval_secs: 60,
+            showcase_interval_minutes: 30,
+        }
+    }
+}
+
+// Which events raise a native desktop notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesktopNotifications {
+    pub qr_confirmed: bool,
+    pub session_restarted: bool,
+    pub job_failed: bool,
+}
+
+impl Default for DesktopNotifications {
+    fn default() -> Self {
+        DesktopNotifications {
+            qr_confirmed: true,
+            session_restarted: true,
+            job_failed: true,
+        }
+    }
+}
+
+// How long cleanup keeps each kind of cached or historical data, in days. 0 removes all of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub image_cache_days: u64,
+    pub product_metadata_days: u64,
+    pub showcase_snapshot_days: u64,
+    pub session_report_days: u64,
+    pub job_history_days: u64,
+    pub old_backup_days: u64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy {
+            image_cache_days: 30,
+            product_metadata_days: 7,
+            showcase_snapshot_days: 14,
+            session_report_days: 180,
+            job_history_days: 90,
+            old_backup_days: 30,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        let days = [
+            self.image_cache_days,
+            self.product_metadata_days,
+            self.showcase_snapshot_days,
+            self.session_report_days,
+            self.job_history_days,
+            self.old_backup_days,
+        ];
+        if days.iter().any(|d| *d > MAX_RETENTION_DAYS) {
+            return Err(format!("Retention periods must be at most {} days", MAX_RETENTION_DAYS));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub environment: ApiEnvironment,
+    // Applies to backend and Shopee requests
+    pub request_timeout_secs: u64,
+    // Shopee region code, used for price formatting
+    pub region: String,
+    // Used by accounts without a proxy of their own
+    pub default_proxy: Option<String>,
+    pub pacing_profile: PacingProfile,
+    pub log_level: LogLevel,
+    pub automation: AutomationDefaults,
+    pub retention: RetentionPolicy,
+    pub desktop_notifications: DesktopNotifications,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            environment: ApiEnvironment::default(),
+            request_timeout_secs: 30,
+            region: "ID".to_string(),
+            default_proxy: None,
+            pacing_profile: PacingProfile::default(),
+            log_level: LogLevel::default(),
+            automation: AutomationDefaults::default(),
+            retention: RetentionPolicy::default(),
+            desktop_notifications: DesktopNotifications::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    fn validate(&mut self) -> Result<(), String> {
+        self.environment.validate()?;
+        if !(5..=300).contains(&self.request_timeout_secs) {
+            return Err("Request timeout must be between 5 and 300 seconds".to_string());
+        }
+        self.region = self.region.trim().to_uppercase();
+        if !crate::price_format::REGIONS.contains(&self.region.as_str()) {
+            return Err(format!("Unknown region '{}', expected one of {}", self.region, crate::price_format::REGIONS.join(", ")));
+        }
+        self.default_proxy = self.default_proxy.take().map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        if let Some(proxy) = &self.default_proxy {
+            crate::account_proxies::validate(proxy)?;
+        }
+        let automation = &self.automation;
+        if automation.viewer_poll_secs < 5 {
+            return Err("Viewer polling interval must be at least 5 seconds".to_string());
+        }
+        if automation.heartbeat_secs < 15 {
+            return Err("Heartbeat interval must be at least 15 seconds".to_string());
+        }
+        if automation.pin_interval_secs < crate::pin_rotation::MIN_PIN_SECS {
+            return Err(format!("Pin interval must be at least {} seconds", crate::pin_rotation::MIN_PIN_SECS));
+        }
+        if automation.showcase_interval_minutes == 0 {
+            return Err("Showcase rotation interval must be at least 1 minute".to_string());
+        }
+        self.retention.validate()
+    }
+}
+
+// Brings a file written by an older version up to SETTINGS_VERSION
+fn migrate(settings: &mut Settings) -> Result<(), String> {
+    if settings.version > SETTINGS_VERSION {
+        return Err(format!(
+            "Settings were saved by a newer version of the app (version {}), please update",
+            settings.version
+        ));
+    }
+    settings.version = SETTINGS_VERSION;
+    Ok(())
+}
+
+fn load(app: &AppHandle) -> Result<Settings, String> {
+    let mut settings: Settings = storage::load_json(app, SETTINGS_FILE)?;
+    migrate(&mut settings)?;
+    Ok(settings)
+}
+
+// Reads the settings file into memory; falls back to defaults if it can't be used
+pub fn init(app: &AppHandle) {
+    let settings = load(app).unwrap_or_else(|e| {
+        eprintln!("[SETTINGS] Using defaults: {}", e);
+        Settings::default()
+    });
+    *CURRENT.lock().unwrap() = Some(settings);
+}
+
+pub fn current() -> Settings {
+    CURRENT.lock().unwrap().clone().unwrap_or_default()
+}
+
+pub fn logs(level: LogLevel) -> bool {
+    level <= current().log_level
+}
+
+// Validates the whole new settings before anything is written
+pub fn save(app: &AppHandle, mut settings: Settings) -> Result<Settings, String> {
+    migrate(&mut settings)?;
+    settings.validate()?;
+    let _guard = storage::lock();
+    storage::save_json(app, SETTINGS_FILE, &settings)?;
+    *CURRENT.lock().unwrap() = Some(settings.clone());
+    Ok(settings)
+}
+
+pub fn update(app: &AppHandle, f: impl FnOnce(&mut Settings)) -> Result<Settings, String> {
+    let mut settings = current();
+    f(&mut settings);
+    save(app, settings)
+}
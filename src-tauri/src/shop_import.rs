@@ -0,0 +1,140 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use botgacor_core::shopee::{self, ShopItem};
+
+use crate::error::CommandError;
+use crate::jobs;
+use crate::product_items::{ProductSetItemInput, UPLOAD_CHUNK_SIZE};
+use crate::ShopeeApiFailure;
+
+const PAGE_SIZE: usize = 30;
+// Pause between listing pages so a large shop doesn't trip Shopee's rate limiter
+const PAGE_DELAY: Duration = Duration::from_millis(1500);
+const MAX_RETRIES: u32 = 4;
+const RATE_LIMIT_ERRORS: &[i32] = &[90309999];
+
+#[derive(Debug, Serialize)]
+pub struct ShopImportSummary {
+    pub shop_id: i64,
+    pub found: usize,
+    pub duplicates: usize,
+    pub added: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ShopImportProgress {
+    product_set_id: i32,
+    stage: &'static str,
+    done: usize,
+    total: Option<usize>,
+}
+
+fn emit_progress(app: &AppHandle, product_set_id: i32, stage: &'static str, done: usize, total: Option<usize>) {
+    let progress = ShopImportProgress { product_set_id, stage, done, total };
+    if let Err(e) = app.emit("shop-import-progress", progress) {
+        eprintln!("Failed to emit shop-import-progress: {}", e);
+    }
+}
+
+fn is_rate_limited(failure: &ShopeeApiFailure) -> bool {
+    failure.http_status == Some(429) || failure.error_code.is_some_and(|c| RATE_LIMIT_ERRORS.contains(&c))
+}
+
+// shopee.co.id/shop/123 or shopee.co.id/<username>
+pub async fn shop_id_from_url(client: &shopee::Client, shop_url: &str) -> Result<i64, String> {
+    let url = url::Url::parse(shop_url.trim()).map_err(|_| format!("'{}' is not a valid URL", shop_url))?;
+    if !url.host_str().is_some_and(|h| h.contains("shopee")) {
+        return Err(format!("'{}' is not a Shopee URL", shop_url));
+    }
+    let segments: Vec<&str> = url.path_segments().map(|s| s.filter(|s| !s.is_empty()).collect()).unwrap_or_default();
+    match segments.as_slice() {
+        ["shop", id, ..] => id.parse().map_err(|_| format!("Invalid shop ID in '{}'", shop_url)),
+        [username] => Ok(client.shop_id_by_username(username).await?),
+        _ => Err(format!("'{}' is not a shop URL", shop_url)),
+    }
+}
+
+async fn fetch_page(client: &shopee::Client, shop_id: i64, offset: usize, cookie: Option<&str>) -> Result<(Vec<ShopItem>, bool), ShopeeApiFailure> {
+    let mut attempt = 0;
+    loop {
+        match client.shop_items(shop_id, offset, PAGE_SIZE, cookie).await {
+            Err(failure) if is_rate_limited(&failure) && attempt < MAX_RETRIES => {
+                attempt += 1;
+                let backoff = Duration::from_secs(5 * 2u64.pow(attempt));
+                println!("[SHOP IMPORT] Rate limited, retrying page at offset {} in {:?}", offset, backoff);
+                tokio::time::sleep(backoff).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+// Walks the shop listing up to `limit` products and appends the ones the set doesn't have yet
+#[allow(clippy::too_many_arguments)]
+pub async fn import(
+    app: &AppHandle,
+    job_id: &str,
+    email: &str,
+    password: &str,
+    client: &shopee::Client,
+    cookie: Option<&str>,
+    shop_id: i64,
+    product_set_id: i32,
+    limit: usize,
+) -> Result<ShopImportSummary, CommandError> {
+    let existing = crate::find_product_set(email, password, product_set_id).await?;
+    let mut seen: HashSet<(i64, i64)> = existing
+        .items
+        .iter()
+        .filter_map(|item| Some((item.shop_id?, item.item_id?)))
+        .collect();
+
+    let mut found = 0;
+    let mut duplicates = 0;
+    let mut items = Vec::new();
+    let mut offset = 0;
+    while found < limit {
+        let (page, has_more) = fetch_page(client, shop_id, offset, cookie).await.map_err(String::from)?;
+        for item in page.into_iter().take(limit - found) {
+            found += 1;
+            if seen.insert((item.shopid, item.itemid)) {
+                items.push(ProductSetItemInput {
+                    url: format!("https://shopee.co.id/product/{}/{}", item.shopid, item.itemid),
+                    shop_id: Some(item.shopid),
+                    item_id: Some(item.itemid),
+                });
+            } else {
+                duplicates += 1;
+            }
+        }
+        emit_progress(app, product_set_id, "listing", found, None);
+        jobs::progress(app, job_id, found, None);
+        if !has_more {
+            break;
+        }
+        offset += PAGE_SIZE;
+        tokio::time::sleep(PAGE_DELAY).await;
+    }
+
+    let mut added = 0;
+    let mut uploaded = 0;
+    for chunk in items.chunks(UPLOAD_CHUNK_SIZE) {
+        let summary =
+            crate::add_product_set_items(app.clone(), email.to_string(), password.to_string(), product_set_id, chunk.to_vec()).await?;
+        uploaded += chunk.len();
+        added += summary.added;
+        duplicates += summary.skipped.len();
+        emit_progress(app, product_set_id, "uploading", uploaded, Some(items.len()));
+        jobs::progress(app, job_id, uploaded, Some(items.len()));
+    }
+
+    Ok(ShopImportSummary {
+        shop_id,
+        found,
+        duplicates,
+        added,
+    })
+}
@@ -0,0 +1,196 @@
+use serde::Serialize;
+use url::Url;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParsedShopeeUrl {
+    pub url: String,
+    // Product URL after following a short link, same as `url` otherwise
+    pub resolved_url: String,
+    pub shop_id: i64,
+    pub item_id: i64,
+}
+
+// Shopee's storefront domains; subdomains such as s., live. and mall. are matched too
+const SHOPEE_DOMAINS: &[&str] = &[
+    "shopee.co.id",
+    "shopee.com",
+    "shopee.sg",
+    "shopee.com.my",
+    "shopee.ph",
+    "shopee.vn",
+    "shopee.co.th",
+    "shopee.tw",
+    "shopee.com.br",
+    "shopee.com.mx",
+    "shopee.com.co",
+    "shopee.cl",
+];
+
+fn is_domain_or_subdomain(host: &str, domain: &str) -> bool {
+    host == domain || host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+pub fn is_shopee_host(host: &str) -> bool {
+    SHOPEE_DOMAINS.iter().any(|d| is_domain_or_subdomain(host, d)) || is_short_link_host(host)
+}
+
+// shp.ee, id.shp.ee, s.shopee.co.id ...
+fn is_short_link_host(host: &str) -> bool {
+    is_domain_or_subdomain(host, "shp.ee")
+        || host.strip_prefix("s.").is_some_and(|domain| SHOPEE_DOMAINS.contains(&domain))
+}
+
+// IDs from any long-form product URL:
+//   /Some-Product-i.123.456, /i.123.456, /product/123/456, ?shopid=123&itemid=456
+pub fn ids_from_url(url: &Url) -> Option<(i64, i64)> {
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    if let [.., "product", shop, item] = segments.as_slice() {
+        if let (Ok(shop), Ok(item)) = (shop.parse(), item.parse()) {
+            return Some((shop, item));
+        }
+    }
+    if let Some(last) = segments.last() {
+        let ids = last.rsplit_once("-i.").map(|(_, ids)| ids).or_else(|| last.strip_prefix("i."));
+        if let Some((shop, item)) = ids.and_then(|ids| ids.split_once('.')) {
+            if let (Ok(shop), Ok(item)) = (shop.parse(), item.parse()) {
+                return Some((shop, item));
+            }
+        }
+    }
+
+    let query = |key: &str| url.query_pairs().find(|(k, _)| k == key).and_then(|(_, v)| v.parse().ok());
+    if let (Some(shop), Some(item)) = (query("shopid").or_else(|| query("shop_id")), query("itemid").or_else(|| query("item_id"))) {
+        return Some((shop, item));
+    }
+
+    // App links wrap the product URL in a redir parameter
+    let redir = url.query_pairs().find(|(k, _)| k == "redir").map(|(_, v)| v.into_owned())?;
+    ids_from_url(&Url::parse(&redir).ok()?)
+}
+
+// Parses the URL, following short links to the product page when needed
+pub async fn parse(raw: &str) -> Result<ParsedShopeeUrl, String> {
+    let raw = raw.trim();
+    let url = Url::parse(raw).map_err(|_| format!("'{}' is not a valid URL", raw))?;
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return Err(format!("'{}' must be an http(s) URL", raw));
+    }
+    let host = url.host_str().unwrap_or_default().to_string();
+    if !is_shopee_host(&host) {
+        return Err(format!("'{}' is not a Shopee URL", raw));
+    }
+
+    let resolved = match ids_from_url(&url) {
+        Some(_) => url,
+        None if is_short_link_host(&host) => resolve_short_link(&url).await?,
+        None => return Err(format!("No shop/item ID found in '{}'", raw)),
+    };
+    let (shop_id, item_id) = ids_from_url(&resolved).ok_or_else(|| format!("No shop/item ID found in '{}'", raw))?;
+
+    Ok(ParsedShopeeUrl {
+        url: raw.to_string(),
+        resolved_url: resolved.to_string(),
+        shop_id,
+        item_id,
+    })
+}
+
+async fn resolve_short_link(url: &Url) -> Result<Url, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(botgacor_core::shopee::DESKTOP_USER_AGENT)
+        // Redirects are only followed within Shopee; anything else stops here and is rejected below
+        .redirect(reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= 10 {
+                attempt.error("too many redirects")
+            } else if attempt.url().host_str().is_some_and(is_shopee_host) {
+                attempt.follow()
+            } else {
+                attempt.stop()
+            }
+        }))
+        .build()
+        .map_err(|e| format!("Failed to create client: {}", e))?;
+    let response = client
+        .get(url.as_str())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to resolve short link {}: {}", url, e))?;
+    let resolved = response.url().clone();
+    if !resolved.host_str().is_some_and(is_shopee_host) {
+        return Err(format!("Short link {} led outside Shopee to {}", url, resolved));
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(raw: &str) -> Option<(i64, i64)> {
+        ids_from_url(&Url::parse(raw).unwrap())
+    }
+
+    #[test]
+    fn product_path() {
+        assert_eq!(ids("https://shopee.co.id/product/123/456"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/product/123/456/"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/product/abc/456"), None);
+    }
+
+    #[test]
+    fn slug_with_ids() {
+        assert_eq!(ids("https://shopee.co.id/Kaos-Polos-i.123.456"), Some((123, 456)));
+        // Only the last "-i." counts, so product names containing one still parse
+        assert_eq!(ids("https://shopee.co.id/Baju-i.Ready-Stock-i.123.456"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/i.123.456"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/Kaos-Polos-i.123"), None);
+    }
+
+    #[test]
+    fn query_and_fragment_are_ignored_for_path_ids() {
+        assert_eq!(ids("https://shopee.co.id/Kaos-i.123.456?sp_atk=abc&xptdk=def#reviews"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/product/123/456?smtt=0.0.9#top"), Some((123, 456)));
+    }
+
+    #[test]
+    fn ids_from_query() {
+        assert_eq!(ids("https://shopee.co.id/universal-link?shopid=123&itemid=456"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/x?item_id=456&shop_id=123"), Some((123, 456)));
+        assert_eq!(ids("https://shopee.co.id/x?shopid=123"), None);
+    }
+
+    #[test]
+    fn ids_from_redir() {
+        let wrapped = "https://shopee.co.id/universal-link?redir=https%3A%2F%2Fshopee.co.id%2FKaos-i.123.456%3Fsp_atk%3Dabc";
+        assert_eq!(ids(wrapped), Some((123, 456)));
+    }
+
+    #[test]
+    fn shop_pages_have_no_ids() {
+        assert_eq!(ids("https://shopee.co.id/tokobaju"), None);
+        assert_eq!(ids("https://shopee.co.id/"), None);
+    }
+
+    #[test]
+    fn accepts_shopee_domains_and_subdomains() {
+        for host in ["shopee.co.id", "mall.shopee.co.id", "live.shopee.co.id", "shopee.com.my", "s.shopee.co.id", "shp.ee", "id.shp.ee"] {
+            assert!(is_shopee_host(host), "{}", host);
+        }
+    }
+
+    #[test]
+    fn rejects_lookalike_hosts() {
+        for host in ["shopee.attacker.com", "notshopee.evil", "shopee.co.id.evil.com", "evilshopee.co.id", "s.shopee.evil", "shp.ee.evil", "xshp.ee"] {
+            assert!(!is_shopee_host(host), "{}", host);
+        }
+    }
+
+    #[test]
+    fn short_link_hosts() {
+        assert!(is_short_link_host("shp.ee"));
+        assert!(is_short_link_host("id.shp.ee"));
+        assert!(is_short_link_host("s.shopee.co.id"));
+        assert!(!is_short_link_host("shopee.co.id"));
+        assert!(!is_short_link_host("s.shopee.attacker.com"));
+    }
+}
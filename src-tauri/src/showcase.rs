@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::error::{AggregateError, CommandError};
+use crate::jobs;
+use crate::live_sync::LiveSyncBinding;
+
+// Shopee rejects a live showcase holding more products than this
+pub const SESSION_LIMIT: usize = 500;
+const MIN_ROTATION_MINUTES: u64 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RotationInfo {
+    pub session_id: String,
+    pub job_id: String,
+    pub product_set_id: i32,
+    pub shopee_account_id: i32,
+    pub interval_minutes: u64,
+    pub chunk: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ShowcaseRotatedEvent {
+    session_id: String,
+    product_set_id: i32,
+    chunk: usize,
+    success: bool,
+    error: Option<String>,
+}
+
+struct Rotation {
+    info: RotationInfo,
+    task: JoinHandle<()>,
+}
+
+// Sessions whose oversized set is pushed one chunk at a time, keyed by session ID
+#[derive(Default)]
+pub struct ShowcaseRotationState {
+    rotations: Mutex<HashMap<String, Rotation>>,
+}
+
+impl ShowcaseRotationState {
+    pub fn stop(&self, session_id: &str) -> bool {
+        match self.rotations.lock().unwrap().remove(session_id) {
+            Some(rotation) => {
+                rotation.task.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn list(&self) -> Vec<RotationInfo> {
+        self.rotations.lock().unwrap().values().map(|r| r.info.clone()).collect()
+    }
+
+    // Chunk currently shown for a session; sessions without a rotation always show the first
+    pub fn current_chunk(&self, session_id: &str) -> Option<usize> {
+        self.rotations.lock().unwrap().get(session_id).map(|r| r.info.chunk)
+    }
+
+    fn advance(&self, session_id: &str) -> Option<usize> {
+        let mut rotations = self.rotations.lock().unwrap();
+        let rotation = rotations.get_mut(session_id)?;
+        rotation.info.chunk += 1;
+        Some(rotation.info.chunk)
+    }
+}
+
+// Items sent per replace/add call, so large sets report progress as they go
+pub const PUSH_BATCH_SIZE: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemFailure {
+    // Position in the push order
+    #[serde(default)]
+    pub index: usize,
+    pub product_set_item_id: i32,
+    pub error: String,
+}
+
+// Set when the set is larger than the showcase can hold
+#[derive(Debug, Clone, Serialize)]
+pub struct ShowcaseNotice {
+    pub total: usize,
+    pub pushed: usize,
+    pub chunk: usize,
+    pub chunks: usize,
+    pub rotating: bool,
+    pub warning: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplaceSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failures: Vec<ItemFailure>,
+    pub showcase: Option<ShowcaseNotice>,
+}
+
+impl ReplaceSummary {
+    // Items that didn't make it into the showcase as an aggregate error, by item ID
+    pub fn check_failures(&self) -> Result<(), CommandError> {
+        let mut failed = AggregateError::new(self.total);
+        failed.succeeded = self.succeeded;
+        for failure in &self.failures {
+            failed.push(failure.index, failure.product_set_item_id.to_string(), failure.error.clone());
+        }
+        failed.into_result().map(|_| ())
+    }
+}
+
+pub fn chunk_count(total: usize) -> usize {
+    total.div_ceil(SESSION_LIMIT).max(1)
+}
+
+// The slice of the push list shown for a chunk index; the index wraps around
+pub fn chunk(item_ids: &[i32], index: usize) -> &[i32] {
+    let start = (index % chunk_count(item_ids.len())) * SESSION_LIMIT;
+    &item_ids[start..(start + SESSION_LIMIT).min(item_ids.len())]
+}
+
+// Pushes the next chunk of the bound set every interval, wrapping back to the first
+pub fn start(app: &AppHandle, state: &ShowcaseRotationState, binding: LiveSyncBinding, interval_minutes: u64) -> RotationInfo {
+    let LiveSyncBinding {
+        product_set_id,
+        shopee_account_id,
+        session_id,
+        email,
+        password,
+    } = binding;
+    state.stop(&session_id);
+
+    let interval_minutes = interval_minutes.max(MIN_ROTATION_MINUTES);
+    let cancel_session = session_id.clone();
+    let job = jobs::start_background(
+        app,
+        "showcase_rotation",
+        format!("Rotate set {} in session {}", product_set_id, session_id),
+        Some(shopee_account_id),
+        move |app| {
+            app.state::<ShowcaseRotationState>().stop(&cancel_session);
+        },
+    );
+    let info = RotationInfo {
+        session_id: session_id.clone(),
+        job_id: job.id().to_string(),
+        product_set_id,
+        shopee_account_id,
+        interval_minutes,
+        chunk: 0,
+    };
+
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            job.sleep(Duration::from_secs(interval_minutes * 60)).await;
+            job.checkpoint().await;
+            crate::blackout::wait_until_clear(&app).await;
+
+            let state = app.state::<ShowcaseRotationState>();
+            let Some(chunk) = state.advance(&session_id) else {
+                job.finish::<(), String>(&Ok(()));
+                return;
+            };
+            println!("[SHOWCASE] Rotating session {} to chunk {}", session_id, chunk);
+
+            let result =
+                crate::replace_products_request(&app, &email, &password, shopee_account_id, &session_id, product_set_id).await;
+            match &result {
+                Ok(_) => crate::session_reports::record_rotation(&app, &session_id),
+                Err(e) => crate::diagnostics::record_failure(&app, &session_id, "showcase_rotation", e),
+            }
+
+            let event = ShowcaseRotatedEvent {
+                session_id: session_id.clone(),
+                product_set_id,
+                chunk,
+                success: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            };
+            if let Err(e) = app.emit("showcase-rotated", event) {
+                eprintln!("Failed to emit showcase-rotated: {}", e);
+            }
+        }
+    });
+
+    state
+        .rotations
+        .lock()
+        .unwrap()
+        .insert(info.session_id.clone(), Rotation { info: info.clone(), task });
+    info
+}
@@ -0,0 +1,69 @@
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::db;
+use crate::storage;
+use crate::unix_now;
+use crate::LiveShowcaseItem;
+
+// Where snapshots were kept before the database
+const LEGACY_SNAPSHOT_FILE: &str = "showcase_snapshots.json";
+
+// What a session's showcase held right before the last replace, for rolling back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShowcaseSnapshot {
+    pub shopee_account_id: i32,
+    pub items: Vec<LiveShowcaseItem>,
+    pub taken_at: u64,
+}
+
+pub fn save(app: &AppHandle, session_id: &str, shopee_account_id: i32, items: Vec<LiveShowcaseItem>) -> Result<(), String> {
+    let snapshot = ShowcaseSnapshot {
+        shopee_account_id,
+        items,
+        taken_at: unix_now(),
+    };
+    restore(app, session_id, snapshot)
+}
+
+pub fn take(app: &AppHandle, session_id: &str) -> Result<Option<ShowcaseSnapshot>, String> {
+    db::with(app, |conn| {
+        let tx = conn.transaction()?;
+        let data: Option<String> = tx
+            .query_row("SELECT data FROM showcase_snapshots WHERE session_id = ?1", [session_id], |row| row.get(0))
+            .optional()?;
+        tx.execute("DELETE FROM showcase_snapshots WHERE session_id = ?1", [session_id])?;
+        tx.commit()?;
+        data.map(|data| db::from_json(&data)).transpose()
+    })
+}
+
+// Puts a snapshot back, e.g. when restoring it failed
+pub fn restore(app: &AppHandle, session_id: &str, snapshot: ShowcaseSnapshot) -> Result<(), String> {
+    db::with(app, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO showcase_snapshots (session_id, shopee_account_id, taken_at, data) VALUES (?1, ?2, ?3, ?4)",
+            (session_id, snapshot.shopee_account_id, snapshot.taken_at as i64, db::to_json(&snapshot)?),
+        )
+        .map(|_| ())
+    })
+}
+
+pub fn import_legacy(app: &AppHandle) {
+    let snapshots = match storage::load_json::<HashMap<String, ShowcaseSnapshot>>(app, LEGACY_SNAPSHOT_FILE) {
+        Ok(snapshots) if !snapshots.is_empty() => snapshots,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("[SHOWCASE] Failed to read {}: {}", LEGACY_SNAPSHOT_FILE, e);
+            return;
+        }
+    };
+    let count = snapshots.len();
+    let imported = snapshots.into_iter().try_for_each(|(session_id, snapshot)| restore(app, &session_id, snapshot));
+    match imported.and_then(|_| storage::retire_file(app, LEGACY_SNAPSHOT_FILE)) {
+        Ok(()) => println!("[SHOWCASE] Imported {} showcase snapshots into the database", count),
+        Err(e) => eprintln!("[SHOWCASE] Failed to import showcase snapshots: {}", e),
+    }
+}
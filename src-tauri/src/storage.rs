@@ -0,0 +1,69 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+// Serializes read-modify-write cycles on local files
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+pub fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir)
+}
+
+pub fn read_file(app: &AppHandle, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let path = data_dir(app)?.join(name);
+    match fs::read(&path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", name, e)),
+    }
+}
+
+// Write through a temp file so a crash mid-write never leaves a truncated file behind
+pub fn write_file(app: &AppHandle, name: &str, bytes: &[u8]) -> Result<(), String> {
+    let path = data_dir(app)?.join(name);
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to replace {}: {}", name, e))
+}
+
+// Moves a file that has been imported elsewhere out of the way, keeping it as a backup
+pub fn retire_file(app: &AppHandle, name: &str) -> Result<(), String> {
+    let path = data_dir(app)?.join(name);
+    let retired = data_dir(app)?.join(format!("{}.imported", name));
+    fs::rename(&path, &retired).map_err(|e| format!("Failed to retire {}: {}", name, e))
+}
+
+pub fn load_json<T: DeserializeOwned + Default>(app: &AppHandle, name: &str) -> Result<T, String> {
+    match read_file(app, name)? {
+        Some(bytes) => serde_json::from_slice(&bytes).map_err(|e| format!("Failed to parse {}: {}", name, e)),
+        None => Ok(T::default()),
+    }
+}
+
+pub fn save_json<T: Serialize>(app: &AppHandle, name: &str, value: &T) -> Result<(), String> {
+    let bytes = serde_json::to_vec_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+    write_file(app, name, &bytes)
+}
+
+pub fn update_json<T, R>(app: &AppHandle, name: &str, f: impl FnOnce(&mut T) -> R) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let _guard = lock();
+    let mut value: T = load_json(app, name)?;
+    let result = f(&mut value);
+    save_json(app, name, &value)?;
+    Ok(result)
+}
+
+pub fn lock() -> std::sync::MutexGuard<'static, ()> {
+    WRITE_LOCK.lock().unwrap()
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use tauri::AppHandle;
+
+use crate::settings::{self, RetentionPolicy};
+use crate::{db, image_cache, migrations, storage};
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageCategory {
+    ImageCache,
+    ProductMetadata,
+    ShowcaseSnapshots,
+    SessionReports,
+    // Viewer polling samples for charts; kept as long as session reports
+    LiveSamples,
+    JobHistory,
+    // Copies taken before data migrations and files retired after moving into the database
+    OldBackups,
+}
+
+const ALL_CATEGORIES: &[StorageCategory] = &[
+    StorageCategory::ImageCache,
+    StorageCategory::ProductMetadata,
+    StorageCategory::ShowcaseSnapshots,
+    StorageCategory::SessionReports,
+    StorageCategory::LiveSamples,
+    StorageCategory::JobHistory,
+    StorageCategory::OldBackups,
+];
+
+impl StorageCategory {
+    // Database table and the column holding each row's age
+    fn table(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            StorageCategory::ProductMetadata => Some(("product_metadata", "fetched_at")),
+            StorageCategory::ShowcaseSnapshots => Some(("showcase_snapshots", "taken_at")),
+            StorageCategory::SessionReports => Some(("session_reports", "ended_at")),
+            StorageCategory::LiveSamples => Some(("live_samples", "at")),
+            StorageCategory::JobHistory => Some(("job_history", "finished_at")),
+            StorageCategory::ImageCache | StorageCategory::OldBackups => None,
+        }
+    }
+
+    fn retention_days(self, policy: &RetentionPolicy) -> u64 {
+        match self {
+            StorageCategory::ImageCache => policy.image_cache_days,
+            StorageCategory::ProductMetadata => policy.product_metadata_days,
+            StorageCategory::ShowcaseSnapshots => policy.showcase_snapshot_days,
+            StorageCategory::SessionReports | StorageCategory::LiveSamples => policy.session_report_days,
+            StorageCategory::JobHistory => policy.job_history_days,
+            StorageCategory::OldBackups => policy.old_backup_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryUsage {
+    pub category: StorageCategory,
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageUsage {
+    // Everything in the app data dir, including files no category covers
+    pub total_bytes: u64,
+    pub database_bytes: u64,
+    pub categories: Vec<CategoryUsage>,
+}
+
+// Categories default to all of them, retention to the policy in settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CleanupOptions {
+    pub categories: Option<Vec<StorageCategory>>,
+    pub retention: Option<RetentionPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupSummary {
+    pub removed: Vec<CategoryUsage>,
+    pub bytes_freed: u64,
+}
+
+fn disk_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| disk_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+fn modified_at(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+// Migration backup directories and retired `.imported` files
+fn old_backups(app: &AppHandle) -> Result<Vec<std::path::PathBuf>, String> {
+    let dir = storage::data_dir(app)?;
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read data dir: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "imported"))
+        .collect();
+    if let Ok(entries) = fs::read_dir(dir.join(migrations::BACKUP_DIR)) {
+        paths.extend(entries.flatten().map(|entry| entry.path()));
+    }
+    Ok(paths)
+}
+
+fn table_usage(app: &AppHandle, table: &str, age_column: &str, cutoff: Option<u64>) -> Result<(usize, u64), String> {
+    let sql = format!(
+        "SELECT COUNT(*), COALESCE(SUM(LENGTH(data)), 0) FROM {} WHERE ?1 IS NULL OR {} < ?1",
+        table, age_column
+    );
+    db::with(app, |conn| {
+        conn.query_row(&sql, [cutoff.map(|c| c as i64)], |row| {
+            Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as u64))
+        })
+    })
+}
+
+fn category_usage(app: &AppHandle, category: StorageCategory) -> Result<CategoryUsage, String> {
+    let (entries, bytes) = match category.table() {
+        Some((table, age_column)) => table_usage(app, table, age_column, None)?,
+        None if category == StorageCategory::ImageCache => image_cache::usage(app)?,
+        None => {
+            let paths = old_backups(app)?;
+            (paths.len(), paths.iter().map(|p| disk_size(p)).sum())
+        }
+    };
+    Ok(CategoryUsage { category, entries, bytes })
+}
+
+pub fn usage(app: &AppHandle) -> Result<StorageUsage, String> {
+    let dir = storage::data_dir(app)?;
+    let database_bytes = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read data dir: {}", e))?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(db::DB_FILE))
+        .map(|entry| disk_size(&entry.path()))
+        .sum();
+    let categories = ALL_CATEGORIES
+        .iter()
+        .map(|category| category_usage(app, *category))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(StorageUsage {
+        total_bytes: disk_size(&dir),
+        database_bytes,
+        categories,
+    })
+}
+
+fn prune(app: &AppHandle, category: StorageCategory, cutoff: u64) -> Result<CategoryUsage, String> {
+    let (entries, bytes) = match category.table() {
+        Some((table, age_column)) => {
+            let removed = table_usage(app, table, age_column, Some(cutoff))?;
+            let sql = format!("DELETE FROM {} WHERE {} < ?1", table, age_column);
+            db::with(app, |conn| conn.execute(&sql, [cutoff as i64]))?;
+            removed
+        }
+        None if category == StorageCategory::ImageCache => image_cache::prune(app, cutoff)?,
+        None => {
+            let mut removed = (0, 0);
+            for path in old_backups(app)?.into_iter().filter(|p| modified_at(p) < cutoff) {
+                let size = disk_size(&path);
+                let result = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+                match result {
+                    Ok(()) => removed = (removed.0 + 1, removed.1 + size),
+                    Err(e) => eprintln!("[STORAGE] Failed to remove {}: {}", path.display(), e),
+                }
+            }
+            removed
+        }
+    };
+    Ok(CategoryUsage { category, entries, bytes })
+}
+
+// Removes data older than its retention period. The database is compacted afterwards so the
+// space is actually given back.
+pub fn cleanup(app: &AppHandle, options: CleanupOptions) -> Result<CleanupSummary, String> {
+    let policy = match options.retention {
+        Some(policy) => {
+            policy.validate()?;
+            policy
+        }
+        None => settings::current().retention,
+    };
+    let categories = options.categories.unwrap_or_else(|| ALL_CATEGORIES.to_vec());
+
+    let now = crate::unix_now();
+    let mut removed = Vec::new();
+    for category in categories {
+        // One second ahead so a retention of 0 also takes entries written this second
+        let cutoff = (now + 1).saturating_sub(category.retention_days(&policy) * DAY_SECS);
+        removed.push(prune(app, category, cutoff)?);
+    }
+
+    let pruned_rows = removed.iter().any(|r| r.category.table().is_some() && r.entries > 0);
+    if pruned_rows {
+        if let Err(e) = db::with(app, |conn| conn.execute_batch("VACUUM")) {
+            eprintln!("[STORAGE] Failed to compact database: {}", e);
+        }
+    }
+    let bytes_freed = removed.iter().map(|r| r.bytes).sum();
+    println!("[STORAGE] Cleanup freed {} bytes", bytes_freed);
+    Ok(CleanupSummary { removed, bytes_freed })
+}
@@ -0,0 +1,109 @@
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{ApiResponse, CommandError};
+
+const RELEASES_ENDPOINT: &str = "/api/app-releases/latest";
+// Leaves startup to finish before the first check
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(30);
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    version: String,
+    changelog: Option<String>,
+    download_url: Option<String>,
+    // Older versions no longer work with the API and must update
+    min_supported_version: Option<String>,
+    published_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+    // The running version is below the backend's minimum supported version
+    pub mandatory: bool,
+    pub changelog: Option<String>,
+    pub download_url: Option<String>,
+    pub published_at: Option<String>,
+}
+
+#[derive(Default)]
+pub struct UpdateState {
+    // Version the update-required event was last sent for, so it's sent once per release
+    reported: Mutex<Option<String>>,
+}
+
+fn parse_version(version: &str) -> Result<Version, String> {
+    Version::parse(version.trim().trim_start_matches('v')).map_err(|e| format!("Invalid version {}: {}", version, e))
+}
+
+// Only https links are offered, so a bad release entry can't point the user somewhere unsafe
+fn safe_download_url(url: Option<String>) -> Option<String> {
+    url.filter(|u| url::Url::parse(u).is_ok_and(|parsed| parsed.scheme() == "https"))
+}
+
+// Asks the backend for the latest release for this platform and compares it with the running
+// version. Emits `update-required` the first time a mandatory update is seen.
+pub async fn check(app: &AppHandle) -> Result<UpdateInfo, CommandError> {
+    let current = app.package_info().version.clone();
+    let query = format!(
+        "app_identifier=botgacor&platform={}-{}&current_version={}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        current
+    );
+    let response: ApiResponse<Release> = crate::make_api_request("GET", RELEASES_ENDPOINT, None, Some(&query)).await?;
+    if !response.success {
+        return Err(response.message.unwrap_or_else(|| "Failed to check for updates".to_string()).into());
+    }
+    let release = response.data.ok_or_else(|| CommandError::from("No release in response"))?;
+
+    let latest = parse_version(&release.version)?;
+    let mandatory = match release.min_supported_version.as_deref() {
+        Some(minimum) => current < parse_version(minimum)?,
+        None => false,
+    };
+    let info = UpdateInfo {
+        current_version: current.to_string(),
+        latest_version: latest.to_string(),
+        update_available: latest > current,
+        mandatory,
+        changelog: release.changelog,
+        download_url: safe_download_url(release.download_url),
+        published_at: release.published_at,
+    };
+
+    if info.mandatory {
+        let state = app.state::<UpdateState>();
+        let mut reported = state.reported.lock().unwrap();
+        if reported.as_deref() != Some(info.latest_version.as_str()) {
+            println!("[UPDATES] Version {} is no longer supported, {} is required", info.current_version, info.latest_version);
+            if let Err(e) = app.emit("update-required", info.clone()) {
+                eprintln!("Failed to emit update-required: {}", e);
+            }
+            *reported = Some(info.latest_version.clone());
+        }
+    }
+    Ok(info)
+}
+
+// Checks shortly after startup and then every few hours, so a mandatory update is noticed
+// even when the app is left running in the tray
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_CHECK_DELAY).await;
+        loop {
+            if let Err(e) = check(&app).await {
+                eprintln!("[UPDATES] Update check failed: {}", e);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
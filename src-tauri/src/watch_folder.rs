@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+use crate::{product_import, storage};
+
+const CONFIG_FILE: &str = "watch_folder.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchFolderConfig {
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WatchFolderEvent {
+    file: String,
+    product_set_id: Option<i32>,
+    product_set_name: String,
+    items: usize,
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct WatchFolderState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WatchFolderState {
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+}
+
+pub fn load_config(app: &AppHandle) -> Result<WatchFolderConfig, String> {
+    storage::load_json(app, CONFIG_FILE)
+}
+
+fn pending_csvs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case("csv")))
+        .collect();
+    files.sort();
+    files
+}
+
+// Move a handled file into processed/ or failed/ so it is never picked up twice
+fn archive(file: &Path, subfolder: &str) {
+    let Some(parent) = file.parent() else {
+        return;
+    };
+    let target_dir = parent.join(subfolder);
+    if let Err(e) = std::fs::create_dir_all(&target_dir) {
+        eprintln!("[WATCH FOLDER] Failed to create {}: {}", target_dir.display(), e);
+        return;
+    }
+    let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let target = target_dir.join(format!("{}-{}", crate::unix_now(), name));
+    if let Err(e) = std::fs::rename(file, &target) {
+        eprintln!("[WATCH FOLDER] Failed to archive {}: {}", file.display(), e);
+    }
+}
+
+// Imports one CSV into the product set named after the file, creating the set if needed
+async fn import_file(app: &AppHandle, email: &str, password: &str, file: &Path, set_name: &str) -> Result<(i32, usize), String> {
+    let contents = std::fs::read_to_string(file).map_err(|e| format!("Failed to read file: {}", e))?;
+    let rows = product_import::parse_csv(&contents)?;
+    if rows.is_empty() {
+        return Err("File has no product URLs".to_string());
+    }
+
+    let existing = crate::get_product_sets(email.to_string(), password.to_string())
+        .await
+        .map_err(|e| e.to_string())?
+        .product_sets
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(set_name));
+    let product_set_id = match existing {
+        Some(set) => set.id,
+        None => {
+            crate::create_product_set(app.clone(), email.to_string(), password.to_string(), set_name.to_string(), None, None)
+                .await
+                .map_err(|e| e.to_string())?
+                .id
+        }
+    };
+
+    let summary = crate::add_product_set_items(app.clone(), email.to_string(), password.to_string(), product_set_id, rows)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok((product_set_id, summary.added))
+}
+
+async fn scan(app: &AppHandle, email: &str, password: &str, dir: &Path) {
+    for file in pending_csvs(dir) {
+        let file_name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let set_name = file.file_stem().map(|n| n.to_string_lossy().trim().to_string()).unwrap_or_default();
+
+        println!("[WATCH FOLDER] Importing {} into '{}'", file_name, set_name);
+        let result = import_file(app, email, password, &file, &set_name).await;
+        archive(&file, if result.is_ok() { "processed" } else { "failed" });
+
+        let event = WatchFolderEvent {
+            file: file_name,
+            product_set_id: result.as_ref().ok().map(|(id, _)| *id),
+            product_set_name: set_name,
+            items: result.as_ref().map(|(_, n)| *n).unwrap_or(0),
+            success: result.is_ok(),
+            error: result.err(),
+        };
+        if let Err(e) = app.emit("watch-folder-imported", event) {
+            eprintln!("Failed to emit watch-folder-imported: {}", e);
+        }
+    }
+}
+
+pub fn start(app: &AppHandle, state: &WatchFolderState, email: String, password: String, path: String) -> Result<(), String> {
+    let dir = PathBuf::from(&path);
+    if !dir.is_dir() {
+        return Err(format!("{} is not a folder", path));
+    }
+    storage::save_json(app, CONFIG_FILE, &WatchFolderConfig { path: Some(path) })?;
+
+    state.stop();
+    let app = app.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            scan(&app, &email, &password, &dir).await;
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+    *state.task.lock().unwrap() = Some(task);
+    Ok(())
+}
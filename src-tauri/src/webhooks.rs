@@ -0,0 +1,227 @@
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::encrypted_store;
+use crate::notifications::NotificationEvent;
+
+// Encrypted because it holds each webhook's signing secret
+const WEBHOOKS_FILE: &str = "webhooks.enc";
+const MAX_WEBHOOKS: usize = 20;
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+const SIGNATURE_HEADER: &str = "X-Botgacor-Signature";
+const EVENT_HEADER: &str = "X-Botgacor-Event";
+
+// A URL the app POSTs events to, e.g. a Zapier or n8n hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub description: Option<String>,
+    // Events sent to this URL; empty means all of them
+    #[serde(default)]
+    pub events: BTreeSet<NotificationEvent>,
+    pub enabled: bool,
+    // Key for the HMAC-SHA256 signature of each body, hex-encoded in the signature header
+    pub secret: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookInput {
+    pub url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub events: BTreeSet<NotificationEvent>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+struct Payload<'a> {
+    event: NotificationEvent,
+    webhook_id: &'a str,
+    occurred_at: u64,
+    data: serde_json::Map<String, serde_json::Value>,
+}
+
+fn validate(input: &mut WebhookInput) -> Result<(), String> {
+    input.url = input.url.trim().to_string();
+    let parsed = url::Url::parse(&input.url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Webhook URL must be an http:// or https:// address".to_string());
+    }
+    input.description = input.description.take().map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    Ok(())
+}
+
+fn new_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+pub fn list(app: &AppHandle) -> Result<Vec<Webhook>, String> {
+    encrypted_store::load(app, WEBHOOKS_FILE)
+}
+
+pub fn add(app: &AppHandle, mut input: WebhookInput) -> Result<Webhook, String> {
+    validate(&mut input)?;
+    let now = crate::unix_now();
+    let webhook = Webhook {
+        id: format!("wh-{}-{}", now, &new_secret()[..8]),
+        url: input.url,
+        description: input.description,
+        events: input.events,
+        enabled: input.enabled,
+        secret: new_secret(),
+        created_at: now,
+    };
+    encrypted_store::update(app, WEBHOOKS_FILE, |webhooks: &mut Vec<Webhook>| {
+        if webhooks.len() >= MAX_WEBHOOKS {
+            return Err(format!("At most {} webhooks can be registered", MAX_WEBHOOKS));
+        }
+        webhooks.push(webhook.clone());
+        Ok(())
+    })??;
+    Ok(webhook)
+}
+
+// Changes the URL, description and filters, keeping the ID and secret
+pub fn update(app: &AppHandle, id: &str, mut input: WebhookInput) -> Result<Webhook, String> {
+    validate(&mut input)?;
+    encrypted_store::update(app, WEBHOOKS_FILE, |webhooks: &mut Vec<Webhook>| {
+        let webhook = webhooks.iter_mut().find(|w| w.id == id).ok_or_else(|| format!("Webhook {} not found", id))?;
+        webhook.url = input.url;
+        webhook.description = input.description;
+        webhook.events = input.events;
+        webhook.enabled = input.enabled;
+        Ok(webhook.clone())
+    })?
+}
+
+// Issues a new signing secret, e.g. after it leaked
+pub fn rotate_secret(app: &AppHandle, id: &str) -> Result<Webhook, String> {
+    encrypted_store::update(app, WEBHOOKS_FILE, |webhooks: &mut Vec<Webhook>| {
+        let webhook = webhooks.iter_mut().find(|w| w.id == id).ok_or_else(|| format!("Webhook {} not found", id))?;
+        webhook.secret = new_secret();
+        Ok(webhook.clone())
+    })?
+}
+
+pub fn remove(app: &AppHandle, id: &str) -> Result<bool, String> {
+    encrypted_store::update(app, WEBHOOKS_FILE, |webhooks: &mut Vec<Webhook>| {
+        let before = webhooks.len();
+        webhooks.retain(|w| w.id != id);
+        webhooks.len() != before
+    })
+}
+
+// Retries network errors and 5xx responses; a 4xx means the receiver rejected the payload
+async fn deliver(client: &reqwest::Client, webhook: &Webhook, event: NotificationEvent, body: Vec<u8>) -> Result<(), String> {
+    let signature = sign(&webhook.secret, &body);
+    let event_name = serde_json::to_value(event).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default();
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_ATTEMPTS {
+        let result = client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &signature)
+            .header(EVENT_HEADER, &event_name)
+            .body(body.clone())
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if response.status().is_client_error() => {
+                return Err(format!("Webhook returned HTTP {}", response.status()));
+            }
+            Ok(response) => last_error = format!("Webhook returned HTTP {}", response.status()),
+            Err(e) => last_error = format!("Failed to reach webhook: {}", e),
+        }
+        if attempt < MAX_ATTEMPTS {
+            tokio::time::sleep(RETRY_DELAY * attempt).await;
+        }
+    }
+    Err(last_error)
+}
+
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(crate::settings::current().request_timeout())
+        .build()
+        .map_err(|e| format!("Failed to create client: {}", e))
+}
+
+fn payload(webhook: &Webhook, event: NotificationEvent, fields: &[(&str, String)]) -> Result<Vec<u8>, String> {
+    let payload = Payload {
+        event,
+        webhook_id: &webhook.id,
+        occurred_at: crate::unix_now(),
+        data: fields.iter().map(|(name, value)| (name.to_string(), value.clone().into())).collect(),
+    };
+    serde_json::to_vec(&payload).map_err(|e| format!("Failed to serialize webhook payload: {}", e))
+}
+
+// Sends the event to every enabled webhook whose filter matches, in the background
+pub fn dispatch(app: &AppHandle, event: NotificationEvent, fields: &[(&str, String)]) {
+    let webhooks = match list(app) {
+        Ok(webhooks) => webhooks,
+        Err(e) => {
+            eprintln!("[WEBHOOKS] Failed to load webhooks: {}", e);
+            return;
+        }
+    };
+    let matching: Vec<(Webhook, Vec<u8>)> = webhooks
+        .into_iter()
+        .filter(|w| w.enabled && (w.events.is_empty() || w.events.contains(&event)))
+        .filter_map(|w| match payload(&w, event, fields) {
+            Ok(body) => Some((w, body)),
+            Err(e) => {
+                eprintln!("[WEBHOOKS] {}", e);
+                None
+            }
+        })
+        .collect();
+    if matching.is_empty() {
+        return;
+    }
+    let client = match client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("[WEBHOOKS] {}", e);
+            return;
+        }
+    };
+    for (webhook, body) in matching {
+        let client = client.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = deliver(&client, &webhook, event, body).await {
+                eprintln!("[WEBHOOKS] Delivery to {} failed: {}", webhook.id, e);
+            }
+        });
+    }
+}
+
+// Sends a sample JobDone event to one webhook, whatever its filter, and waits for the result
+pub async fn test(app: &AppHandle, id: &str) -> Result<(), String> {
+    let webhook = list(app)?.into_iter().find(|w| w.id == id).ok_or_else(|| format!("Webhook {} not found", id))?;
+    let fields = [("label", "Test event".to_string()), ("test", "true".to_string())];
+    let body = payload(&webhook, NotificationEvent::JobDone, &fields)?;
+    deliver(&client()?, &webhook, NotificationEvent::JobDone, body).await
+}
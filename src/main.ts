@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 
 // ==================== Types ====================
 
@@ -86,17 +87,18 @@ function byId<T extends HTMLElement>(id: string): T {
   return el as T;
 }
 
-function showToast(message: string, type: "success" | "error" | "info" = "info") {
+function showToast(message: string, type: "success" | "error" | "warning" | "info" = "info") {
   const container = byId<HTMLDivElement>("toast-container");
   const toast = document.createElement("div");
   
   const colors = {
     success: "border-green-200 bg-green-50 text-green-800",
     error: "border-red-200 bg-red-50 text-red-800",
+    warning: "border-yellow-200 bg-yellow-50 text-yellow-800",
     info: "border-blue-200 bg-blue-50 text-blue-800",
   };
   
-  toast.className = `rounded-lg border p-3 text-sm shadow-lg ${colors[type]}`;
+  toast.className = `rounded-lg border p-3 text-sm shadow-lg whitespace-pre-line ${colors[type]}`;
   toast.textContent = message;
   
   container.appendChild(toast);
@@ -106,6 +108,32 @@ function showToast(message: string, type: "success" | "error" | "info" = "info")
   }, 5000);
 }
 
+interface AggregateError {
+  kind: "aggregate";
+  total: number;
+  succeeded: number;
+  errors: { index: number; target: string; error: unknown }[];
+  message: string;
+}
+
+function isAggregateError(error: unknown): error is AggregateError {
+  return (error as { kind?: string } | null)?.kind === "aggregate";
+}
+
+// Items that didn't make it into the showcase are listed instead of reporting failure; the
+// rest of the set is live. Targets are product set item IDs.
+function warnReplaceFailures(productSet: ProductSet, error: unknown): boolean {
+  if (!isAggregateError(error)) {
+    return false;
+  }
+  const lines = error.errors.map(failure => {
+    const item = productSet.items.find(item => String(item.id) === failure.target);
+    return `${item?.url ?? `Item ${failure.target}`}: ${String(failure.error)}`;
+  });
+  showToast(`${error.errors.length} dari ${error.total} produk gagal ditambahkan ke "${productSet.name}":\n${lines.join("\n")}`, "warning");
+  return true;
+}
+
 async function showStep(step: number) {
   for (let i = 0; i <= 3; i++) {
     const stepEl = byId(`step-${i}`);
@@ -210,6 +238,19 @@ function confirmDialog(message: string): Promise<boolean> {
   });
 }
 
+// Destructive commands first return a short-lived token plus an impact summary; the
+// action only runs when the token is sent back. With `ask`, the impact is shown first.
+async function invokeConfirmed<T>(command: string, args: Record<string, unknown>, ask = true): Promise<T | null> {
+  const outcome = await invoke<{ status: string; token?: string; impact?: { summary: string } }>(command, args);
+  if (outcome?.status !== "confirmation_required") {
+    return outcome as T;
+  }
+  if (ask && !(await confirmDialog(outcome.impact?.summary ?? "Lanjutkan?"))) {
+    return null;
+  }
+  return invoke<T>(command, { ...args, confirmationToken: outcome.token });
+}
+
 // ==================== Step 0: Login ====================
 
 async function handleLogin(event: Event) {
@@ -591,14 +632,15 @@ function renderShopeeAccounts(accounts: ShopeeAccount[]) {
     card.querySelector(".btn-delete-account")?.addEventListener("click", async (e) => {
       e.stopPropagation();
       if (!state.currentUser) return;
-      if (!confirm(`Yakin ingin menghapus akun "${account.name}"?`)) return;
       
       try {
-        await invoke("delete_shopee_account", {
+        // The backend's impact summary is shown for confirmation; null means the user declined
+        const result = await invokeConfirmed("delete_shopee_account", {
           email: state.currentUser.email,
           password: state.currentPassword,
           accountId: account.id,
         });
+        if (result === null) return;
         loadShopeeAccounts();
       } catch (error) {
         showToast(`Gagal menghapus akun: ${error}`, "error");
@@ -641,12 +683,24 @@ async function generateQR() {
     // Poll for status
     if (qrStatusInterval) clearInterval(qrStatusInterval);
     
+    let currentQrId = qrcode_id;
     qrStatusInterval = window.setInterval(async () => {
       try {
-        const { status: qrStatus, qrcode_token } = await invoke<{ status: string; qrcode_token?: string }>("check_qr_status", { qrcodeId: qrcode_id });
+        const { status: qrStatus, qrcode_token, regenerated } = await invoke<{
+          status: string;
+          qrcode_token?: string;
+          regenerated?: { qrcode_id: string; qrcode_base64: string } | null;
+        }>("check_qr_status", { qrcodeId: currentQrId });
         statusText.textContent = qrStatus;
         
-        if (qrStatus === "CONFIRMED" && qrcode_token) {
+        if (qrStatus === "EXPIRED" && regenerated) {
+          // The backend already made a replacement; show it and poll that one instead
+          currentQrId = regenerated.qrcode_id;
+          img.src = `data:image/png;base64,${regenerated.qrcode_base64}`;
+          statusText.textContent = "WAITING";
+          statusMsg.className = "mb-4 rounded-lg border border-green-300 bg-green-50 p-3 text-sm text-green-800";
+          statusMsg.textContent = "QR Code diperbarui. Scan QR baru dengan aplikasi Shopee Anda.";
+        } else if (qrStatus === "CONFIRMED" && qrcode_token) {
           clearInterval(qrStatusInterval!);
           await handleQRConfirmed(qrcode_token);
         } else if (qrStatus === "SUCCESS" && qrcode_token) {
@@ -694,13 +748,7 @@ async function handleQRConfirmed(qrcode_token: string) {
         statusMsg.textContent = `Menyimpan akun "${accountName}"...`;
         
         if (state.currentUser) {
-          await invoke<{ success: boolean; data: ShopeeAccount }>("add_shopee_account", {
-            email: state.currentUser.email,
-            password: state.currentPassword,
-            name: accountName,
-            cookie: loginResult.cookies,
-            isActive: true,
-          });
+          await saveShopeeAccount(accountName, loginResult.cookies);
           
           statusMsg.textContent = `✅ Akun "${accountName}" berhasil ditambahkan!`;
           statusMsg.className = "mb-4 rounded-lg border border-green-300 bg-green-50 p-3 text-sm text-green-800";
@@ -722,6 +770,34 @@ async function handleQRConfirmed(qrcode_token: string) {
   }
 }
 
+// Adds the account, or refreshes the cookie of the stored copy when it is already there
+async function saveShopeeAccount(name: string, cookie: string) {
+  if (!state.currentUser) return;
+  try {
+    await invoke<ShopeeAccount>("add_shopee_account", {
+      email: state.currentUser.email,
+      password: state.currentPassword,
+      name,
+      cookie,
+      isActive: true,
+    });
+  } catch (error) {
+    const duplicate = error as { kind?: string; existing_id?: number };
+    if (duplicate?.kind !== "duplicate_account" || duplicate.existing_id === undefined) throw error;
+    if (!confirm("Akun Shopee ini sudah ditambahkan. Perbarui cookie akun yang sudah ada?")) {
+      throw "Akun sudah ada";
+    }
+    await invoke<ShopeeAccount>("update_shopee_account", {
+      email: state.currentUser.email,
+      password: state.currentPassword,
+      accountId: duplicate.existing_id,
+      name,
+      cookie,
+      isActive: true,
+    });
+  }
+}
+
 async function handleAddAccountManual(event: Event) {
   event.preventDefault();
   
@@ -731,13 +807,7 @@ async function handleAddAccountManual(event: Event) {
   if (!state.currentUser) return;
   
   try {
-    await invoke<{ success: boolean; data: ShopeeAccount }>("add_shopee_account", {
-      email: state.currentUser.email,
-      password: state.currentPassword,
-      name,
-      cookie,
-      isActive: true,
-    });
+    await saveShopeeAccount(name, cookie);
     
     hideModal("modal-add-account-manual");
     showToast("Akun berhasil ditambahkan!", "success");
@@ -859,14 +929,6 @@ function renderNiches(niches: Niche[]) {
         e.preventDefault();
         console.log("Delete niche clicked:", niche.id, niche.name);
         
-        const confirmed = await confirmDialog(`Yakin ingin menghapus niche "${niche.name}"?`);
-        console.log("Confirm result:", confirmed);
-        
-        if (!confirmed) {
-          console.log("User cancelled delete niche");
-          return;
-        }
-        
         if (!state.currentUser) {
           console.error("No current user");
           return;
@@ -876,11 +938,15 @@ function renderNiches(niches: Niche[]) {
         console.log("Deleting niche:", niche.id);
         
         try {
-          const result = await invoke("delete_niche", {
+          const result = await invokeConfirmed("delete_niche", {
             email: state.currentUser.email,
             password: state.currentPassword,
             nicheId: niche.id,
           });
+          if (result === null) {
+            console.log("User cancelled delete niche");
+            return;
+          }
           console.log("Niche deleted successfully, result:", result);
           showToast("Niche berhasil dihapus", "success");
           
@@ -1322,23 +1388,27 @@ async function handleProductSetForm(event: Event) {
       
       // Clear existing items and add new ones
       if (urls.length > 0) {
+        let cleared = true;
         try {
-          await invoke("clear_product_set_items", {
+          cleared = (await invokeConfirmed("clear_product_set_items", {
             email: state.currentUser.email,
             password: state.currentPassword,
             productSetId,
-          });
+          })) !== null;
         } catch (clearError) {
           // Ignore clear errors, might not have items
         }
         
-        const items = urls.map(url => ({ url }));
-        await invoke("add_product_set_items", {
-          email: state.currentUser.email,
-          password: state.currentPassword,
-          productSetId,
-          items,
-        });
+        // Adding on top of items the user chose to keep would duplicate them
+        if (cleared) {
+          const items = urls.map(url => ({ url }));
+          await invoke("add_product_set_items", {
+            email: state.currentUser.email,
+            password: state.currentPassword,
+            productSetId,
+            items,
+          });
+        }
       }
       
       showToast("Product set berhasil diupdate", "success");
@@ -1614,15 +1684,25 @@ async function switchToProductSet(index: number) {
     }
     
     // Use the single session ID
-    await invoke("replace_products", {
-      email: state.currentUser.email,
-      password: state.currentPassword,
-      shopeeAccountId: state.selectedAccount.id,
-      sessionId: state.sessionId,
-      productSetId: productSet.id,
-    });
+    let partial = false;
+    try {
+      await invoke("replace_products", {
+        email: state.currentUser.email,
+        password: state.currentPassword,
+        shopeeAccountId: state.selectedAccount.id,
+        sessionId: state.sessionId,
+        productSetId: productSet.id,
+      });
+    } catch (error) {
+      if (!warnReplaceFailures(productSet, error)) {
+        throw error;
+      }
+      partial = true;
+    }
     
-    showToast(`Switched ke product set "${productSet.name}"`, "success");
+    if (!partial) {
+      showToast(`Switched ke product set "${productSet.name}"`, "success");
+    }
     byId("bot-status-text").textContent = "Berjalan";
     byId("bot-last-action").textContent = `Set "${productSet.name}" diaktifkan`;
     
@@ -2019,13 +2099,19 @@ async function startBot() {
       
       // Use the single session ID to replace products
       try {
-        await invoke("replace_products", {
-          email: state.currentUser!.email,
-          password: state.currentPassword,
-          shopeeAccountId: state.selectedAccount!.id,
-          sessionId: sessionId,
-          productSetId: productSet.id,
-        });
+        try {
+          await invoke("replace_products", {
+            email: state.currentUser!.email,
+            password: state.currentPassword,
+            shopeeAccountId: state.selectedAccount!.id,
+            sessionId: sessionId,
+            productSetId: productSet.id,
+          });
+        } catch (error) {
+          if (!warnReplaceFailures(productSet, error)) {
+            throw error;
+          }
+        }
         
         state.errorCount = 0; // Reset on success
         
@@ -2170,9 +2256,44 @@ function stopBot() {
   renderProductSetsRotation();
 }
 
+// ==================== Remote Assist ====================
+
+function renderRemoteAssistBanner(session: { support_code: string; expires_at: number } | null) {
+  let banner = document.getElementById("remote-assist-banner");
+  if (!session) {
+    banner?.remove();
+    return;
+  }
+  if (!banner) {
+    banner = document.createElement("div");
+    banner.id = "remote-assist-banner";
+    banner.className = "fixed top-0 inset-x-0 z-50 flex items-center justify-center gap-3 bg-blue-600 px-4 py-2 text-sm text-white";
+    document.body.appendChild(banner);
+  }
+  const until = new Date(session.expires_at * 1000).toLocaleTimeString();
+  banner.innerHTML = `Remote assist aktif — kode support <b>${session.support_code}</b> (berakhir ${until}) `;
+  const stop = document.createElement("button");
+  stop.className = "rounded bg-white/20 px-2 py-0.5 hover:bg-white/30";
+  stop.textContent = "Hentikan";
+  stop.addEventListener("click", () => invoke("stop_remote_assist"));
+  banner.appendChild(stop);
+}
+
 // ==================== Event Listeners ====================
 
 window.addEventListener("DOMContentLoaded", () => {
+  // Cached account list was reconciled with the server and changed
+  listen<{ cache: string; accounts: ShopeeAccount[] }>("cache-updated", (event) => {
+    if (event.payload.cache === "shopee_accounts" && state.currentUser) {
+      renderShopeeAccounts(event.payload.accounts);
+    }
+  });
+
+  // Remote assist indicator stays visible for as long as support can see snapshots
+  listen<{ active: boolean; session: { support_code: string; expires_at: number } | null }>("remote-assist-changed", (event) => {
+    renderRemoteAssistBanner(event.payload.active ? event.payload.session : null);
+  });
+
   // Step 0
   byId("login-form").addEventListener("submit", handleLogin);
   byId("btn-redeem-license").addEventListener("click", () => showModal("modal-redeem-license"));